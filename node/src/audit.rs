@@ -0,0 +1,86 @@
+//! The `audit-storage` subcommand, checking the course storage of the best block for corruption.
+
+use node_uni_runtime::{opaque::Block, AccountId};
+use pallet_course_passing_runtime_api::CourseAuditApi;
+use sc_cli::{CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::generic::BlockId;
+use std::sync::Arc;
+
+/// Check the course storage of the best block for inconsistencies.
+#[derive(Debug, clap::Parser)]
+pub struct AuditStorageCmd {
+	/// The number of storage entries checked per runtime call.
+	#[clap(long, default_value = "1000")]
+	pub page_size: u32,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+}
+
+impl AuditStorageCmd {
+	/// Print every inconsistency of the course storage, failing if there is any.
+	pub fn run<C>(&self, client: Arc<C>) -> sc_cli::Result<()>
+	where
+		C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+		C::Api: CourseAuditApi<Block, AccountId>,
+	{
+		let best = client.info().best_hash;
+		let at = BlockId::Hash(best);
+		let api = client.runtime_api();
+
+		let mut cursor = None;
+		let mut courses = 0u64;
+		let mut owners = 0u64;
+		let mut found = 0usize;
+		let course_count = loop {
+			let page = api
+				.audit_storage(&at, cursor, self.page_size)
+				.map_err(|e| format!("Failed to audit the storage: {:?}", e))?;
+			for inconsistency in &page.inconsistencies {
+				println!("{:?}", inconsistency);
+			}
+			found += page.inconsistencies.len();
+			courses += u64::from(page.courses_checked);
+			owners += u64::from(page.owners_checked);
+			cursor = page.next;
+			if cursor.is_none() {
+				break page.course_count
+			}
+		};
+		if courses != course_count {
+			println!("CountForCourses is {} but {} courses are stored", course_count, courses);
+			found += 1;
+		}
+
+		println!("Checked {} courses and {} owners at block {:?}.", courses, owners, best);
+		if found > 0 {
+			return Err(format!("Found {} inconsistencies.", found).into())
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for AuditStorageCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+}
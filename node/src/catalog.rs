@@ -0,0 +1,143 @@
+//! The `import-catalog` subcommand, seeding the genesis courses of a chain spec from the course
+//! catalog of the registrar.
+
+use crate::chain_spec::{self, ChainSpec};
+use node_uni_runtime::{pallet_course_passing::CourseYear, CourseGradingConfig};
+use sc_cli::{CliConfiguration, SharedParams};
+use serde::Deserialize;
+use std::{collections::BTreeSet, fs, io::Write, path::PathBuf};
+
+/// Build a chain spec whose genesis courses are the courses of a catalog.
+#[derive(Debug, clap::Parser)]
+pub struct ImportCatalogCmd {
+	/// The catalog to import, as JSON if its extension is `.json` and as CSV otherwise.
+	#[clap(parse(from_os_str))]
+	pub catalog: PathBuf,
+
+	/// The file to write the chain spec to, standard output if omitted.
+	#[clap(long, short = 'o', parse(from_os_str))]
+	pub output: Option<PathBuf>,
+
+	/// Write the chain spec in raw storage format.
+	#[clap(long)]
+	pub raw: bool,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+}
+
+/// A course of the catalog.
+///
+/// In CSV catalogs these are the columns of the header row, in any order. Fields cannot be quoted,
+/// so they cannot contain commas.
+#[derive(Deserialize)]
+struct CatalogCourse {
+	/// Course code, e.g. `"CS101"`.
+	code: String,
+	/// Year of study, `1` to `4` or `First` to `Fourth`.
+	year: String,
+	/// Department offering the course, e.g. `"Computer Science"`.
+	department: String,
+	/// SS58 address of the account owning the course.
+	owner: String,
+}
+
+impl CatalogCourse {
+	fn year(&self) -> Result<CourseYear, String> {
+		match self.year.to_lowercase().as_str() {
+			"1" | "first" => Ok(CourseYear::First),
+			"2" | "second" => Ok(CourseYear::Second),
+			"3" | "third" => Ok(CourseYear::Third),
+			"4" | "fourth" => Ok(CourseYear::Fourth),
+			_ => Err(format!("Invalid year `{}` of course {}", self.year, self.code)),
+		}
+	}
+}
+
+fn parse_csv(catalog: &str) -> Result<Vec<CatalogCourse>, String> {
+	let mut lines = catalog.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+	let (_, header) = lines.next().ok_or_else(|| "The catalog is empty".to_string())?;
+	let header: Vec<_> = header.split(',').map(str::trim).collect();
+	let column = |name: &str| {
+		header
+			.iter()
+			.position(|column| *column == name)
+			.ok_or_else(|| format!("The catalog has no `{}` column", name))
+	};
+	let (code, year, department, owner) =
+		(column("code")?, column("year")?, column("department")?, column("owner")?);
+
+	lines
+		.map(|(number, line)| {
+			let fields: Vec<_> = line.split(',').map(str::trim).collect();
+			if fields.len() != header.len() {
+				return Err(format!(
+					"Line {} of the catalog has {} fields, expected {}",
+					number + 1,
+					fields.len(),
+					header.len()
+				))
+			}
+			Ok(CatalogCourse {
+				code: fields[code].to_string(),
+				year: fields[year].to_string(),
+				department: fields[department].to_string(),
+				owner: fields[owner].to_string(),
+			})
+		})
+		.collect()
+}
+
+impl ImportCatalogCmd {
+	/// Replace the genesis courses of `spec` with the courses of the catalog.
+	pub fn run(&self, spec: Box<dyn sc_service::ChainSpec>) -> sc_cli::Result<()> {
+		let catalog = fs::read_to_string(&self.catalog)?;
+		let catalog: Vec<CatalogCourse> =
+			if self.catalog.extension().map_or(false, |ext| ext == "json") {
+				serde_json::from_str(&catalog)
+					.map_err(|e| format!("Error parsing the catalog: {}", e))?
+			} else {
+				parse_csv(&catalog)?
+			};
+
+		let mut seen = BTreeSet::new();
+		let mut courses = Vec::with_capacity(catalog.len());
+		for course in &catalog {
+			if !seen.insert((&course.department, &course.code)) {
+				return Err(format!(
+					"Course {} of {} is listed more than once",
+					course.code, course.department
+				)
+				.into())
+			}
+			courses.push((
+				chain_spec::parse_account(&course.owner)?,
+				chain_spec::course_dna(&format!("{}/{}", course.department, course.code)),
+				course.year()?,
+			));
+		}
+
+		let mut json: serde_json::Value = serde_json::from_str(&spec.as_json(false)?)
+			.map_err(|e| format!("Error parsing the chain spec: {}", e))?;
+		let runtime = json
+			.pointer_mut("/genesis/runtime")
+			.ok_or_else(|| "The chain spec must not be raw".to_string())?;
+		runtime["courseGrading"] = serde_json::to_value(CourseGradingConfig { courses })
+			.map_err(|e| format!("Error serializing the courses: {}", e))?;
+
+		let spec = ChainSpec::from_json_bytes(json.to_string().into_bytes())?;
+		let spec = sc_service::ChainSpec::as_json(&spec, self.raw)?;
+		match &self.output {
+			Some(path) => fs::write(path, spec)?,
+			None => std::io::stdout().write_all(spec.as_bytes())?,
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ImportCatalogCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+}
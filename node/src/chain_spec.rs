@@ -1,19 +1,35 @@
 use node_uni_runtime::{
-	AccountId, AuraConfig, BalancesConfig, CourseGrading, CourseGradingConfig, GenesisConfig,
-	GrandpaConfig, Signature, SudoConfig, SystemConfig, WASM_BINARY,
+	opaque::SessionKeys,
+	pallet_catalog_referenda::Role,
+	pallet_course_passing::{dna::Dna, CourseYear},
+	AccountId, AssetsConfig, BabeConfig, BalancesConfig, CatalogReferendaConfig,
+	CourseGradingConfig, DidConfig, GenesisConfig, GrandpaConfig, SenateConfig, SessionConfig,
+	Signature, SystemConfig, TechnicalCommitteeConfig, ValidatorSetConfig,
+	BABE_GENESIS_EPOCH_CONFIG, UNI_EUR, WASM_BINARY,
 };
-use sc_service::ChainType;
-use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{sr25519, Pair, Public};
+use sc_service::{config::MultiaddrWithPeerId, ChainType};
+use serde::Deserialize;
+use sp_consensus_babe::AuthorityId as BabeId;
+use sp_core::{crypto::Ss58Codec, ed25519, hashing::blake2_256, sr25519, Pair, Public};
 use sp_finality_grandpa::AuthorityId as GrandpaId;
 use sp_runtime::traits::{IdentifyAccount, Verify};
+use std::path::Path;
 
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
 
+/// Default location of the manifest used by `--chain consortium`.
+pub const CONSORTIUM_MANIFEST: &str = "consortium.json";
+
+/// Balance given to every endowed account that does not specify its own.
+const DEFAULT_ENDOWMENT: u128 = 1 << 60;
+
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
 pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig>;
 
+/// A course seeded into genesis: owner, DNA and year.
+type GenesisCourse = (AccountId, Dna, CourseYear);
+
 /// Generate a crypto pair from seed.
 pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
 	TPublic::Pair::from_string(&format!("//{}", seed), None)
@@ -31,11 +47,42 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-/// Generate an Aura authority key.
-pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
-	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
+/// Generate the validator account together with its Babe and Grandpa authority keys.
+pub fn authority_keys_from_seed(s: &str) -> (AccountId, BabeId, GrandpaId) {
+	(
+		get_account_id_from_seed::<sr25519::Public>(s),
+		get_from_seed::<BabeId>(s),
+		get_from_seed::<GrandpaId>(s),
+	)
+}
+
+fn session_keys(babe: BabeId, grandpa: GrandpaId) -> SessionKeys {
+	SessionKeys { babe, grandpa }
+}
+
+/// Derive a deterministic course DNA from a human readable label, so that the genesis catalog is
+/// identical across every node that builds the same preset.
+pub fn course_dna(label: &str) -> Dna {
+	blake2_256(label.as_bytes())
 }
 
+/// One course of each of `years` for every account, named after the account's seed.
+fn sample_courses(seeds: &[&str], years: &[CourseYear]) -> Vec<GenesisCourse> {
+	seeds
+		.iter()
+		.flat_map(|seed| {
+			let owner = get_account_id_from_seed::<sr25519::Public>(seed);
+			years.iter().map(move |year| {
+				(owner.clone(), course_dna(&format!("{}/{:?}", seed, year)), year.clone())
+			})
+		})
+		.collect()
+}
+
+/// Every course year.
+const ALL_YEARS: [CourseYear; 4] =
+	[CourseYear::First, CourseYear::Second, CourseYear::Third, CourseYear::Fourth];
+
 pub fn development_config() -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
@@ -50,15 +97,41 @@ pub fn development_config() -> Result<ChainSpec, String> {
 				wasm_binary,
 				// Initial PoA authorities
 				vec![authority_keys_from_seed("Alice")],
-				// Sudo account
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				// Pre-funded accounts
+				// Senate: Alice as the registrar, Bob and Charlie as professors. Identity has no
+				// genesis configuration, so the README lists the calls verifying them.
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+					get_account_id_from_seed::<sr25519::Public>("Charlie"),
+				],
+				// Technical committee
+				vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
+				// Pre-funded accounts: senators, professors and students
 				vec![
 					get_account_id_from_seed::<sr25519::Public>("Alice"),
 					get_account_id_from_seed::<sr25519::Public>("Bob"),
+					get_account_id_from_seed::<sr25519::Public>("Charlie"),
+					get_account_id_from_seed::<sr25519::Public>("Dave"),
+					get_account_id_from_seed::<sr25519::Public>("Eve"),
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
+				]
+				.into_iter()
+				.map(|acct| (acct, DEFAULT_ENDOWMENT))
+				.collect(),
+				// Students with a DID, registered once the registrar verified them
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("Dave"),
+					get_account_id_from_seed::<sr25519::Public>("Eve"),
 				],
+				// Genesis courses, owned by the professors, and the students' first courses
+				sample_courses(&["Bob", "Charlie"], &ALL_YEARS)
+					.into_iter()
+					.chain(sample_courses(
+						&["Dave", "Eve"],
+						&[CourseYear::First, CourseYear::Second],
+					))
+					.collect(),
 				true,
 			)
 		},
@@ -90,9 +163,14 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				wasm_binary,
 				// Initial PoA authorities
 				vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")],
-				// Sudo account
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				// Pre-funded accounts
+				// Senate
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+				],
+				// Technical committee
+				vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
+				// Pre-funded accounts: senators, professors and students
 				vec![
 					get_account_id_from_seed::<sr25519::Public>("Alice"),
 					get_account_id_from_seed::<sr25519::Public>("Bob"),
@@ -106,7 +184,14 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-				],
+				]
+				.into_iter()
+				.map(|acct| (acct, DEFAULT_ENDOWMENT))
+				.collect(),
+				// Students with a DID, registered once the registrar verified them
+				vec![],
+				// Genesis courses, owned by the professors
+				sample_courses(&["Bob", "Charlie"], &ALL_YEARS),
 				true,
 			)
 		},
@@ -124,34 +209,351 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 	))
 }
 
+/// A member university as described in the consortium manifest.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsortiumMember {
+	/// Human readable name of the university.
+	pub name: String,
+	/// SS58 address of the university's institutional account.
+	pub account: String,
+	/// SS58 encoded sr25519 block authoring key of the university's validator.
+	pub babe: String,
+	/// SS58 encoded ed25519 finality key of the university's validator.
+	pub grandpa: String,
+	/// Initial free balance of the institutional account.
+	#[serde(default)]
+	pub endowment: Option<u128>,
+	/// Courses the university brings into genesis.
+	#[serde(default)]
+	pub courses: Vec<ConsortiumCourse>,
+}
+
+/// A course listed by a member university in the consortium manifest.
+#[derive(Deserialize)]
+pub struct ConsortiumCourse {
+	/// Course code used to derive the course DNA, e.g. `"CS101"`.
+	pub code: String,
+	/// Year of study the course belongs to.
+	pub year: CourseYear,
+}
+
+/// The JSON manifest describing a consortium deployment.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsortiumManifest {
+	/// Name of the chain.
+	pub name: String,
+	/// Identifier of the chain.
+	pub id: String,
+	/// SS58 addresses of the technical committee members, able to fast-track runtime upgrades.
+	#[serde(default)]
+	pub technical_committee: Vec<String>,
+	/// Additional accounts to endow, e.g. students and professors.
+	#[serde(default)]
+	pub accounts: Vec<String>,
+	/// Member universities, each running one validator and holding a seat in the Senate.
+	pub members: Vec<ConsortiumMember>,
+	/// Multiaddresses of the members' public nodes, which joining nodes warp sync from.
+	#[serde(default)]
+	pub boot_nodes: Vec<String>,
+}
+
+pub(crate) fn parse_account(ss58: &str) -> Result<AccountId, String> {
+	AccountId::from_ss58check(ss58).map_err(|e| format!("Invalid account `{}`: {:?}", ss58, e))
+}
+
+/// Endow `account` with `DEFAULT_ENDOWMENT`, unless it is endowed already.
+fn endow(endowed_accounts: &mut Vec<(AccountId, u128)>, account: AccountId) {
+	if !endowed_accounts.iter().any(|(endowed, _)| *endowed == account) {
+		endowed_accounts.push((account, DEFAULT_ENDOWMENT));
+	}
+}
+
+/// The genesis accounts and courses described by a consortium manifest.
+struct ConsortiumGenesis {
+	authorities: Vec<(AccountId, BabeId, GrandpaId)>,
+	senate: Vec<AccountId>,
+	technical_committee: Vec<AccountId>,
+	endowed_accounts: Vec<(AccountId, u128)>,
+	courses: Vec<GenesisCourse>,
+}
+
+/// Check a consortium manifest and collect the genesis accounts and courses it describes.
+fn consortium_genesis(manifest: &ConsortiumManifest) -> Result<ConsortiumGenesis, String> {
+	if manifest.members.is_empty() {
+		return Err("Consortium manifest must list at least one member university".into())
+	}
+
+	let technical_committee = manifest
+		.technical_committee
+		.iter()
+		.map(|account| parse_account(account))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let mut authorities = Vec::new();
+	let mut senate = Vec::new();
+	let mut endowed_accounts = Vec::new();
+	let mut courses = Vec::new();
+	for member in &manifest.members {
+		let account = parse_account(&member.account)?;
+		let babe = sr25519::Public::from_ss58check(&member.babe)
+			.map_err(|e| format!("Invalid babe key for {}: {:?}", member.name, e))?;
+		let grandpa = ed25519::Public::from_ss58check(&member.grandpa)
+			.map_err(|e| format!("Invalid grandpa key for {}: {:?}", member.name, e))?;
+
+		authorities.push((account.clone(), babe.into(), grandpa.into()));
+		senate.push(account.clone());
+		if endowed_accounts.iter().any(|(endowed, _)| *endowed == account) {
+			return Err(format!("Account of {} is listed for another member too", member.name))
+		}
+		endowed_accounts.push((account.clone(), member.endowment.unwrap_or(DEFAULT_ENDOWMENT)));
+		courses.extend(member.courses.iter().map(|course| {
+			(
+				account.clone(),
+				course_dna(&format!("{}/{}", member.name, course.code)),
+				course.year.clone(),
+			)
+		}));
+	}
+	// Members get their own endowment. Committee members and extra accounts that are not members
+	// already get the default one, so that no account is endowed twice.
+	for account in technical_committee.iter().cloned() {
+		endow(&mut endowed_accounts, account);
+	}
+	for account in &manifest.accounts {
+		endow(&mut endowed_accounts, parse_account(account)?);
+	}
+
+	Ok(ConsortiumGenesis { authorities, senate, technical_committee, endowed_accounts, courses })
+}
+
+/// Build the consortium chain specification from a manifest of member universities.
+pub fn consortium_config(manifest: &Path) -> Result<ChainSpec, String> {
+	let wasm_binary = WASM_BINARY.ok_or_else(|| "Consortium wasm not available".to_string())?;
+
+	let file = std::fs::File::open(manifest)
+		.map_err(|e| format!("Error opening consortium manifest {}: {}", manifest.display(), e))?;
+	let manifest: ConsortiumManifest = serde_json::from_reader(file)
+		.map_err(|e| format!("Error parsing consortium manifest: {}", e))?;
+
+	let ConsortiumGenesis { authorities, senate, technical_committee, endowed_accounts, courses } =
+		consortium_genesis(&manifest)?;
+	let boot_nodes = manifest
+		.boot_nodes
+		.iter()
+		.map(|addr| {
+			addr.parse::<MultiaddrWithPeerId>()
+				.map_err(|e| format!("Invalid boot node `{}`: {}", addr, e))
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	Ok(ChainSpec::from_genesis(
+		// Name
+		&manifest.name,
+		// ID
+		&manifest.id,
+		ChainType::Live,
+		move || {
+			testnet_genesis(
+				wasm_binary,
+				authorities.clone(),
+				senate.clone(),
+				technical_committee.clone(),
+				endowed_accounts.clone(),
+				vec![],
+				courses.clone(),
+				false,
+			)
+		},
+		// Bootnodes
+		boot_nodes,
+		// Telemetry
+		None,
+		// Protocol ID
+		None,
+		None,
+		// Properties
+		None,
+		// Extensions
+		None,
+	))
+}
+
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
 	wasm_binary: &[u8],
-	initial_authorities: Vec<(AuraId, GrandpaId)>,
-	root_key: AccountId,
-	endowed_accounts: Vec<AccountId>,
+	initial_authorities: Vec<(AccountId, BabeId, GrandpaId)>,
+	senate: Vec<AccountId>,
+	technical_committee: Vec<AccountId>,
+	endowed_accounts: Vec<(AccountId, u128)>,
+	students: Vec<AccountId>,
+	initial_courses: Vec<GenesisCourse>,
 	_enable_println: bool,
 ) -> GenesisConfig {
+	let uni_eur_issuer = senate.first().cloned();
 	GenesisConfig {
 		system: SystemConfig {
 			// Add Wasm runtime to storage.
 			code: wasm_binary.to_vec(),
 		},
 		balances: BalancesConfig {
-			// Configure endowed accounts with initial balance of 1 << 60.
-			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect(),
+			// Configure endowed accounts with their initial balance.
+			balances: endowed_accounts,
+		},
+		validator_set: ValidatorSetConfig {
+			validators: initial_authorities.iter().map(|x| x.0.clone()).collect(),
 		},
-		aura: AuraConfig {
-			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
+		session: SessionConfig {
+			keys: initial_authorities
+				.iter()
+				.map(|x| (x.0.clone(), x.0.clone(), session_keys(x.1.clone(), x.2.clone())))
+				.collect(),
 		},
-		grandpa: GrandpaConfig {
-			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
+		// Babe and Grandpa authorities are initialized by the session pallet from the keys above.
+		babe: BabeConfig { authorities: vec![], epoch_config: Some(BABE_GENESIS_EPOCH_CONFIG) },
+		grandpa: GrandpaConfig { authorities: vec![] },
+		// Governance: the Senate schedules runtime upgrades, the committee fast-tracks them.
+		senate: SenateConfig { phantom: Default::default(), members: senate },
+		technical_committee: TechnicalCommitteeConfig {
+			phantom: Default::default(),
+			members: technical_committee,
 		},
-		sudo: SudoConfig {
-			// Assign network admin rights.
-			key: Some(root_key),
+		// The Senate elects the appeals committee once the chain is running.
+		appeals_committee: Default::default(),
+		appeals_committee_membership: Default::default(),
+		// UNI-EUR is issued by the registrar, the first member of the Senate, and can be held by
+		// any account, with a minimum of a cent.
+		assets: AssetsConfig {
+			assets: uni_eur_issuer
+				.iter()
+				.map(|issuer| (UNI_EUR, issuer.clone(), true, 10_000))
+				.collect(),
+			metadata: uni_eur_issuer
+				.iter()
+				.map(|_| (UNI_EUR, b"UNI-EUR".to_vec(), b"UNI-EUR".to_vec(), 6))
+				.collect(),
+			accounts: vec![],
 		},
 		transaction_payment: Default::default(),
-		course_grading: CourseGradingConfig { courses: vec![] },
+		// Only the marketplace creates vesting schedules, as sales proceeds.
+		vesting: Default::default(),
+		treasury: Default::default(),
+		course_grading: CourseGradingConfig { courses: initial_courses },
+		did: DidConfig { dids: students },
+		// Professors weigh three times as much as students in catalog referenda, alumni twice.
+		catalog_referenda: CatalogReferendaConfig {
+			role_weights: vec![(Role::Professor, 3), (Role::Student, 1), (Role::Alumnus, 2)],
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn example_manifest() -> ConsortiumManifest {
+		serde_json::from_str(include_str!("../../consortium.example.json"))
+			.expect("the example manifest parses")
+	}
+
+	fn endowment_of(genesis: &ConsortiumGenesis, ss58: &str) -> Vec<u128> {
+		let account = parse_account(ss58).unwrap();
+		genesis
+			.endowed_accounts
+			.iter()
+			.filter(|(endowed, _)| *endowed == account)
+			.map(|(_, endowment)| *endowment)
+			.collect()
+	}
+
+	#[test]
+	fn example_manifest_describes_the_genesis() {
+		let manifest = example_manifest();
+		let genesis = consortium_genesis(&manifest).unwrap();
+
+		let members = manifest
+			.members
+			.iter()
+			.map(|member| parse_account(&member.account).unwrap())
+			.collect::<Vec<_>>();
+		assert_eq!(genesis.senate, members);
+		assert_eq!(
+			genesis
+				.authorities
+				.iter()
+				.map(|(account, _, _)| account.clone())
+				.collect::<Vec<_>>(),
+			members
+		);
+		assert_eq!(genesis.technical_committee.len(), 1);
+		assert_eq!(genesis.courses.len(), 3);
+		assert!(genesis.courses[..2].iter().all(|(owner, _, _)| *owner == members[0]));
+		assert_eq!(genesis.courses[2].0, members[1]);
+
+		assert_eq!(endowment_of(&genesis, &manifest.members[0].account), vec![DEFAULT_ENDOWMENT]);
+		assert_eq!(
+			endowment_of(&genesis, &manifest.members[1].account),
+			vec![1_000_000_000_000_000]
+		);
+		for account in &manifest.accounts {
+			assert_eq!(endowment_of(&genesis, account), vec![DEFAULT_ENDOWMENT]);
+		}
+	}
+
+	#[test]
+	fn courses_of_different_members_get_different_dna() {
+		let mut manifest = example_manifest();
+		manifest.members[1].courses[0].code = manifest.members[0].courses[0].code.clone();
+		let genesis = consortium_genesis(&manifest).unwrap();
+
+		assert_ne!(genesis.courses[0].1, genesis.courses[2].1);
+	}
+
+	#[test]
+	fn accounts_are_endowed_once() {
+		let mut manifest = example_manifest();
+		let member = manifest.members[1].account.clone();
+		manifest.technical_committee.push(member.clone());
+		manifest.accounts.push(member.clone());
+		manifest.accounts.push(manifest.accounts[0].clone());
+		let genesis = consortium_genesis(&manifest).unwrap();
+
+		// Members keep their own endowment.
+		assert_eq!(endowment_of(&genesis, &member), vec![1_000_000_000_000_000]);
+		assert_eq!(endowment_of(&genesis, &manifest.accounts[0]), vec![DEFAULT_ENDOWMENT]);
+	}
+
+	#[test]
+	fn account_listed_for_two_members_is_rejected() {
+		let mut manifest = example_manifest();
+		manifest.members[1].account = manifest.members[0].account.clone();
+
+		assert_eq!(
+			consortium_genesis(&manifest).err(),
+			Some("Account of State University is listed for another member too".into())
+		);
+	}
+
+	#[test]
+	fn manifest_without_members_is_rejected() {
+		let mut manifest = example_manifest();
+		manifest.members.clear();
+
+		assert!(consortium_genesis(&manifest).is_err());
+	}
+
+	#[test]
+	fn invalid_keys_are_rejected() {
+		let mut manifest = example_manifest();
+		manifest.members[0].grandpa = "not an address".into();
+		assert!(consortium_genesis(&manifest)
+			.err()
+			.unwrap()
+			.starts_with("Invalid grandpa key for Technical University"));
+
+		let mut manifest = example_manifest();
+		manifest.accounts.push("not an address".into());
+		assert!(consortium_genesis(&manifest).err().unwrap().starts_with("Invalid account"));
 	}
 }
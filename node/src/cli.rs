@@ -36,7 +36,24 @@ pub enum Subcommand {
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
+	/// Check the course storage for inconsistencies.
+	AuditStorage(crate::audit::AuditStorageCmd),
+
+	/// Export the courses at a block to JSON or CSV.
+	ExportAcademicState(crate::export::ExportAcademicStateCmd),
+
+	/// Build a chain spec seeded with the courses of a catalog.
+	ImportCatalog(crate::catalog::ImportCatalogCmd),
+
 	/// The custom benchmark subcommand benchmarking runtime pallets.
 	#[clap(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
+
+	/// Try some command against runtime state.
+	#[cfg(feature = "try-runtime")]
+	TryRuntime(try_runtime_cli::TryRuntimeCmd),
+
+	/// Try some command against runtime state. Note: `try-runtime` feature must be enabled.
+	#[cfg(not(feature = "try-runtime"))]
+	TryRuntime,
 }
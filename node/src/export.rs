@@ -0,0 +1,163 @@
+//! The `export-academic-state` subcommand, dumping the courses at a block for audits and backups.
+
+use node_uni_runtime::{opaque::Block, AccountId, Balance, Hash};
+use pallet_course_passing_runtime_api::{CourseExportApi, CourseMetadata};
+use sc_cli::{BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use serde::Serialize;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::hexdisplay::HexDisplay;
+use sp_runtime::generic::BlockId;
+use std::{fs, io::Write, path::PathBuf, sync::Arc};
+
+/// The number of courses fetched per runtime call.
+const PAGE_SIZE: u32 = 1000;
+
+/// Export the courses at a block to JSON or CSV.
+#[derive(Debug, clap::Parser)]
+pub struct ExportAcademicStateCmd {
+	/// The block to export the state of, by number or hash. The best block if omitted.
+	#[clap(long, value_name = "HASH or NUMBER")]
+	pub at: Option<BlockNumberOrHash>,
+
+	/// The file to write the export to, standard output if omitted.
+	#[clap(long, short = 'o', parse(from_os_str))]
+	pub output: Option<PathBuf>,
+
+	/// Write the courses as CSV rather than JSON.
+	#[clap(long)]
+	pub csv: bool,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+}
+
+/// A course in the export. Hashes and the DNA are hex encoded, prices are decimal strings in the
+/// smallest balance unit.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportedCourse {
+	course_id: String,
+	owner: String,
+	year: String,
+	dna: String,
+	price: Option<String>,
+	syllabus: Option<String>,
+	materials: Option<String>,
+}
+
+impl ExportedCourse {
+	fn new(course_id: Hash, metadata: CourseMetadata<AccountId, Balance>) -> Self {
+		let cid = |cid: Vec<u8>| String::from_utf8_lossy(&cid).into_owned();
+		Self {
+			course_id: format!("{:?}", course_id),
+			owner: metadata.owner.to_string(),
+			year: format!("{:?}", metadata.year),
+			dna: format!("0x{}", HexDisplay::from(&metadata.dna)),
+			price: metadata.price.map(|price| price.to_string()),
+			syllabus: metadata.syllabus.map(cid),
+			materials: metadata.materials.map(cid),
+		}
+	}
+
+	fn csv_row(&self) -> String {
+		let optional = |field: &Option<String>| field.clone().unwrap_or_default();
+		[
+			self.course_id.clone(),
+			self.owner.clone(),
+			self.year.clone(),
+			self.dna.clone(),
+			optional(&self.price),
+			optional(&self.syllabus),
+			optional(&self.materials),
+		]
+		.join(",")
+	}
+}
+
+/// The JSON export.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Export {
+	block_number: u32,
+	block_hash: Hash,
+	courses: Vec<ExportedCourse>,
+}
+
+impl ExportAcademicStateCmd {
+	/// Export the courses at the requested block.
+	pub fn run<C>(&self, client: Arc<C>) -> sc_cli::Result<()>
+	where
+		C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+		C::Api: CourseExportApi<Block, AccountId, Balance>,
+	{
+		let at = match &self.at {
+			Some(at) => at.parse::<Block>()?,
+			None => BlockId::Hash(client.info().best_hash),
+		};
+		let block_hash = client
+			.block_hash_from_id(&at)?
+			.ok_or_else(|| format!("Unknown block {:?}", at))?;
+		let block_number = client
+			.block_number_from_id(&at)?
+			.ok_or_else(|| format!("Unknown block {:?}", at))?;
+		let at = BlockId::Hash(block_hash);
+
+		let api = client.runtime_api();
+		let mut courses = Vec::new();
+		let mut cursor = None;
+		loop {
+			let (page, next) = api
+				.courses(&at, cursor, PAGE_SIZE)
+				.map_err(|e| format!("Failed to export the courses: {:?}", e))?;
+			courses
+				.extend(page.into_iter().map(|(id, metadata)| ExportedCourse::new(id, metadata)));
+			cursor = next;
+			if cursor.is_none() {
+				break
+			}
+		}
+
+		let export = if self.csv {
+			let mut csv = String::from("course_id,owner,year,dna,price,syllabus,materials\n");
+			for course in &courses {
+				csv.push_str(&course.csv_row());
+				csv.push('\n');
+			}
+			csv
+		} else {
+			let export = Export { block_number, block_hash, courses };
+			serde_json::to_string_pretty(&export)
+				.map_err(|e| format!("Failed to serialize the export: {}", e))?
+		};
+
+		match &self.output {
+			Some(path) => fs::write(path, export)?,
+			None => std::io::stdout().write_all(export.as_bytes())?,
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ExportAcademicStateCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+}
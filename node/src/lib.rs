@@ -1,3 +1,7 @@
+pub mod audit;
+pub mod catalog;
 pub mod chain_spec;
+pub mod export;
+pub mod metrics;
 pub mod rpc;
 pub mod service;
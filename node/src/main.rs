@@ -1,11 +1,15 @@
 //! Substrate Node Template CLI library.
 #![warn(missing_docs)]
 
+mod audit;
+mod catalog;
 mod chain_spec;
 #[macro_use]
 mod service;
 mod cli;
 mod command;
+mod export;
+mod metrics;
 mod rpc;
 
 fn main() -> sc_cli::Result<()> {
@@ -0,0 +1,274 @@
+//! Prometheus metrics derived from the runtime events of every finalized block, and gauges of the
+//! pallet counters at every finalized block.
+//!
+//! The metrics are registered on the node's existing Prometheus registry, so they are served from
+//! the regular `--prometheus-port` endpoint next to the Substrate client metrics.
+
+use codec::{Decode, Encode};
+use frame_support::traits::GetCallMetadata;
+use frame_system::{EventRecord, Phase};
+use futures::StreamExt;
+use node_uni_runtime::{opaque::Block, pallet_course_passing, Event, Hash, UncheckedExtrinsic};
+use sc_client_api::{Backend, BlockBackend, BlockchainEvents, StorageProvider};
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::twox_128, storage::StorageKey};
+use sp_runtime::{generic::BlockId, traits::Header};
+use std::sync::Arc;
+use substrate_prometheus_endpoint::{
+	register, Counter, CounterVec, Gauge, Opts, PrometheusError, Registry, U64,
+};
+
+/// Counters for the activity of the runtime pallets.
+#[derive(Clone)]
+pub struct PalletMetrics {
+	courses_minted: Counter<U64>,
+	courses_transferred: Counter<U64>,
+	trades_settled: Counter<U64>,
+	trade_volume: Counter<U64>,
+	stable_trade_volume: Counter<U64>,
+	failed_extrinsics: CounterVec<U64>,
+	courses: Gauge<U64>,
+	course_owners: Gauge<U64>,
+	misconduct_cases: Gauge<U64>,
+}
+
+impl PalletMetrics {
+	/// Register the pallet metrics on the given registry.
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			courses_minted: register(
+				Counter::new("uni_courses_minted_total", "Number of courses minted")?,
+				registry,
+			)?,
+			courses_transferred: register(
+				Counter::new(
+					"uni_courses_transferred_total",
+					"Number of courses transferred without a sale",
+				)?,
+				registry,
+			)?,
+			trades_settled: register(
+				Counter::new("uni_trades_settled_total", "Number of course sales settled")?,
+				registry,
+			)?,
+			trade_volume: register(
+				Counter::new(
+					"uni_trade_volume_total",
+					"Sum of the prices paid for courses, in the smallest balance unit",
+				)?,
+				registry,
+			)?,
+			stable_trade_volume: register(
+				Counter::new(
+					"uni_stable_trade_volume_total",
+					"Sum of the prices paid for courses in UNI-EUR, in its smallest unit",
+				)?,
+				registry,
+			)?,
+			failed_extrinsics: register(
+				CounterVec::new(
+					Opts::new("uni_failed_extrinsics_total", "Number of failed extrinsics"),
+					&["pallet"],
+				)?,
+				registry,
+			)?,
+			courses: register(
+				Gauge::new(
+					"uni_courses",
+					"Number of courses in existence, as of the last finalized block",
+				)?,
+				registry,
+			)?,
+			course_owners: register(
+				Gauge::new(
+					"uni_course_owners",
+					"Number of accounts owning a course, as of the last finalized block",
+				)?,
+				registry,
+			)?,
+			misconduct_cases: register(
+				Gauge::new(
+					"uni_misconduct_cases",
+					"Number of undecided misconduct cases, as of the last finalized block",
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	fn observe(
+		&self,
+		records: Vec<EventRecord<Event, Hash>>,
+		extrinsics: &[Option<UncheckedExtrinsic>],
+	) {
+		for record in records {
+			match record.event {
+				Event::CourseGrading(pallet_course_passing::Event::Created(..)) =>
+					self.courses_minted.inc(),
+				Event::CourseGrading(pallet_course_passing::Event::Transferred(..)) =>
+					self.courses_transferred.inc(),
+				Event::CourseGrading(pallet_course_passing::Event::Bought(_, _, _, price)) |
+				Event::CourseGrading(pallet_course_passing::Event::BundleBought(
+					_,
+					_,
+					_,
+					price,
+				)) => {
+					self.trades_settled.inc();
+					self.trade_volume.inc_by(u64::try_from(price).unwrap_or(u64::MAX));
+				},
+				Event::CourseGrading(pallet_course_passing::Event::BoughtInStable(
+					_,
+					_,
+					_,
+					price,
+				)) => {
+					self.trades_settled.inc();
+					self.stable_trade_volume.inc_by(u64::try_from(price).unwrap_or(u64::MAX));
+				},
+				Event::System(frame_system::Event::ExtrinsicFailed { .. }) => {
+					let pallet = match record.phase {
+						Phase::ApplyExtrinsic(index) => extrinsics
+							.get(index as usize)
+							.and_then(Option::as_ref)
+							.map(|xt| xt.function.get_call_metadata().pallet_name),
+						_ => None,
+					};
+					self.failed_extrinsics.with_label_values(&[pallet.unwrap_or("unknown")]).inc();
+				},
+				_ => {},
+			}
+		}
+	}
+}
+
+/// Storage key of the storage value `item` of `pallet`.
+pub(crate) fn storage_value_key(pallet: &[u8], item: &[u8]) -> StorageKey {
+	let mut key = twox_128(pallet).to_vec();
+	key.extend_from_slice(&twox_128(item));
+	StorageKey(key)
+}
+
+/// The counter of type `V` stored under `key` at `at`, zero if it is unset.
+fn read_counter<V, C, B>(client: &C, at: &BlockId<Block>, key: &StorageKey) -> Option<u64>
+where
+	V: Decode + Default + Into<u64>,
+	C: StorageProvider<Block, B>,
+	B: Backend<Block>,
+{
+	let value = match client.storage(at, key) {
+		Ok(Some(data)) => match V::decode(&mut &data.0[..]) {
+			Ok(value) => value,
+			Err(e) => {
+				log::warn!("Failed to decode counter {:?} at {:?}: {:?}", key, at, e);
+				return None
+			},
+		},
+		Ok(None) => V::default(),
+		Err(e) => {
+			log::warn!("Failed to read counter {:?} at {:?}: {:?}", key, at, e);
+			return None
+		},
+	};
+	Some(value.into())
+}
+
+/// Observe the events of every finalized block and update the metrics accordingly.
+///
+/// Counting finalized blocks only, every block is counted once, and never one of a fork that is
+/// abandoned later.
+pub async fn run<C, B>(client: Arc<C>, metrics: PalletMetrics)
+where
+	C: BlockchainEvents<Block>
+		+ BlockBackend<Block>
+		+ HeaderBackend<Block>
+		+ StorageProvider<Block, B>,
+	B: Backend<Block>,
+{
+	let events_key = storage_value_key(b"System", b"Events");
+	let mut finalized = client.finality_notification_stream();
+	let mut last_finalized = None;
+
+	while let Some(notification) = finalized.next().await {
+		// A notification can finalize several blocks at once, walk back to the last one counted.
+		let mut hashes = vec![notification.hash];
+		if let Some(last) = last_finalized {
+			let mut parent = *notification.header.parent_hash();
+			while parent != last {
+				match client.header(BlockId::Hash(parent)) {
+					Ok(Some(header)) => {
+						hashes.push(parent);
+						parent = *header.parent_hash();
+					},
+					_ => break,
+				}
+			}
+		}
+		last_finalized = Some(notification.hash);
+
+		for hash in hashes.into_iter().rev() {
+			observe_block(&*client, hash, &events_key, &metrics);
+		}
+	}
+}
+
+/// Update the metrics with the events and extrinsics of the block `hash`.
+fn observe_block<C, B>(client: &C, hash: Hash, events_key: &StorageKey, metrics: &PalletMetrics)
+where
+	C: BlockBackend<Block> + StorageProvider<Block, B>,
+	B: Backend<Block>,
+{
+	let at = BlockId::Hash(hash);
+
+	let records = match client.storage(&at, events_key) {
+		Ok(Some(data)) => match Vec::<EventRecord<Event, Hash>>::decode(&mut &data.0[..]) {
+			Ok(records) => records,
+			Err(e) => {
+				log::warn!("Failed to decode events of block {}: {:?}", hash, e);
+				return
+			},
+		},
+		Ok(None) => return,
+		Err(e) => {
+			log::warn!("Failed to read events of block {}: {:?}", hash, e);
+			return
+		},
+	};
+
+	// Keep undecodable extrinsics as `None`, so the positions still match the event phases.
+	let extrinsics: Vec<Option<UncheckedExtrinsic>> = client
+		.block_body(&at)
+		.ok()
+		.flatten()
+		.unwrap_or_default()
+		.iter()
+		.map(|xt| UncheckedExtrinsic::decode(&mut &xt.encode()[..]).ok())
+		.collect();
+
+	metrics.observe(records, &extrinsics);
+}
+
+/// Read the pallet counters at every finalized block and set the gauges accordingly.
+pub async fn run_gauges<C, B>(client: Arc<C>, metrics: PalletMetrics)
+where
+	C: BlockchainEvents<Block> + StorageProvider<Block, B>,
+	B: Backend<Block>,
+{
+	let courses_key = storage_value_key(b"CourseGrading", b"CountForCourses");
+	let owners_key = storage_value_key(b"CourseGrading", b"OwnerCount");
+	let cases_key = storage_value_key(b"Misconduct", b"OpenCases");
+	let mut finalized = client.finality_notification_stream();
+
+	while let Some(notification) = finalized.next().await {
+		let at = BlockId::Hash(notification.hash);
+		if let Some(courses) = read_counter::<u64, _, _>(&*client, &at, &courses_key) {
+			metrics.courses.set(courses);
+		}
+		if let Some(owners) = read_counter::<u32, _, _>(&*client, &at, &owners_key) {
+			metrics.course_owners.set(owners);
+		}
+		if let Some(cases) = read_counter::<u32, _, _>(&*client, &at, &cases_key) {
+			metrics.misconduct_cases.set(cases);
+		}
+	}
+}
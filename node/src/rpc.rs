@@ -5,46 +5,88 @@
 
 #![warn(missing_docs)]
 
+mod course_events;
+
 use std::sync::Arc;
 
-use node_uni_runtime::{opaque::Block, AccountId, Balance, Index};
+use node_uni_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Index};
+use sc_client_api::{Backend, BlockchainEvents, ProofProvider, StorageProvider};
+use sc_rpc::SubscriptionTaskExecutor;
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_core::offchain::OffchainStorage;
 
 /// Full client dependencies.
-pub struct FullDeps<C, P> {
+pub struct FullDeps<C, P, S> {
 	/// The client instance to use.
 	pub client: Arc<C>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
+	/// The off-chain database, holding the off-chain index.
+	pub offchain_storage: Option<S>,
+	/// The number of blocks of state kept behind the latest finalized block, `None` if none are
+	/// pruned.
+	pub state_pruning: Option<u32>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// Executor of the tasks streaming subscriptions.
+	pub subscription_executor: SubscriptionTaskExecutor,
 }
 
-/// Instantiate all full RPC extensions.
-pub fn create_full<C, P>(deps: FullDeps<C, P>) -> jsonrpc_core::IoHandler<sc_rpc::Metadata>
+/// Instantiate all full RPC extensions, for a client reading the state from `B`.
+pub fn create_full<C, P, S, B>(deps: FullDeps<C, P, S>) -> jsonrpc_core::IoHandler<sc_rpc::Metadata>
 where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
+	C: ProofProvider<Block> + BlockchainEvents<Block> + StorageProvider<Block, B>,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
+	C::Api: pallet_course_passing_rpc::CourseProofRuntimeApi<Block>,
+	C::Api: pallet_course_passing_rpc::CourseMetadataRuntimeApi<Block, AccountId, Balance>,
+	C::Api: pallet_course_passing_rpc::CourseTradesRuntimeApi<Block, AccountId, Balance>,
+	C::Api: pallet_course_passing_rpc::CourseArchiveRuntimeApi<Block, AccountId, Balance>,
+	C::Api: pallet_course_passing_rpc::CourseAuditRuntimeApi<Block, AccountId>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
+	S: OffchainStorage + 'static,
+	B: Backend<Block> + Send + Sync + 'static,
 {
+	use course_events::{CourseEvents, CourseEventsApi};
+	use pallet_contracts_rpc::{Contracts, ContractsApi};
+	use pallet_course_passing_rpc::{CourseHistory, CourseHistoryApi, Courses, CoursesApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
-	let FullDeps { client, pool, deny_unsafe } = deps;
+	let FullDeps {
+		client,
+		pool,
+		offchain_storage,
+		state_pruning,
+		deny_unsafe,
+		subscription_executor,
+	} = deps;
 
 	io.extend_with(SystemApi::to_delegate(FullSystem::new(client.clone(), pool, deny_unsafe)));
 
 	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone())));
 
+	io.extend_with(ContractsApi::to_delegate(Contracts::new(client.clone())));
+
+	io.extend_with(CoursesApi::to_delegate(Courses::new(client.clone(), state_pruning)));
+
+	io.extend_with(CourseHistoryApi::to_delegate(CourseHistory::new(offchain_storage)));
+
+	io.extend_with(CourseEventsApi::to_delegate(CourseEvents::new(
+		client.clone(),
+		subscription_executor,
+	)));
+
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
 	// to call into the runtime.
@@ -0,0 +1,194 @@
+//! A subscription to the course events of every new best block, decoded into JSON.
+
+use codec::Decode;
+use frame_system::EventRecord;
+use futures::{future, stream, FutureExt, SinkExt, StreamExt};
+use jsonrpc_core::{Error as RpcError, Result};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use node_uni_runtime::{
+	opaque::Block, pallet_course_passing::Event as PalletEvent, AccountId, Event, Hash,
+};
+use sc_client_api::{Backend, BlockchainEvents, StorageProvider};
+use sc_rpc::{Metadata, SubscriptionTaskExecutor};
+use serde::Serialize;
+use sp_runtime::generic::BlockId;
+use std::{marker::PhantomData, sync::Arc};
+
+/// What happened to a course.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "event")]
+pub enum CourseEventKind {
+	/// The course was minted.
+	Created { owner: AccountId },
+	/// The course changed hands without a sale.
+	Transferred { from: AccountId, to: AccountId },
+	/// The course was sold, for a price in the smallest balance unit as a decimal string.
+	Bought { buyer: AccountId, seller: AccountId, price: String },
+	/// The course was listed, for a price in the smallest balance unit as a decimal string, or
+	/// delisted if the price is `null`.
+	PriceSet { owner: AccountId, price: Option<String> },
+}
+
+/// A course event, as sent to subscribers.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseEvent {
+	/// The block the event was emitted in.
+	pub block_hash: Hash,
+	pub course_id: Hash,
+	/// What happened, with the name of the event under `event`.
+	#[serde(flatten)]
+	pub kind: CourseEventKind,
+}
+
+impl CourseEvent {
+	fn new(block_hash: Hash, event: Event) -> Option<Self> {
+		let (course_id, kind) = match event {
+			Event::CourseGrading(PalletEvent::Created(owner, course_id)) =>
+				(course_id, CourseEventKind::Created { owner }),
+			Event::CourseGrading(PalletEvent::Transferred(from, to, course_id)) =>
+				(course_id, CourseEventKind::Transferred { from, to }),
+			Event::CourseGrading(PalletEvent::Bought(buyer, seller, course_id, price)) =>
+				(course_id, CourseEventKind::Bought { buyer, seller, price: price.to_string() }),
+			Event::CourseGrading(PalletEvent::PriceSet(owner, course_id, price)) => (
+				course_id,
+				CourseEventKind::PriceSet { owner, price: price.map(|price| price.to_string()) },
+			),
+			_ => return None,
+		};
+		Some(Self { block_hash, course_id, kind })
+	}
+
+	/// Whether `who` is one of the parties of the event.
+	fn involves(&self, who: &AccountId) -> bool {
+		match &self.kind {
+			CourseEventKind::Created { owner } | CourseEventKind::PriceSet { owner, .. } =>
+				owner == who,
+			CourseEventKind::Transferred { from, to } => from == who || to == who,
+			CourseEventKind::Bought { buyer, seller, .. } => buyer == who || seller == who,
+		}
+	}
+}
+
+/// Course event subscriptions.
+#[rpc]
+pub trait CourseEventsApi {
+	/// RPC metadata.
+	type Metadata;
+
+	/// Stream the `Created`, `Transferred`, `Bought` and `PriceSet` events of every new best
+	/// block, of `course_id` only if given, and only those `account` is a party of if given.
+	#[pubsub(
+		subscription = "uniChain_courseEvents",
+		subscribe,
+		name = "uniChain_subscribeCourseEvents"
+	)]
+	fn subscribe(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<CourseEvent>,
+		course_id: Option<Hash>,
+		account: Option<AccountId>,
+	);
+
+	/// Stop streaming course events.
+	#[pubsub(
+		subscription = "uniChain_courseEvents",
+		unsubscribe,
+		name = "uniChain_unsubscribeCourseEvents"
+	)]
+	fn unsubscribe(&self, metadata: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool>;
+}
+
+/// Streams course events decoded from the events of the runtime.
+pub struct CourseEvents<C, B> {
+	client: Arc<C>,
+	manager: SubscriptionManager,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> CourseEvents<C, B> {
+	/// Create new `CourseEvents` with the given reference to the client.
+	pub fn new(client: Arc<C>, executor: SubscriptionTaskExecutor) -> Self {
+		Self { client, manager: SubscriptionManager::new(Arc::new(executor)), _marker: PhantomData }
+	}
+}
+
+/// The course events of block `hash`, of `course_id` only if given, and only those `account` is a
+/// party of if given.
+fn course_events<C, B>(
+	client: &C,
+	hash: Hash,
+	course_id: Option<Hash>,
+	account: Option<&AccountId>,
+) -> Vec<CourseEvent>
+where
+	C: StorageProvider<Block, B>,
+	B: Backend<Block>,
+{
+	let key = crate::metrics::storage_value_key(b"System", b"Events");
+	let records = match client.storage(&BlockId::Hash(hash), &key) {
+		Ok(Some(data)) => match Vec::<EventRecord<Event, Hash>>::decode(&mut &data.0[..]) {
+			Ok(records) => records,
+			Err(e) => {
+				log::warn!("Failed to decode events of block {}: {:?}", hash, e);
+				return Vec::new()
+			},
+		},
+		Ok(None) => return Vec::new(),
+		Err(e) => {
+			log::warn!("Failed to read events of block {}: {:?}", hash, e);
+			return Vec::new()
+		},
+	};
+
+	records
+		.into_iter()
+		.filter_map(|record| CourseEvent::new(hash, record.event))
+		.filter(|event| course_id.map_or(true, |id| id == event.course_id))
+		.filter(|event| account.map_or(true, |who| event.involves(who)))
+		.collect()
+}
+
+impl<C, B> CourseEventsApi for CourseEvents<C, B>
+where
+	C: BlockchainEvents<Block> + StorageProvider<Block, B> + Send + Sync + 'static,
+	B: Backend<Block> + Send + Sync + 'static,
+{
+	type Metadata = Metadata;
+
+	fn subscribe(
+		&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<CourseEvent>,
+		course_id: Option<Hash>,
+		account: Option<AccountId>,
+	) {
+		let client = self.client.clone();
+		let events = self
+			.client
+			.import_notification_stream()
+			// Blocks on abandoned forks never take effect.
+			.filter(|notification| future::ready(notification.is_new_best))
+			.flat_map(move |notification| {
+				stream::iter(course_events(
+					&*client,
+					notification.hash,
+					course_id,
+					account.as_ref(),
+				))
+			})
+			.map(|event| Ok::<_, ()>(Ok::<_, RpcError>(event)));
+
+		self.manager.add(subscriber, |sink| {
+			events
+				.forward(sink.sink_map_err(|e| log::warn!("Failed to send course events: {:?}", e)))
+				.map(|_| ())
+		});
+	}
+
+	fn unsubscribe(&self, _metadata: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool> {
+		Ok(self.manager.cancel(id))
+	}
+}
@@ -0,0 +1,265 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Promotional courses given away to the accounts of a list.
+//!
+//! An issuer creates a drop of `count` courses of a collection for the accounts of a list it only
+//! publishes off-chain, committing to it with a Merkle root. The issuer reserves the deposit of
+//! every course of the drop up front. Each listed account claims one course with a proof of its
+//! place in the list until the drop expires; after that anyone can close the drop, which returns
+//! the deposits of the unclaimed courses to the issuer.
+//!
+//! The leaves of the tree are the hashes of the SCALE-encoded accounts, its inner nodes the
+//! hashes of their two children, and a node without a sibling is carried up unchanged. A proof
+//! lists the siblings on the path from the leaf to the root, bottom up, each with whether it is
+//! the left one.
+
+pub use pallet::*;
+
+use frame_support::{dispatch::DispatchError, traits::ConstU32, BoundedVec};
+
+/// The maximum number of siblings in a proof, enough for lists of `u32::MAX` accounts.
+pub const MAX_PROOF_LEN: u32 = 32;
+
+/// A proof that an account is on the list of a drop.
+pub type Proof<Hash> = BoundedVec<(Hash, bool), ConstU32<MAX_PROOF_LEN>>;
+
+/// Mints the courses of the drops.
+pub trait CourseMinter<AccountId, Collection, CourseId> {
+	/// Mint a course of `collection` for `to`, reserving its deposit from `funder`.
+	fn mint(
+		to: &AccountId,
+		funder: &AccountId,
+		collection: &Collection,
+	) -> Result<CourseId, DispatchError>;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::{CourseMinter, Proof};
+	use frame_support::{
+		pallet_prelude::*,
+		sp_runtime::traits::{Hash, Saturating, Zero},
+		traits::{Currency, ReservableCurrency},
+		transactional,
+	};
+	use frame_system::pallet_prelude::*;
+
+	type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// Identifier of a drop.
+	pub type DropId = u32;
+
+	// Struct for holding a drop.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Airdrop<T: Config> {
+		pub issuer: T::AccountId,
+		pub collection: T::Collection,
+		/// The Merkle root of the accounts that can claim a course.
+		pub root: T::Hash,
+		/// The number of courses left to claim.
+		pub remaining: u32,
+		/// The deposit reserved from the issuer for each course.
+		pub deposit: BalanceOf<T>,
+		/// The block from which nobody can claim anymore.
+		pub expires: T::BlockNumber,
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types it depends on.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency the course deposits are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// A group of courses a drop gives away.
+		type Collection: Parameter + MaxEncodedLen;
+
+		/// Mints the claimed courses.
+		type Courses: CourseMinter<Self::AccountId, Self::Collection, Self::Hash>;
+
+		/// The deposit of a course, reserved from the issuer for every course of a drop.
+		#[pallet::constant]
+		type DepositPerCourse: Get<BalanceOf<Self>>;
+
+		/// The maximum number of courses of a drop.
+		#[pallet::constant]
+		type MaxDropSize: Get<u32>;
+
+		/// The maximum number of blocks a drop can be claimed for.
+		#[pallet::constant]
+		type MaxDropDuration: Get<Self::BlockNumber>;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn drops)]
+	/// The open drops.
+	pub(super) type Drops<T: Config> = StorageMap<_, Twox64Concat, DropId, Airdrop<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_drop_id)]
+	/// The identifier of the next drop.
+	pub(super) type NextDropId<T: Config> = StorageValue<_, DropId, ValueQuery>;
+
+	#[pallet::storage]
+	/// The accounts that claimed their course of a drop.
+	pub(super) type Claimed<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, DropId, Blake2_128Concat, T::AccountId, ()>;
+
+	// Errors.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A drop must give away at least one course, and at most `MaxDropSize`.
+		InvalidDropSize,
+		/// A drop must be claimable for at least one block, and at most `MaxDropDuration`.
+		InvalidDropDuration,
+		/// The issuer cannot reserve the deposits of the drop.
+		NotEnoughBalance,
+		/// Ran out of drop ids.
+		DropIdOverflow,
+		/// The drop does not exist, or all its courses were claimed.
+		DropNotExist,
+		/// The drop expired.
+		DropExpired,
+		/// The drop has not expired yet.
+		DropNotExpired,
+		/// The account claimed its course of the drop already.
+		AlreadyClaimed,
+		/// The proof does not show the account is on the list of the drop.
+		NotEligible,
+	}
+
+	// Events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A drop was created. \[drop_id, issuer, count, expires\]
+		DropCreated(DropId, T::AccountId, u32, T::BlockNumber),
+		/// A course of a drop was claimed. \[drop_id, who, course_id\]
+		Claimed(DropId, T::AccountId, T::Hash),
+		/// An expired drop was closed. \[drop_id, unclaimed\]
+		DropClosed(DropId, u32),
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Give away `count` courses of `collection` to the accounts of the list of `root`, until
+		/// `duration` blocks from now.
+		///
+		/// The deposits of the courses are reserved from the sender.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 3))]
+		pub fn create_drop(
+			origin: OriginFor<T>,
+			collection: T::Collection,
+			root: T::Hash,
+			count: u32,
+			duration: T::BlockNumber,
+		) -> DispatchResult {
+			let issuer = ensure_signed(origin)?;
+			ensure!(count > 0 && count <= T::MaxDropSize::get(), <Error<T>>::InvalidDropSize);
+			ensure!(
+				!duration.is_zero() && duration <= T::MaxDropDuration::get(),
+				<Error<T>>::InvalidDropDuration
+			);
+
+			let id = Self::next_drop_id();
+			let next_id = id.checked_add(1).ok_or(<Error<T>>::DropIdOverflow)?;
+			let deposit = T::DepositPerCourse::get();
+			T::Currency::reserve(&issuer, deposit.saturating_mul(count.into()))
+				.map_err(|_| <Error<T>>::NotEnoughBalance)?;
+
+			let expires = <frame_system::Pallet<T>>::block_number().saturating_add(duration);
+			<Drops<T>>::insert(
+				id,
+				Airdrop {
+					issuer: issuer.clone(),
+					collection,
+					root,
+					remaining: count,
+					deposit,
+					expires,
+				},
+			);
+			<NextDropId<T>>::put(next_id);
+
+			Self::deposit_event(Event::DropCreated(id, issuer, count, expires));
+			Ok(())
+		}
+
+		/// Claim a course of drop `drop_id`, with a `proof` that the sender is on its list.
+		// Most of it is the mint of the course.
+		#[pallet::weight(100_000 + T::DbWeight::get().reads_writes(19, 18))]
+		#[transactional]
+		pub fn claim(
+			origin: OriginFor<T>,
+			drop_id: DropId,
+			proof: Proof<T::Hash>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut drop = Self::drops(drop_id).ok_or(<Error<T>>::DropNotExist)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() < drop.expires,
+				<Error<T>>::DropExpired
+			);
+			ensure!(!<Claimed<T>>::contains_key(drop_id, &who), <Error<T>>::AlreadyClaimed);
+			ensure!(Self::is_eligible(&drop.root, &who, &proof), <Error<T>>::NotEligible);
+
+			// The deposit moves from the drop to the course.
+			T::Currency::unreserve(&drop.issuer, drop.deposit);
+			let course_id = T::Courses::mint(&who, &drop.issuer, &drop.collection)?;
+
+			<Claimed<T>>::insert(drop_id, &who, ());
+			drop.remaining -= 1;
+			if drop.remaining == 0 {
+				<Drops<T>>::remove(drop_id);
+			} else {
+				<Drops<T>>::insert(drop_id, drop);
+			}
+
+			Self::deposit_event(Event::Claimed(drop_id, who, course_id));
+			Ok(())
+		}
+
+		/// Close an expired drop, returning the deposits of its unclaimed courses to the issuer.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn close_drop(origin: OriginFor<T>, drop_id: DropId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let drop = Self::drops(drop_id).ok_or(<Error<T>>::DropNotExist)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= drop.expires,
+				<Error<T>>::DropNotExpired
+			);
+
+			T::Currency::unreserve(
+				&drop.issuer,
+				drop.deposit.saturating_mul(drop.remaining.into()),
+			);
+			<Drops<T>>::remove(drop_id);
+
+			Self::deposit_event(Event::DropClosed(drop_id, drop.remaining));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether `proof` shows `who` is on the list of `root`.
+		pub fn is_eligible(root: &T::Hash, who: &T::AccountId, proof: &[(T::Hash, bool)]) -> bool {
+			let computed =
+				proof.iter().fold(T::Hashing::hash_of(who), |hash, (sibling, is_left)| {
+					if *is_left {
+						T::Hashing::hash_of(&(sibling, hash))
+					} else {
+						T::Hashing::hash_of(&(hash, sibling))
+					}
+				});
+			computed == *root
+		}
+	}
+}
@@ -0,0 +1,508 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Assignment submissions with on-chain timestamps.
+//!
+//! An instructor sets an assignment of a course with a deadline. The owner of the course submits
+//! the hash of their work, and the block it was submitted in settles any dispute about when it was
+//! handed in. The work itself stays off-chain. Depending on the assignment, submissions after the
+//! deadline are rejected or accepted and flagged as late. Submissions can be replaced under the
+//! same rules, so replacing an on-time submission after the deadline makes it late.
+//!
+//! For group work the instructor registers groups of students. Any member submits for the group,
+//! and the submission only counts once every member attested it, under the same deadline rules.
+//! A new submission clears the attestations, and members that did not attest are on record.
+//!
+//! Instructors appoint teaching assistants to a course until the end of the term. Until then, a
+//! teaching assistant sets assignments of the course and registers groups for them like an
+//! instructor, with a plain signed origin.
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use pallet_course_passing_primitives::CourseInspector;
+	use sp_std::vec::Vec;
+
+	/// Identifier of an assignment.
+	pub type AssignmentId = u32;
+
+	/// Identifier of a group of students.
+	pub type GroupId = u32;
+
+	/// What happens to submissions after the deadline.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum LatePolicy {
+		/// They are rejected.
+		Reject,
+		/// They are accepted and flagged as late.
+		Flag,
+	}
+
+	// Struct for holding an assignment.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Assignment<T: Config> {
+		pub course_id: T::Hash,
+		pub instructor: T::AccountId,
+		/// The last block submissions are on time in.
+		pub deadline: T::BlockNumber,
+		/// The expected size of the work in bytes, for information only.
+		pub max_size_hint: u32,
+		pub late_policy: LatePolicy,
+	}
+
+	// Struct for holding a submission.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Submission<T: Config> {
+		/// The hash of the submitted work.
+		pub content_hash: T::Hash,
+		pub submitted: T::BlockNumber,
+		pub late: bool,
+	}
+
+	// Struct for holding a group of students working on an assignment together.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Group<T: Config> {
+		pub assignment_id: AssignmentId,
+		pub members: BoundedVec<T::AccountId, T::MaxGroupSize>,
+	}
+
+	// Struct for holding the submission of a group.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct GroupSubmission<T: Config> {
+		/// The hash of the submitted work.
+		pub content_hash: T::Hash,
+		pub submitter: T::AccountId,
+		pub submitted: T::BlockNumber,
+		/// The members that attested the submission, in order.
+		pub attested: BoundedVec<T::AccountId, T::MaxGroupSize>,
+		/// Whether the submission or one of its attestations came after the deadline.
+		pub late: bool,
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types it depends on.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The courses assignments are set for.
+		type Courses: CourseInspector<Self::AccountId, Self::Hash>;
+
+		/// Origin allowed to set assignments, resolving to the instructor's account.
+		type InstructorOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
+		/// The maximum number of students in a group.
+		#[pallet::constant]
+		type MaxGroupSize: Get<u32>;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn assignments)]
+	/// The assignments, by id.
+	pub(super) type Assignments<T: Config> =
+		StorageMap<_, Twox64Concat, AssignmentId, Assignment<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_assignment_id)]
+	/// The identifier of the next assignment.
+	pub(super) type NextAssignmentId<T: Config> = StorageValue<_, AssignmentId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn submissions)]
+	/// The latest submission of each account to an assignment.
+	pub(super) type Submissions<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		AssignmentId,
+		Blake2_128Concat,
+		T::AccountId,
+		Submission<T>,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn teaching_assistants)]
+	/// The teaching assistants of each course, and the block their appointment ends at.
+	pub(super) type TeachingAssistants<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::Hash, Blake2_128Concat, T::AccountId, T::BlockNumber>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn groups)]
+	/// The groups, by id.
+	pub(super) type Groups<T: Config> = StorageMap<_, Twox64Concat, GroupId, Group<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_group_id)]
+	/// The identifier of the next group.
+	pub(super) type NextGroupId<T: Config> = StorageValue<_, GroupId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn group_of)]
+	/// The group each student works in, per assignment.
+	pub(super) type GroupOf<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, AssignmentId, Blake2_128Concat, T::AccountId, GroupId>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn group_submissions)]
+	/// The latest submission of each group.
+	pub(super) type GroupSubmissions<T: Config> =
+		StorageMap<_, Twox64Concat, GroupId, GroupSubmission<T>>;
+
+	// Errors.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Ran out of assignment ids.
+		AssignmentIdOverflow,
+		/// The course does not exist.
+		CourseNotExist,
+		/// The deadline is in the past.
+		DeadlineInPast,
+		/// The assignment does not exist.
+		AssignmentNotExist,
+		/// Only the owner of the course can submit.
+		NotCourseOwner,
+		/// The deadline passed and the assignment does not accept late submissions.
+		DeadlinePassed,
+		/// Only the instructor of the assignment can register groups.
+		NotInstructor,
+		/// A group must have members, each listed once.
+		InvalidGroup,
+		/// A member is in another group for the assignment already.
+		AlreadyInGroup,
+		/// Ran out of group ids.
+		GroupIdOverflow,
+		/// The group does not exist.
+		GroupNotExist,
+		/// The account is not a member of the group.
+		NotGroupMember,
+		/// Members of a group submit for their group.
+		SubmitForGroup,
+		/// The group has not submitted anything yet.
+		NoGroupSubmission,
+		/// The member attested the submission already.
+		AlreadyAttested,
+		/// The appointment would end in the past.
+		AppointmentInPast,
+		/// The account is not a teaching assistant of the course.
+		NotTeachingAssistant,
+	}
+
+	// Events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An assignment was set. \[assignment_id, course_id, deadline\]
+		AssignmentCreated(AssignmentId, T::Hash, T::BlockNumber),
+		/// Work was submitted to an assignment. \[assignment_id, who, content_hash, late\]
+		Submitted(AssignmentId, T::AccountId, T::Hash, bool),
+		/// A group was registered for an assignment. \[assignment_id, group_id\]
+		GroupRegistered(AssignmentId, GroupId),
+		/// Work was submitted for a group. \[group_id, who, content_hash, late\]
+		GroupSubmitted(GroupId, T::AccountId, T::Hash, bool),
+		/// A member attested the submission of their group. \[group_id, who\]
+		Attested(GroupId, T::AccountId),
+		/// Every member attested the submission of a group, which now counts. \[group_id\]
+		GroupSubmissionComplete(GroupId),
+		/// A teaching assistant was appointed to a course. \[course_id, who, until\]
+		TeachingAssistantAppointed(T::Hash, T::AccountId, T::BlockNumber),
+		/// A teaching assistant was dismissed from a course. \[course_id, who\]
+		TeachingAssistantDismissed(T::Hash, T::AccountId),
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set an assignment of `course_id` due at block `deadline`.
+		///
+		/// The dispatch origin for this call must be `InstructorOrigin`, or signed by a teaching
+		/// assistant of the course.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2))]
+		pub fn create_assignment(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			deadline: T::BlockNumber,
+			max_size_hint: u32,
+			late_policy: LatePolicy,
+		) -> DispatchResult {
+			let instructor = Self::ensure_instructor(origin, &course_id)?;
+			ensure!(T::Courses::exists(&course_id), <Error<T>>::CourseNotExist);
+			ensure!(
+				deadline >= <frame_system::Pallet<T>>::block_number(),
+				<Error<T>>::DeadlineInPast
+			);
+
+			let id = Self::next_assignment_id();
+			let next_id = id.checked_add(1).ok_or(<Error<T>>::AssignmentIdOverflow)?;
+			<Assignments<T>>::insert(
+				id,
+				Assignment { course_id, instructor, deadline, max_size_hint, late_policy },
+			);
+			<NextAssignmentId<T>>::put(next_id);
+
+			Self::deposit_event(Event::AssignmentCreated(id, course_id, deadline));
+			Ok(())
+		}
+
+		/// Submit the hash of the sender's work to an assignment, replacing any earlier one.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn submit(
+			origin: OriginFor<T>,
+			assignment_id: AssignmentId,
+			content_hash: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let assignment =
+				Self::assignments(assignment_id).ok_or(<Error<T>>::AssignmentNotExist)?;
+			ensure!(
+				T::Courses::owner_of(&assignment.course_id).as_ref() == Some(&who),
+				<Error<T>>::NotCourseOwner
+			);
+			ensure!(!<GroupOf<T>>::contains_key(assignment_id, &who), <Error<T>>::SubmitForGroup);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let late = Self::ensure_on_time(&assignment, now)?;
+
+			<Submissions<T>>::insert(
+				assignment_id,
+				&who,
+				Submission { content_hash, submitted: now, late },
+			);
+
+			Self::deposit_event(Event::Submitted(assignment_id, who, content_hash, late));
+			Ok(())
+		}
+
+		/// Register a group of students working on an assignment together.
+		///
+		/// The dispatch origin for this call must be `InstructorOrigin`, resolving to the
+		/// instructor of the assignment, or signed by a teaching assistant of its course.
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(3, 2) +
+				T::DbWeight::get().reads_writes(1, 1) * members.len() as Weight
+		)]
+		pub fn register_group(
+			origin: OriginFor<T>,
+			assignment_id: AssignmentId,
+			members: BoundedVec<T::AccountId, T::MaxGroupSize>,
+		) -> DispatchResult {
+			let assignment =
+				Self::assignments(assignment_id).ok_or(<Error<T>>::AssignmentNotExist)?;
+			let who = Self::ensure_instructor(origin, &assignment.course_id)?;
+			ensure!(
+				assignment.instructor == who ||
+					Self::is_teaching_assistant(&assignment.course_id, &who),
+				<Error<T>>::NotInstructor
+			);
+
+			let mut distinct = members.to_vec();
+			distinct.sort();
+			distinct.dedup();
+			ensure!(
+				!members.is_empty() && distinct.len() == members.len(),
+				<Error<T>>::InvalidGroup
+			);
+			ensure!(
+				members.iter().all(|member| !<GroupOf<T>>::contains_key(assignment_id, member)),
+				<Error<T>>::AlreadyInGroup
+			);
+
+			let group_id = Self::next_group_id();
+			let next_id = group_id.checked_add(1).ok_or(<Error<T>>::GroupIdOverflow)?;
+			for member in members.iter() {
+				<GroupOf<T>>::insert(assignment_id, member, group_id);
+			}
+			<Groups<T>>::insert(group_id, Group { assignment_id, members });
+			<NextGroupId<T>>::put(next_id);
+
+			Self::deposit_event(Event::GroupRegistered(assignment_id, group_id));
+			Ok(())
+		}
+
+		/// Submit the hash of the work of the sender's group, replacing any earlier submission
+		/// and its attestations. The submission counts as the sender's attestation.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 1))]
+		pub fn submit_for_group(
+			origin: OriginFor<T>,
+			group_id: GroupId,
+			content_hash: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (group, assignment) = Self::group_and_assignment(group_id)?;
+			ensure!(group.members.contains(&who), <Error<T>>::NotGroupMember);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let late = Self::ensure_on_time(&assignment, now)?;
+
+			let attested = BoundedVec::try_from(sp_std::vec![who.clone()])
+				.map_err(|_| <Error<T>>::InvalidGroup)?;
+			let complete = group.members.len() == 1;
+			<GroupSubmissions<T>>::insert(
+				group_id,
+				GroupSubmission {
+					content_hash,
+					submitter: who.clone(),
+					submitted: now,
+					attested,
+					late,
+				},
+			);
+
+			Self::deposit_event(Event::GroupSubmitted(group_id, who, content_hash, late));
+			if complete {
+				Self::deposit_event(Event::GroupSubmissionComplete(group_id));
+			}
+			Ok(())
+		}
+
+		/// Attest the submission of the sender's group.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 1))]
+		pub fn attest(origin: OriginFor<T>, group_id: GroupId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (group, assignment) = Self::group_and_assignment(group_id)?;
+			ensure!(group.members.contains(&who), <Error<T>>::NotGroupMember);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let late = Self::ensure_on_time(&assignment, now)?;
+
+			let complete = <GroupSubmissions<T>>::try_mutate(group_id, |submission| {
+				let submission = submission.as_mut().ok_or(<Error<T>>::NoGroupSubmission)?;
+				ensure!(!submission.attested.contains(&who), <Error<T>>::AlreadyAttested);
+				submission
+					.attested
+					.try_push(who.clone())
+					.map_err(|_| <Error<T>>::InvalidGroup)?;
+				submission.late |= late;
+				Ok::<_, Error<T>>(submission.attested.len() == group.members.len())
+			})?;
+
+			Self::deposit_event(Event::Attested(group_id, who));
+			if complete {
+				Self::deposit_event(Event::GroupSubmissionComplete(group_id));
+			}
+			Ok(())
+		}
+
+		/// Appoint `who` teaching assistant of `course_id` until block `until`, the end of the
+		/// term, replacing any earlier appointment.
+		///
+		/// The dispatch origin for this call must be `InstructorOrigin`.
+		#[pallet::weight((
+			10_000 + T::DbWeight::get().reads_writes(1, 1),
+			DispatchClass::Operational,
+		))]
+		pub fn appoint_teaching_assistant(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			who: T::AccountId,
+			until: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			T::InstructorOrigin::ensure_origin(origin)?;
+			ensure!(T::Courses::exists(&course_id), <Error<T>>::CourseNotExist);
+			ensure!(
+				until > <frame_system::Pallet<T>>::block_number(),
+				<Error<T>>::AppointmentInPast
+			);
+
+			<TeachingAssistants<T>>::insert(&course_id, &who, until);
+
+			Self::deposit_event(Event::TeachingAssistantAppointed(course_id, who, until));
+			Ok(Pays::No.into())
+		}
+
+		/// Dismiss a teaching assistant of `course_id` before the end of their appointment.
+		///
+		/// The dispatch origin for this call must be `InstructorOrigin`.
+		#[pallet::weight((
+			10_000 + T::DbWeight::get().reads_writes(1, 1),
+			DispatchClass::Operational,
+		))]
+		pub fn dismiss_teaching_assistant(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			who: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::InstructorOrigin::ensure_origin(origin)?;
+			ensure!(
+				<TeachingAssistants<T>>::contains_key(&course_id, &who),
+				<Error<T>>::NotTeachingAssistant
+			);
+
+			<TeachingAssistants<T>>::remove(&course_id, &who);
+
+			Self::deposit_event(Event::TeachingAssistantDismissed(course_id, who));
+			Ok(Pays::No.into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account setting assignments of `course_id` with `origin`: an instructor, or a
+		/// teaching assistant of the course.
+		fn ensure_instructor(
+			origin: OriginFor<T>,
+			course_id: &T::Hash,
+		) -> Result<T::AccountId, DispatchError> {
+			match T::InstructorOrigin::try_origin(origin) {
+				Ok(instructor) => Ok(instructor),
+				Err(origin) => {
+					let who = ensure_signed(origin)?;
+					ensure!(
+						Self::is_teaching_assistant(course_id, &who),
+						<Error<T>>::NotInstructor
+					);
+					Ok(who)
+				},
+			}
+		}
+
+		/// Whether `who` is a teaching assistant of `course_id` at this block.
+		pub fn is_teaching_assistant(course_id: &T::Hash, who: &T::AccountId) -> bool {
+			Self::teaching_assistants(course_id, who)
+				.map_or(false, |until| <frame_system::Pallet<T>>::block_number() < until)
+		}
+
+		/// Whether a submission at block `now` is late, failing if the assignment rejects late
+		/// submissions.
+		fn ensure_on_time(
+			assignment: &Assignment<T>,
+			now: T::BlockNumber,
+		) -> Result<bool, Error<T>> {
+			let late = now > assignment.deadline;
+			ensure!(
+				!late || assignment.late_policy == LatePolicy::Flag,
+				<Error<T>>::DeadlinePassed
+			);
+			Ok(late)
+		}
+
+		fn group_and_assignment(group_id: GroupId) -> Result<(Group<T>, Assignment<T>), Error<T>> {
+			let group = Self::groups(group_id).ok_or(<Error<T>>::GroupNotExist)?;
+			let assignment =
+				Self::assignments(group.assignment_id).ok_or(<Error<T>>::AssignmentNotExist)?;
+			Ok((group, assignment))
+		}
+
+		/// The members of a group that have not attested its latest submission, all of them if
+		/// it has none.
+		pub fn non_attesting(group_id: GroupId) -> Vec<T::AccountId> {
+			let group = match Self::groups(group_id) {
+				Some(group) => group,
+				None => return Vec::new(),
+			};
+			let attested = Self::group_submissions(group_id)
+				.map(|submission| submission.attested.into_inner())
+				.unwrap_or_default();
+			group.members.into_iter().filter(|member| !attested.contains(member)).collect()
+		}
+	}
+}
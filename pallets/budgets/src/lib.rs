@@ -0,0 +1,321 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Treasury spends tagged with a department and bounded by its budget for the term.
+//!
+//! Anyone proposes a spend from the treasury for a department, reserving `ProposalBond`. The
+//! proposal states what the spend is for: a course-creation grant, a scholarship or equipment.
+//! `BudgetOrigin` sets the cap of each department for a term, a run of `TermLength` blocks.
+//! `ApproveOrigin` approves a proposal, paying it from the treasury and returning the bond, as long
+//! as the department's spends of the current term stay within its cap. Rejected proposals forfeit
+//! their bond to the treasury.
+
+pub use pallet::*;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+
+/// What a spend is for.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum SpendKind {
+	/// A grant for creating a course.
+	CourseGrant,
+	/// A scholarship for a student.
+	Scholarship,
+	/// Equipment for the department.
+	Equipment,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::SpendKind;
+	use frame_support::{
+		pallet_prelude::*,
+		sp_runtime::traits::{SaturatedConversion, Saturating, Zero},
+		traits::{BalanceStatus, Currency, ExistenceRequirement, ReservableCurrency},
+	};
+	use frame_system::pallet_prelude::*;
+
+	type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// The name of a department, e.g. `b"Computer Science"`.
+	pub type Department<T> = BoundedVec<u8, <T as Config>::MaxDepartmentLength>;
+
+	/// Identifier of a spend proposal.
+	pub type ProposalId = u32;
+
+	/// Index of a term, the current block divided by `TermLength`.
+	pub type Term = u32;
+
+	// Struct for holding a spend proposal.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Proposal<T: Config> {
+		pub proposer: T::AccountId,
+		pub department: Department<T>,
+		pub kind: SpendKind,
+		pub value: BalanceOf<T>,
+		pub beneficiary: T::AccountId,
+		/// The bond reserved from the proposer.
+		pub bond: BalanceOf<T>,
+	}
+
+	// Struct for holding the budget of a department for a term.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Budget<Balance> {
+		/// The most the department can spend in the term.
+		pub cap: Balance,
+		/// What the approved spends of the term add up to.
+		pub spent: Balance,
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types it depends on.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency spends are paid and bonds reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The account spends are paid from.
+		type Treasury: Get<Self::AccountId>;
+
+		/// Origin allowed to set the budgets of the departments.
+		type BudgetOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin allowed to approve and reject spends.
+		type ApproveOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The amount reserved from the proposer of a spend until it is decided.
+		#[pallet::constant]
+		type ProposalBond: Get<BalanceOf<Self>>;
+
+		/// The number of blocks of a term.
+		#[pallet::constant]
+		type TermLength: Get<Self::BlockNumber>;
+
+		/// The maximum length of the name of a department.
+		#[pallet::constant]
+		type MaxDepartmentLength: Get<u32>;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn proposals)]
+	/// The spend proposals waiting for a decision.
+	pub(super) type Proposals<T: Config> = StorageMap<_, Twox64Concat, ProposalId, Proposal<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_proposal_id)]
+	/// The identifier of the next spend proposal.
+	pub(super) type NextProposalId<T: Config> = StorageValue<_, ProposalId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn budgets)]
+	/// The budget of each department for each term it was given one.
+	pub(super) type Budgets<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		Department<T>,
+		Twox64Concat,
+		Term,
+		Budget<BalanceOf<T>>,
+	>;
+
+	// Errors.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A spend must not be zero.
+		ZeroSpend,
+		/// The proposer cannot reserve the bond.
+		NotEnoughBalance,
+		/// Ran out of proposal ids.
+		ProposalIdOverflow,
+		/// The proposal does not exist, or was decided already.
+		ProposalNotExist,
+		/// The department has no budget for the current term.
+		NoBudget,
+		/// The spend would take the department over its budget for the term.
+		OverBudget,
+		/// The cap is below what the department spent in the term already.
+		CapBelowSpent,
+		/// The treasury cannot pay the spend.
+		InsufficientTreasury,
+	}
+
+	// Events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A spend was proposed.
+		/// \[proposal_id, proposer, department, kind, value, beneficiary\]
+		SpendProposed(
+			ProposalId,
+			T::AccountId,
+			Department<T>,
+			SpendKind,
+			BalanceOf<T>,
+			T::AccountId,
+		),
+		/// The budget of a department for a term was set. \[department, term, cap\]
+		BudgetSet(Department<T>, Term, BalanceOf<T>),
+		/// A spend was approved and paid. \[proposal_id, department, kind, value, beneficiary\]
+		SpendApproved(ProposalId, Department<T>, SpendKind, BalanceOf<T>, T::AccountId),
+		/// A spend was rejected and its bond forfeited to the treasury. \[proposal_id, bond\]
+		SpendRejected(ProposalId, BalanceOf<T>),
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Propose to pay `value` from the treasury to `beneficiary`, for `kind` in `department`.
+		///
+		/// `ProposalBond` is reserved from the sender until the proposal is decided.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 3))]
+		pub fn propose_spend(
+			origin: OriginFor<T>,
+			department: Department<T>,
+			kind: SpendKind,
+			value: BalanceOf<T>,
+			beneficiary: T::AccountId,
+		) -> DispatchResult {
+			let proposer = ensure_signed(origin)?;
+			ensure!(!value.is_zero(), <Error<T>>::ZeroSpend);
+
+			let id = Self::next_proposal_id();
+			let next_id = id.checked_add(1).ok_or(<Error<T>>::ProposalIdOverflow)?;
+			let bond = T::ProposalBond::get();
+			T::Currency::reserve(&proposer, bond).map_err(|_| <Error<T>>::NotEnoughBalance)?;
+
+			<Proposals<T>>::insert(
+				id,
+				Proposal {
+					proposer: proposer.clone(),
+					department: department.clone(),
+					kind,
+					value,
+					beneficiary: beneficiary.clone(),
+					bond,
+				},
+			);
+			<NextProposalId<T>>::put(next_id);
+
+			Self::deposit_event(Event::SpendProposed(
+				id,
+				proposer,
+				department,
+				kind,
+				value,
+				beneficiary,
+			));
+			Ok(())
+		}
+
+		/// Set the most `department` can spend in `term`.
+		///
+		/// The dispatch origin for this call must be `BudgetOrigin`.
+		#[pallet::weight((
+			10_000 + T::DbWeight::get().reads_writes(1, 1),
+			DispatchClass::Operational,
+		))]
+		pub fn set_budget(
+			origin: OriginFor<T>,
+			department: Department<T>,
+			term: Term,
+			cap: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			T::BudgetOrigin::ensure_origin(origin)?;
+
+			<Budgets<T>>::try_mutate(&department, term, |budget| -> DispatchResult {
+				let spent = budget.as_ref().map_or_else(Zero::zero, |budget| budget.spent);
+				ensure!(cap >= spent, <Error<T>>::CapBelowSpent);
+				*budget = Some(Budget { cap, spent });
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::BudgetSet(department, term, cap));
+			Ok(Pays::No.into())
+		}
+
+		/// Approve a spend, paying it from the treasury and returning the bond to the proposer.
+		///
+		/// The dispatch origin for this call must be `ApproveOrigin`.
+		#[pallet::weight((
+			10_000 + T::DbWeight::get().reads_writes(5, 4),
+			DispatchClass::Operational,
+		))]
+		pub fn approve_spend(
+			origin: OriginFor<T>,
+			proposal_id: ProposalId,
+		) -> DispatchResultWithPostInfo {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			let proposal = Self::proposals(proposal_id).ok_or(<Error<T>>::ProposalNotExist)?;
+
+			let term = Self::current_term();
+			let mut budget =
+				Self::budgets(&proposal.department, term).ok_or(<Error<T>>::NoBudget)?;
+			let spent = budget.spent.saturating_add(proposal.value);
+			ensure!(spent <= budget.cap, <Error<T>>::OverBudget);
+			budget.spent = spent;
+
+			T::Currency::transfer(
+				&T::Treasury::get(),
+				&proposal.beneficiary,
+				proposal.value,
+				ExistenceRequirement::KeepAlive,
+			)
+			.map_err(|_| <Error<T>>::InsufficientTreasury)?;
+			T::Currency::unreserve(&proposal.proposer, proposal.bond);
+			<Budgets<T>>::insert(&proposal.department, term, budget);
+			<Proposals<T>>::remove(proposal_id);
+
+			Self::deposit_event(Event::SpendApproved(
+				proposal_id,
+				proposal.department,
+				proposal.kind,
+				proposal.value,
+				proposal.beneficiary,
+			));
+			Ok(Pays::No.into())
+		}
+
+		/// Reject a spend, forfeiting the bond of the proposer to the treasury.
+		///
+		/// The dispatch origin for this call must be `ApproveOrigin`.
+		#[pallet::weight((
+			10_000 + T::DbWeight::get().reads_writes(3, 3),
+			DispatchClass::Operational,
+		))]
+		pub fn reject_spend(
+			origin: OriginFor<T>,
+			proposal_id: ProposalId,
+		) -> DispatchResultWithPostInfo {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			let proposal = Self::proposals(proposal_id).ok_or(<Error<T>>::ProposalNotExist)?;
+
+			// Leaves the bond reserved if the treasury account cannot receive it.
+			let _ = T::Currency::repatriate_reserved(
+				&proposal.proposer,
+				&T::Treasury::get(),
+				proposal.bond,
+				BalanceStatus::Free,
+			);
+			<Proposals<T>>::remove(proposal_id);
+
+			Self::deposit_event(Event::SpendRejected(proposal_id, proposal.bond));
+			Ok(Pays::No.into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The term of the current block.
+		pub fn current_term() -> Term {
+			let now = <frame_system::Pallet<T>>::block_number();
+			(now / T::TermLength::get().max(1u32.into())).saturated_into()
+		}
+	}
+}
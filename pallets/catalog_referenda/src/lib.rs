@@ -0,0 +1,407 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Referenda on changes to the course catalog, with votes weighted by role and stake.
+//!
+//! `ProposeOrigin` puts a curriculum change, described by the hash of a document kept off-chain,
+//! to a referendum lasting `VotingPeriod` blocks. Professors, students and alumni vote on it with a
+//! stake and a conviction. The power of a vote is the weight of the voter's role, set by
+//! `WeightOrigin`, times the stake, capped at `MaxVoteStake`, times the conviction multiplier. The
+//! cap keeps large balances from outvoting the roles. The stake is locked until the referendum
+//! ends, and for `LockPeriod` blocks per conviction level after that. Once the referendum ended,
+//! anyone closes it, recording whether the ayes outweighed the nays.
+
+pub use pallet::*;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+
+/// The role an account votes in.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum Role {
+	Professor,
+	Student,
+	Alumnus,
+}
+
+/// The roles of accounts.
+pub trait Roles<AccountId> {
+	/// The role of `who`, `None` if it cannot vote.
+	fn role(who: &AccountId) -> Option<Role>;
+}
+
+/// How long a voter locks their stake for, multiplying the power of their vote.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Conviction {
+	/// A tenth of the power, locked until the referendum ends.
+	None,
+	/// The power, locked for one `LockPeriod` after the referendum ends.
+	Locked1x,
+	/// Twice the power, locked for two `LockPeriod`s.
+	Locked2x,
+	/// Three times the power, locked for four `LockPeriod`s.
+	Locked3x,
+	/// Four times the power, locked for eight `LockPeriod`s.
+	Locked4x,
+	/// Five times the power, locked for sixteen `LockPeriod`s.
+	Locked5x,
+	/// Six times the power, locked for thirty-two `LockPeriod`s.
+	Locked6x,
+}
+
+impl Conviction {
+	/// The multiplier of the power of a vote, in tenths.
+	pub fn multiplier(self) -> u128 {
+		match self {
+			Conviction::None => 1,
+			Conviction::Locked1x => 10,
+			Conviction::Locked2x => 20,
+			Conviction::Locked3x => 30,
+			Conviction::Locked4x => 40,
+			Conviction::Locked5x => 50,
+			Conviction::Locked6x => 60,
+		}
+	}
+
+	/// The number of `LockPeriod`s the stake stays locked after the referendum ends.
+	pub fn lock_periods(self) -> u32 {
+		match self {
+			Conviction::None => 0,
+			Conviction::Locked1x => 1,
+			Conviction::Locked2x => 2,
+			Conviction::Locked3x => 4,
+			Conviction::Locked4x => 8,
+			Conviction::Locked5x => 16,
+			Conviction::Locked6x => 32,
+		}
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::{Conviction, Role, Roles};
+	use frame_support::{
+		pallet_prelude::*,
+		sp_runtime::traits::{Saturating, UniqueSaturatedInto, Zero},
+		traits::{Currency, LockIdentifier, LockableCurrency, WithdrawReasons},
+	};
+	use frame_system::pallet_prelude::*;
+
+	type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// The identifier of the lock on the stakes of voters.
+	const LOCK_ID: LockIdentifier = *b"catalogr";
+
+	/// Identifier of a referendum.
+	pub type ReferendumId = u32;
+
+	// Struct for holding a referendum.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Referendum<T: Config> {
+		/// The hash of the document describing the curriculum change.
+		pub description: T::Hash,
+		/// The last block votes are accepted in.
+		pub end: T::BlockNumber,
+		pub ayes: u128,
+		pub nays: u128,
+		/// Whether the referendum passed, once it is closed.
+		pub passed: Option<bool>,
+	}
+
+	// Struct for holding a vote.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Vote<Balance> {
+		pub aye: bool,
+		pub stake: Balance,
+		pub conviction: Conviction,
+		/// The power counted in the tally.
+		pub power: u128,
+	}
+
+	// Struct for holding the stake locked from a voter.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct VoteLock<Balance, BlockNumber> {
+		/// The largest stake of the voter's votes.
+		pub amount: Balance,
+		/// The block the stake can be unlocked from.
+		pub until: BlockNumber,
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types it depends on.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency stakes are locked in.
+		type Currency: LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
+
+		/// The roles of the voters.
+		type Roles: Roles<Self::AccountId>;
+
+		/// Origin allowed to start referenda.
+		type ProposeOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin allowed to set the weights of the roles.
+		type WeightOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The number of blocks a referendum accepts votes for.
+		#[pallet::constant]
+		type VotingPeriod: Get<Self::BlockNumber>;
+
+		/// The number of blocks a stake stays locked for per conviction level.
+		#[pallet::constant]
+		type LockPeriod: Get<Self::BlockNumber>;
+
+		/// The largest stake counted in the power of a vote.
+		#[pallet::constant]
+		type MaxVoteStake: Get<BalanceOf<Self>>;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn referenda)]
+	/// The referenda, including closed ones.
+	pub(super) type Referenda<T: Config> = StorageMap<_, Twox64Concat, ReferendumId, Referendum<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_referendum_id)]
+	/// The identifier of the next referendum.
+	pub(super) type NextReferendumId<T: Config> = StorageValue<_, ReferendumId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn votes)]
+	/// The vote of each voter in each referendum.
+	pub(super) type Votes<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ReferendumId,
+		Blake2_128Concat,
+		T::AccountId,
+		Vote<BalanceOf<T>>,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn vote_locks)]
+	/// The stake locked from each voter.
+	pub(super) type VoteLocks<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, VoteLock<BalanceOf<T>, T::BlockNumber>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn role_weights)]
+	/// The weight of the votes of each role. Roles without a weight cannot vote.
+	pub(super) type RoleWeights<T: Config> = StorageMap<_, Twox64Concat, Role, u32, ValueQuery>;
+
+	// Our pallet's genesis configuration.
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {
+		/// The weight of the votes of each role.
+		pub role_weights: Vec<(Role, u32)>,
+	}
+
+	// Required to implement default for GenesisConfig.
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> GenesisConfig {
+			GenesisConfig { role_weights: vec![] }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			for (role, weight) in &self.role_weights {
+				<RoleWeights<T>>::insert(role, weight);
+			}
+		}
+	}
+
+	// Errors.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Ran out of referendum ids.
+		ReferendumIdOverflow,
+		/// The referendum does not exist.
+		ReferendumNotExist,
+		/// The referendum ended.
+		ReferendumEnded,
+		/// The referendum has not ended yet.
+		ReferendumNotEnded,
+		/// The referendum was closed already.
+		ReferendumClosed,
+		/// The account has no role with a weight.
+		NoVotingRole,
+		/// A stake must not be zero.
+		ZeroStake,
+		/// The account cannot lock the stake.
+		NotEnoughBalance,
+		/// The account has no locked stake.
+		NotLocked,
+		/// The stake is still locked.
+		StillLocked,
+	}
+
+	// Events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A referendum was started. \[referendum_id, description, end\]
+		Started(ReferendumId, T::Hash, T::BlockNumber),
+		/// An account voted. \[referendum_id, who, role, aye, power\]
+		Voted(ReferendumId, T::AccountId, Role, bool, u128),
+		/// A referendum was closed. \[referendum_id, passed, ayes, nays\]
+		Closed(ReferendumId, bool, u128, u128),
+		/// The weight of a role was set. \[role, weight\]
+		RoleWeightSet(Role, u32),
+		/// The stake of an account was unlocked. \[who\]
+		Unlocked(T::AccountId),
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Start a referendum on the curriculum change described by `description`.
+		///
+		/// The dispatch origin for this call must be `ProposeOrigin`.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2))]
+		pub fn propose(origin: OriginFor<T>, description: T::Hash) -> DispatchResult {
+			T::ProposeOrigin::ensure_origin(origin)?;
+
+			let id = Self::next_referendum_id();
+			let next_id = id.checked_add(1).ok_or(<Error<T>>::ReferendumIdOverflow)?;
+			let end =
+				<frame_system::Pallet<T>>::block_number().saturating_add(T::VotingPeriod::get());
+			<Referenda<T>>::insert(
+				id,
+				Referendum { description, end, ayes: 0, nays: 0, passed: None },
+			);
+			<NextReferendumId<T>>::put(next_id);
+
+			Self::deposit_event(Event::Started(id, description, end));
+			Ok(())
+		}
+
+		/// Vote on a referendum with `stake` and `conviction`, replacing any earlier vote of the
+		/// sender.
+		///
+		/// The stake is locked until the referendum ends and the conviction's lock periods pass.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 4))]
+		pub fn vote(
+			origin: OriginFor<T>,
+			referendum_id: ReferendumId,
+			aye: bool,
+			stake: BalanceOf<T>,
+			conviction: Conviction,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut referendum =
+				Self::referenda(referendum_id).ok_or(<Error<T>>::ReferendumNotExist)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() <= referendum.end,
+				<Error<T>>::ReferendumEnded
+			);
+			let role = T::Roles::role(&who).ok_or(<Error<T>>::NoVotingRole)?;
+			let weight = Self::role_weights(role);
+			ensure!(weight > 0, <Error<T>>::NoVotingRole);
+			ensure!(!stake.is_zero(), <Error<T>>::ZeroStake);
+			ensure!(T::Currency::free_balance(&who) >= stake, <Error<T>>::NotEnoughBalance);
+
+			if let Some(previous) = <Votes<T>>::get(referendum_id, &who) {
+				Self::tally(&mut referendum, previous.aye, previous.power, false);
+			}
+			let counted: u128 = stake.min(T::MaxVoteStake::get()).unique_saturated_into();
+			let power =
+				(weight as u128).saturating_mul(counted).saturating_mul(conviction.multiplier());
+			Self::tally(&mut referendum, aye, power, true);
+
+			let lock_periods: T::BlockNumber = conviction.lock_periods().into();
+			let until =
+				referendum.end.saturating_add(T::LockPeriod::get().saturating_mul(lock_periods));
+			let lock = match Self::vote_locks(&who) {
+				Some(lock) =>
+					VoteLock { amount: lock.amount.max(stake), until: lock.until.max(until) },
+				None => VoteLock { amount: stake, until },
+			};
+			T::Currency::set_lock(LOCK_ID, &who, lock.amount, WithdrawReasons::TRANSFER);
+			<VoteLocks<T>>::insert(&who, lock);
+			<Votes<T>>::insert(referendum_id, &who, Vote { aye, stake, conviction, power });
+			<Referenda<T>>::insert(referendum_id, referendum);
+
+			Self::deposit_event(Event::Voted(referendum_id, who, role, aye, power));
+			Ok(())
+		}
+
+		/// Close a referendum that ended, recording whether it passed.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn close(origin: OriginFor<T>, referendum_id: ReferendumId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let mut referendum =
+				Self::referenda(referendum_id).ok_or(<Error<T>>::ReferendumNotExist)?;
+			ensure!(referendum.passed.is_none(), <Error<T>>::ReferendumClosed);
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() > referendum.end,
+				<Error<T>>::ReferendumNotEnded
+			);
+
+			let passed = referendum.ayes > referendum.nays;
+			referendum.passed = Some(passed);
+			let (ayes, nays) = (referendum.ayes, referendum.nays);
+			<Referenda<T>>::insert(referendum_id, referendum);
+
+			Self::deposit_event(Event::Closed(referendum_id, passed, ayes, nays));
+			Ok(())
+		}
+
+		/// Unlock the stake of the sender once all its locks expired.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn unlock(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let lock = Self::vote_locks(&who).ok_or(<Error<T>>::NotLocked)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= lock.until,
+				<Error<T>>::StillLocked
+			);
+
+			T::Currency::remove_lock(LOCK_ID, &who);
+			<VoteLocks<T>>::remove(&who);
+
+			Self::deposit_event(Event::Unlocked(who));
+			Ok(())
+		}
+
+		/// Set the weight of the votes of `role`, `0` to keep it from voting.
+		///
+		/// Votes already cast keep their power.
+		///
+		/// The dispatch origin for this call must be `WeightOrigin`.
+		#[pallet::weight((
+			10_000 + T::DbWeight::get().reads_writes(0, 1),
+			DispatchClass::Operational,
+		))]
+		pub fn set_role_weight(
+			origin: OriginFor<T>,
+			role: Role,
+			weight: u32,
+		) -> DispatchResultWithPostInfo {
+			T::WeightOrigin::ensure_origin(origin)?;
+
+			<RoleWeights<T>>::insert(role, weight);
+
+			Self::deposit_event(Event::RoleWeightSet(role, weight));
+			Ok(Pays::No.into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Add `power` to the ayes or the nays of `referendum`, or remove it unless `add`.
+		fn tally(referendum: &mut Referendum<T>, aye: bool, power: u128, add: bool) {
+			let side = if aye { &mut referendum.ayes } else { &mut referendum.nays };
+			*side = if add { side.saturating_add(power) } else { side.saturating_sub(power) };
+		}
+	}
+}
@@ -0,0 +1,253 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Randomness produced by participants committing to secrets and revealing them later.
+//!
+//! Time is split into rounds of `CommitPeriod` blocks followed by `RevealPeriod` blocks. During
+//! the commit period, participants `commit` the hash of their account and a secret, reserving
+//! `CommitDeposit`. During the reveal period they `reveal` the secret, which is mixed into the
+//! seed of the round and returns their deposit. Once the round is over, its seed is published if
+//! at least `MinReveals` secrets were revealed, and the deposits of the commitments that were not
+//! revealed are slashed.
+//!
+//! A single honest participant is enough for the seed to be unpredictable, and unlike block
+//! randomness no block producer can choose it. The last participants to reveal can still withhold
+//! their secret once they know the others, at the cost of their deposit, so the deposit should be
+//! worth more than anything riding on a single seed.
+//!
+//! Consumers either draw from the seed of a round they fixed beforehand with `seed`, such as a
+//! lottery drawing from the first round starting after entries close, or from the latest seed
+//! through `Randomness`.
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		sp_runtime::traits::{Hash, Saturating, UniqueSaturatedInto, Zero},
+		traits::{Currency, OnUnbalanced, Randomness, ReservableCurrency},
+	};
+	use frame_system::pallet_prelude::*;
+
+	type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	/// Index of a round, counted from genesis.
+	pub type RoundId = u32;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types it depends on.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency the deposits are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved with each commitment, returned when the secret is revealed.
+		#[pallet::constant]
+		type CommitDeposit: Get<BalanceOf<Self>>;
+
+		/// Handler for the deposits of the commitments that were not revealed.
+		type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// The number of blocks at the start of a round during which participants commit.
+		#[pallet::constant]
+		type CommitPeriod: Get<Self::BlockNumber>;
+
+		/// The number of blocks after the commit period during which participants reveal.
+		#[pallet::constant]
+		type RevealPeriod: Get<Self::BlockNumber>;
+
+		/// The maximum number of commitments in a round.
+		#[pallet::constant]
+		type MaxParticipants: Get<u32>;
+
+		/// The number of revealed secrets below which a round publishes no seed.
+		#[pallet::constant]
+		type MinReveals: Get<u32>;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn commitments)]
+	/// The commitment of each participant of a round, until its secret is revealed.
+	pub(super) type Commitments<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, RoundId, Blake2_128Concat, T::AccountId, T::Hash>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn participants)]
+	/// The accounts that committed in each round, until the round is over.
+	pub(super) type Participants<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		RoundId,
+		BoundedVec<T::AccountId, T::MaxParticipants>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reveals)]
+	/// The secrets of each round mixed together and their number, until the round is over.
+	pub(super) type Reveals<T: Config> =
+		StorageMap<_, Twox64Concat, RoundId, (T::Hash, u32), ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn seed)]
+	/// The seed published by each round with enough reveals.
+	pub(super) type Seeds<T: Config> = StorageMap<_, Twox64Concat, RoundId, T::Hash>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn latest_seed)]
+	/// The latest published seed and the block it was published at.
+	pub(super) type LatestSeed<T: Config> = StorageValue<_, (T::Hash, T::BlockNumber)>;
+
+	// Errors.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The current round is past its commit period.
+		NotCommitPeriod,
+		/// The current round is not in its reveal period.
+		NotRevealPeriod,
+		/// The account committed in this round already.
+		AlreadyCommitted,
+		/// The round has `MaxParticipants` commitments already.
+		TooManyParticipants,
+		/// The account did not commit in this round, or revealed already.
+		NoCommitment,
+		/// The secret does not match the commitment.
+		InvalidReveal,
+	}
+
+	// Events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An account committed to a secret. \[round, who\]
+		Committed(RoundId, T::AccountId),
+		/// An account revealed its secret. \[round, who\]
+		Revealed(RoundId, T::AccountId),
+		/// A round was over with enough reveals and published its seed. \[round, seed, reveals\]
+		SeedPublished(RoundId, T::Hash, u32),
+		/// A round was over with too few reveals to publish a seed. \[round, reveals\]
+		RoundFailed(RoundId, u32),
+		/// The deposit of a commitment that was not revealed was slashed. \[round, who\]
+		DepositSlashed(RoundId, T::AccountId),
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let (round, offset) = Self::round_at(n);
+			if round.is_zero() || !offset.is_zero() {
+				return 0
+			}
+			Self::close_round(round - 1)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Commit to a secret in the current round, reserving `CommitDeposit`.
+		///
+		/// `commitment` is the hash of the SCALE-encoded pair of the sender and its secret, so
+		/// other accounts cannot copy it.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn commit(origin: OriginFor<T>, commitment: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (round, offset) = Self::round_at(<frame_system::Pallet<T>>::block_number());
+			ensure!(offset < T::CommitPeriod::get(), <Error<T>>::NotCommitPeriod);
+			ensure!(!<Commitments<T>>::contains_key(round, &who), <Error<T>>::AlreadyCommitted);
+
+			let mut participants = Self::participants(round);
+			participants
+				.try_push(who.clone())
+				.map_err(|_| <Error<T>>::TooManyParticipants)?;
+			T::Currency::reserve(&who, T::CommitDeposit::get())?;
+			<Participants<T>>::insert(round, participants);
+			<Commitments<T>>::insert(round, &who, commitment);
+
+			Self::deposit_event(Event::Committed(round, who));
+			Ok(())
+		}
+
+		/// Reveal the secret committed to in the current round, returning the deposit.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn reveal(origin: OriginFor<T>, secret: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (round, offset) = Self::round_at(<frame_system::Pallet<T>>::block_number());
+			ensure!(offset >= T::CommitPeriod::get(), <Error<T>>::NotRevealPeriod);
+
+			let commitment = Self::commitments(round, &who).ok_or(<Error<T>>::NoCommitment)?;
+			ensure!(T::Hashing::hash_of(&(&who, secret)) == commitment, <Error<T>>::InvalidReveal);
+
+			<Commitments<T>>::remove(round, &who);
+			<Reveals<T>>::mutate(round, |(mixed, count)| {
+				*mixed = *mixed ^ secret;
+				*count = count.saturating_add(1);
+			});
+			T::Currency::unreserve(&who, T::CommitDeposit::get());
+
+			Self::deposit_event(Event::Revealed(round, who));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The round block `n` is in, and the number of blocks since the round started.
+		pub fn round_at(n: T::BlockNumber) -> (RoundId, T::BlockNumber) {
+			let length = T::CommitPeriod::get().saturating_add(T::RevealPeriod::get());
+			if length.is_zero() {
+				return (0, n)
+			}
+			((n / length).unique_saturated_into(), n % length)
+		}
+
+		/// Publish the seed of `round`, which is over, and slash the deposits of the commitments
+		/// that were not revealed. Rounds nobody committed in are skipped.
+		fn close_round(round: RoundId) -> Weight {
+			let participants = <Participants<T>>::take(round);
+			if participants.is_empty() {
+				return T::DbWeight::get().reads_writes(1, 1)
+			}
+			let (mixed, reveals) = <Reveals<T>>::take(round);
+			let mut slashed = 0;
+			for who in participants.iter() {
+				if <Commitments<T>>::take(round, who).is_some() {
+					let (imbalance, _) = T::Currency::slash_reserved(who, T::CommitDeposit::get());
+					T::Slashed::on_unbalanced(imbalance);
+					Self::deposit_event(Event::DepositSlashed(round, who.clone()));
+					slashed += 1;
+				}
+			}
+
+			if reveals >= T::MinReveals::get() {
+				let seed = T::Hashing::hash_of(&(b"commit-reveal", round, mixed));
+				<Seeds<T>>::insert(round, seed);
+				<LatestSeed<T>>::put((seed, <frame_system::Pallet<T>>::block_number()));
+				Self::deposit_event(Event::SeedPublished(round, seed, reveals));
+			} else {
+				Self::deposit_event(Event::RoundFailed(round, reveals));
+			}
+
+			T::DbWeight::get().reads_writes(
+				participants.len() as Weight + slashed + 2,
+				participants.len() as Weight + slashed + 4,
+			)
+		}
+	}
+
+	impl<T: Config> Randomness<T::Hash, T::BlockNumber> for Pallet<T> {
+		/// The latest seed mixed with `subject`, and the block the seed was published at. Before
+		/// any seed is published, a hash of `subject` alone and the genesis block.
+		fn random(subject: &[u8]) -> (T::Hash, T::BlockNumber) {
+			let (seed, published) = Self::latest_seed().unwrap_or_default();
+			(T::Hashing::hash_of(&(subject, seed)), published)
+		}
+	}
+}
@@ -0,0 +1,23 @@
+//! Traits other pallets inspect the course registry through.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Read access to the course registry, for pallets built around courses.
+///
+/// Pallets take an implementation in their `Config` instead of reading the storage of the
+/// registry, so they can be tested against a mock registry and reused with another one.
+pub trait CourseInspector<AccountId, CourseId> {
+	/// The year of the curriculum courses belong to.
+	type Year;
+
+	/// The owner of `course_id`, if the course exists.
+	fn owner_of(course_id: &CourseId) -> Option<AccountId>;
+
+	/// The year of `course_id`, if the course exists.
+	fn year_of(course_id: &CourseId) -> Option<Self::Year>;
+
+	/// Whether `course_id` exists.
+	fn exists(course_id: &CourseId) -> bool {
+		Self::owner_of(course_id).is_some()
+	}
+}
@@ -0,0 +1,542 @@
+//! RPC methods for the course registry.
+
+use std::{fmt::Display, marker::PhantomData, sync::Arc};
+
+use codec::{Codec, Decode, Encode};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_course_passing::{
+	archive,
+	blobs::{self, Blob},
+	dna,
+	history::{history_key, CourseRecord},
+	CourseYear, Term,
+};
+pub use pallet_course_passing_runtime_api::{
+	CourseArchiveApi as CourseArchiveRuntimeApi, CourseAuditApi as CourseAuditRuntimeApi,
+	CourseMetadataApi as CourseMetadataRuntimeApi, CourseProofApi as CourseProofRuntimeApi,
+	CourseTradesApi as CourseTradesRuntimeApi,
+};
+use sc_client_api::ProofProvider;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	hexdisplay::HexDisplay,
+	offchain::{OffchainStorage, STORAGE_PREFIX},
+	Bytes,
+};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor, Saturating, UniqueSaturatedInto, Zero},
+};
+
+/// A course entry of the state, with a proof of it against the state root of block `at`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseProof<Hash> {
+	/// The finalized block the proof was generated at.
+	pub at: Hash,
+	/// The storage key of the course.
+	pub key: Bytes,
+	/// The SCALE-encoded course, or `None` if it does not exist.
+	pub value: Option<Bytes>,
+	/// The trie nodes proving `value` is stored under `key`.
+	pub proof: Vec<Bytes>,
+}
+
+/// The most courses a page of `courses_auditReport` proves.
+pub const MAX_REPORT_COURSES: u32 = 256;
+
+/// A storage entry the annual report of a year is checked against.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReportEntry {
+	pub key: Bytes,
+	/// The SCALE-encoded value, or `None` if there is none.
+	pub value: Option<Bytes>,
+}
+
+/// A page of the entries the annual report of a year is checked against, with a single proof of
+/// all of them against the state root of block `at`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportProof<Hash> {
+	/// The finalized block the proof was generated at.
+	pub at: Hash,
+	/// The course count, the statistics of the year, the roots of the archived terms and of the
+	/// ownership snapshots of the year, then the courses of the page.
+	pub entries: Vec<ReportEntry>,
+	/// The number of courses among the entries.
+	pub courses: u32,
+	/// The cursor of the next page, `None` once all courses are listed.
+	pub next: Option<Bytes>,
+	/// The trie nodes proving every entry.
+	pub proof: Vec<Bytes>,
+}
+
+/// The earliest block a node can prove courses at.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProvableBlock<Hash> {
+	pub number: u64,
+	pub hash: Hash,
+}
+
+/// A course in the metadata format NFT marketplaces and explorers understand.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CourseNft<AccountId> {
+	pub name: String,
+	pub description: String,
+	/// The course materials, as an `ipfs://` URI.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub image: Option<String>,
+	pub owner: AccountId,
+	pub attributes: Vec<NftAttribute>,
+}
+
+/// A trait of an NFT, displayed by marketplaces as a property.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NftAttribute {
+	pub trait_type: String,
+	pub value: String,
+}
+
+impl NftAttribute {
+	fn new(trait_type: &str, value: impl Display) -> Self {
+		Self { trait_type: trait_type.into(), value: value.to_string() }
+	}
+}
+
+/// A change of ownership of a course, as recorded in the off-chain index.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CourseTransfer<AccountId> {
+	pub block: u64,
+	/// The previous owner, `None` when the course was minted.
+	pub from: Option<AccountId>,
+	pub to: AccountId,
+	/// The price paid in the smallest balance unit, if the course was bought.
+	pub price: Option<String>,
+}
+
+/// A sale of a course, from the recent trades the chain keeps.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentTrade<AccountId, Hash> {
+	pub course_id: Hash,
+	pub seller: AccountId,
+	pub buyer: AccountId,
+	/// The price paid in the smallest balance unit.
+	pub price: String,
+	pub block: u64,
+}
+
+#[rpc]
+pub trait CoursesApi<BlockHash, AccountId> {
+	/// Prove the state of a course at block `at`, by default the latest finalized block.
+	///
+	/// A light client holding a GRANDPA-finalized header for `at` can check the proof without
+	/// trusting the node.
+	#[rpc(name = "courses_proveCourse")]
+	fn prove_course(
+		&self,
+		course_id: BlockHash,
+		at: Option<BlockHash>,
+	) -> Result<CourseProof<BlockHash>>;
+
+	/// The earliest block whose state the node keeps, and can prove courses at.
+	///
+	/// Pruning only discards the states of older blocks; the latest finalized state, and every
+	/// course in it, stays provable whatever the pruning mode.
+	#[rpc(name = "courses_earliestProvableBlock")]
+	fn earliest_provable_block(&self) -> Result<ProvableBlock<BlockHash>>;
+
+	/// The metadata of a course as an NFT, assembled from its on-chain fields.
+	#[rpc(name = "courses_nftMetadata")]
+	fn nft_metadata(
+		&self,
+		course_id: BlockHash,
+		at: Option<BlockHash>,
+	) -> Result<Option<CourseNft<AccountId>>>;
+
+	/// The latest sales of courses at block `at`, by default the best block, newest first.
+	///
+	/// At most `limit` sales are returned, and never more than the chain keeps.
+	#[rpc(name = "courses_recentTrades")]
+	fn recent_trades(
+		&self,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> Result<Vec<RecentTrade<AccountId, BlockHash>>>;
+
+	/// Read an archived course of `term` at block `at`, by default the latest finalized block,
+	/// with a proof of it.
+	///
+	/// The key is the key of the course in the child trie of the term, and the value its
+	/// SCALE-encoded `ArchivedCourse`. The proof covers the root of the child trie in the state.
+	#[rpc(name = "courses_archivedCourse")]
+	fn archived_course(
+		&self,
+		term: Term,
+		course_id: BlockHash,
+		at: Option<BlockHash>,
+	) -> Result<CourseProof<BlockHash>>;
+
+	/// Prove a blob of a course at block `at`, by default the latest finalized block.
+	///
+	/// The key is the key of the blob in the child trie of the course, and the value the blob.
+	/// The proof covers the root of the child trie in the state, which the `blobsRoot` of the
+	/// course proven with `courses_proveCourse` commits to.
+	#[rpc(name = "courses_proveBlob")]
+	fn prove_blob(
+		&self,
+		course_id: BlockHash,
+		blob: Blob,
+		at: Option<BlockHash>,
+	) -> Result<CourseProof<BlockHash>>;
+
+	/// Prove the entries the annual report of `course_year` is checked against at the finalized
+	/// block `at`, by default the latest one, with up to `limit` courses from `cursor`, and never
+	/// more than `MAX_REPORT_COURSES`.
+	///
+	/// Every page proves the course count, the statistics of the year and the Merkle roots of the
+	/// archived terms and of the ownership snapshots of the year. Walking the pages with `next` at
+	/// the same block proves every course.
+	#[rpc(name = "courses_auditReport")]
+	fn audit_report(
+		&self,
+		course_year: CourseYear,
+		cursor: Option<Bytes>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<ReportProof<BlockHash>>;
+}
+
+/// Implements the `CoursesApi` RPC trait for interacting with the course registry.
+pub struct Courses<C, M> {
+	client: Arc<C>,
+	/// The number of blocks of state kept behind the latest finalized block, `None` if the node
+	/// keeps them all.
+	state_pruning: Option<u32>,
+	_marker: PhantomData<M>,
+}
+
+impl<C, M> Courses<C, M> {
+	/// Create new `Courses` with the given reference to the client and its state pruning.
+	pub fn new(client: Arc<C>, state_pruning: Option<u32>) -> Self {
+		Self { client, state_pruning, _marker: Default::default() }
+	}
+}
+
+fn runtime_error(message: &str, e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(1),
+		message: message.into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+fn ipfs_uri(cid: &[u8]) -> String {
+	format!("ipfs://{}", String::from_utf8_lossy(cid))
+}
+
+impl<C, Block, AccountId, Balance> CoursesApi<<Block as BlockT>::Hash, AccountId>
+	for Courses<C, (Block, Balance)>
+where
+	Block: BlockT,
+	Block::Hash: Codec,
+	AccountId: Codec + Send + Sync + 'static,
+	Balance: Codec + Display + Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + ProofProvider<Block>,
+	C: Send + Sync + 'static,
+	C::Api: CourseProofRuntimeApi<Block>,
+	C::Api: CourseMetadataRuntimeApi<Block, AccountId, Balance>,
+	C::Api: CourseTradesRuntimeApi<Block, AccountId, Balance>,
+	C::Api: CourseArchiveRuntimeApi<Block, AccountId, Balance>,
+	C::Api: CourseAuditRuntimeApi<Block, AccountId>,
+{
+	fn prove_course(
+		&self,
+		course_id: <Block as BlockT>::Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<CourseProof<<Block as BlockT>::Hash>> {
+		let at = at.unwrap_or_else(|| self.client.info().finalized_hash);
+		let block = BlockId::hash(at);
+
+		let (key, value) = self
+			.client
+			.runtime_api()
+			.course_storage(&block, course_id)
+			.map_err(|e| runtime_error("Unable to read the course.", e))?;
+		let proof = self
+			.client
+			.read_proof(&block, &mut std::iter::once(key.as_slice()))
+			.map_err(|e| runtime_error("Unable to prove the course.", e))?;
+
+		Ok(CourseProof {
+			at,
+			key: key.into(),
+			value: value.map(Into::into),
+			proof: proof.iter_nodes().map(Into::into).collect(),
+		})
+	}
+
+	fn earliest_provable_block(&self) -> Result<ProvableBlock<<Block as BlockT>::Hash>> {
+		let number = match self.state_pruning {
+			Some(kept) => self.client.info().finalized_number.saturating_sub(kept.into()),
+			None => Zero::zero(),
+		};
+		let hash = self
+			.client
+			.hash(number)
+			.map_err(|e| runtime_error("Unable to read the block.", e))?
+			.ok_or_else(|| runtime_error("The node does not have the block.", number))?;
+
+		Ok(ProvableBlock { number: number.unique_saturated_into(), hash })
+	}
+
+	fn nft_metadata(
+		&self,
+		course_id: <Block as BlockT>::Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<CourseNft<AccountId>>> {
+		let block = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let metadata = self
+			.client
+			.runtime_api()
+			.course_metadata(&block, course_id)
+			.map_err(|e| runtime_error("Unable to read the course.", e))?;
+
+		Ok(metadata.map(|course| {
+			let id = HexDisplay::from(&course_id.as_ref());
+			let year = format!("{:?}", course.year);
+			let traits = dna::traits(&course.dna);
+
+			let mut attributes = vec![
+				NftAttribute::new("Year", &year),
+				NftAttribute::new("DNA", format!("0x{}", HexDisplay::from(&course.dna))),
+				NftAttribute::new("Department", traits.department),
+				NftAttribute::new("Modality", format!("{:?}", traits.modality)),
+				NftAttribute::new("Difficulty", traits.difficulty),
+				NftAttribute::new("Language", traits.language),
+				NftAttribute::new("Rarity", format!("{:?}", traits.rarity)),
+			];
+			if let Some(price) = course.price {
+				attributes.push(NftAttribute::new("Price", price));
+			}
+			if let Some(syllabus) = &course.syllabus {
+				attributes.push(NftAttribute::new("Syllabus", ipfs_uri(syllabus)));
+			}
+
+			CourseNft {
+				name: format!("{} year course 0x{}", year, id),
+				description: format!(
+					"A {} year uni-chain course, transferable between students.",
+					year.to_lowercase()
+				),
+				image: course.materials.as_deref().map(ipfs_uri),
+				owner: course.owner,
+				attributes,
+			}
+		}))
+	}
+
+	fn recent_trades(
+		&self,
+		limit: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<RecentTrade<AccountId, <Block as BlockT>::Hash>>> {
+		let block = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let trades = self
+			.client
+			.runtime_api()
+			.recent_trades(&block, limit.unwrap_or(u32::MAX))
+			.map_err(|e| runtime_error("Unable to read the recent trades.", e))?;
+
+		Ok(trades
+			.into_iter()
+			.map(|trade| RecentTrade {
+				course_id: trade.course_id,
+				seller: trade.seller,
+				buyer: trade.buyer,
+				price: trade.price.to_string(),
+				block: trade.block.unique_saturated_into(),
+			})
+			.collect())
+	}
+
+	fn archived_course(
+		&self,
+		term: Term,
+		course_id: <Block as BlockT>::Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<CourseProof<<Block as BlockT>::Hash>> {
+		let at = at.unwrap_or_else(|| self.client.info().finalized_hash);
+		let block = BlockId::hash(at);
+
+		let archived = self
+			.client
+			.runtime_api()
+			.archived_course(&block, term, course_id)
+			.map_err(|e| runtime_error("Unable to read the archive.", e))?;
+		let key = archive::key(&course_id);
+		let proof = self
+			.client
+			.read_child_proof(
+				&block,
+				&archive::child_info(term),
+				&mut std::iter::once(key.as_slice()),
+			)
+			.map_err(|e| runtime_error("Unable to prove the archived course.", e))?;
+
+		Ok(CourseProof {
+			at,
+			key: key.into(),
+			value: archived.map(|course| course.encode().into()),
+			proof: proof.iter_nodes().map(Into::into).collect(),
+		})
+	}
+
+	fn prove_blob(
+		&self,
+		course_id: <Block as BlockT>::Hash,
+		blob: Blob,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<CourseProof<<Block as BlockT>::Hash>> {
+		let at = at.unwrap_or_else(|| self.client.info().finalized_hash);
+		let block = BlockId::hash(at);
+
+		let value = self
+			.client
+			.runtime_api()
+			.course_blob(&block, course_id, blob)
+			.map_err(|e| runtime_error("Unable to read the blob.", e))?;
+		let key = blobs::key(blob);
+		let proof = self
+			.client
+			.read_child_proof(
+				&block,
+				&blobs::child_info(&course_id),
+				&mut std::iter::once(key.as_slice()),
+			)
+			.map_err(|e| runtime_error("Unable to prove the blob.", e))?;
+
+		Ok(CourseProof {
+			at,
+			key: key.into(),
+			value: value.map(Into::into),
+			proof: proof.iter_nodes().map(Into::into).collect(),
+		})
+	}
+
+	fn audit_report(
+		&self,
+		course_year: CourseYear,
+		cursor: Option<Bytes>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<ReportProof<<Block as BlockT>::Hash>> {
+		let info = self.client.info();
+		let at = at.unwrap_or(info.finalized_hash);
+		let number = self
+			.client
+			.number(at)
+			.map_err(|e| runtime_error("Unable to read the block.", e))?
+			.ok_or_else(|| runtime_error("The node does not have the block.", at))?;
+		// Blocks of abandoned forks at or below the finalized height are not finalized either.
+		let canonical = self
+			.client
+			.hash(number)
+			.map_err(|e| runtime_error("Unable to read the block.", e))?;
+		if number > info.finalized_number || canonical != Some(at) {
+			return Err(runtime_error("The block is not finalized.", at))
+		}
+		let block = BlockId::hash(at);
+
+		let page = self
+			.client
+			.runtime_api()
+			.report_storage(
+				&block,
+				course_year,
+				cursor.map(|cursor| cursor.0),
+				limit.min(MAX_REPORT_COURSES),
+			)
+			.map_err(|e| runtime_error("Unable to read the report entries.", e))?;
+		let proof = self
+			.client
+			.read_proof(&block, &mut page.entries.iter().map(|(key, _)| key.as_slice()))
+			.map_err(|e| runtime_error("Unable to prove the report entries.", e))?;
+
+		Ok(ReportProof {
+			at,
+			entries: page
+				.entries
+				.into_iter()
+				.map(|(key, value)| ReportEntry { key: key.into(), value: value.map(Into::into) })
+				.collect(),
+			courses: page.courses,
+			next: page.next.map(Into::into),
+			proof: proof.iter_nodes().map(Into::into).collect(),
+		})
+	}
+}
+
+#[rpc]
+pub trait CourseHistoryApi<BlockHash, AccountId> {
+	/// Every mint, transfer and sale of a course, oldest first.
+	///
+	/// Only served by nodes run with `--enable-offchain-indexing` since the course was minted.
+	#[rpc(name = "courses_history")]
+	fn history(&self, course_id: BlockHash) -> Result<Vec<CourseTransfer<AccountId>>>;
+}
+
+/// Implements the `CourseHistoryApi` RPC trait by reading the off-chain index.
+pub struct CourseHistory<S, M> {
+	storage: Option<S>,
+	_marker: PhantomData<M>,
+}
+
+impl<S, M> CourseHistory<S, M> {
+	/// Create new `CourseHistory` reading the given off-chain storage, if the node has one.
+	pub fn new(storage: Option<S>) -> Self {
+		Self { storage, _marker: Default::default() }
+	}
+}
+
+impl<S, Block, AccountId, Balance> CourseHistoryApi<<Block as BlockT>::Hash, AccountId>
+	for CourseHistory<S, (Block, Balance)>
+where
+	S: OffchainStorage + 'static,
+	Block: BlockT,
+	Block::Hash: Codec,
+	AccountId: Codec + Send + Sync + 'static,
+	Balance: Codec + Display + Send + Sync + 'static,
+{
+	fn history(
+		&self,
+		course_id: <Block as BlockT>::Hash,
+	) -> Result<Vec<CourseTransfer<AccountId>>> {
+		let storage = self.storage.as_ref().ok_or_else(|| RpcError {
+			code: ErrorCode::ServerError(2),
+			message: "The node has no off-chain storage.".into(),
+			data: None,
+		})?;
+
+		let mut history = Vec::new();
+		for index in 0.. {
+			let raw = match storage.get(STORAGE_PREFIX, &history_key(&course_id, index)) {
+				Some(raw) => raw,
+				None => break,
+			};
+			let record =
+				CourseRecord::<AccountId, Balance, NumberFor<Block>>::decode(&mut &raw[..])
+					.map_err(|e| runtime_error("Unable to decode a course record.", e))?;
+			history.push(CourseTransfer {
+				block: record.block.unique_saturated_into(),
+				from: record.from,
+				to: record.to,
+				price: record.price.map(|price| price.to_string()),
+			});
+		}
+		Ok(history)
+	}
+}
@@ -0,0 +1,140 @@
+//! Runtime API definitions for the course registry.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use pallet_course_passing::{
+	archive::ArchivedCourse,
+	audit::{AuditPage, ReportPage},
+	blobs::Blob,
+	dna::Dna,
+	ChainStats, CourseYear, MarketSummary, Term, Trade, YearStats,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::NumberFor, RuntimeDebug};
+use sp_std::vec::Vec;
+
+/// Description of a course event, for indexers.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct EventSchema {
+	/// The variant name of the event.
+	pub name: Vec<u8>,
+	/// The names of the event's fields, in order.
+	pub fields: Vec<Vec<u8>>,
+	/// The names of the topics the event is indexed by, in order.
+	pub topics: Vec<Vec<u8>>,
+}
+
+/// The on-chain fields describing a course.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct CourseMetadata<AccountId, Balance> {
+	pub owner: AccountId,
+	pub year: CourseYear,
+	pub dna: Dna,
+	pub price: Option<Balance>,
+	/// The CID of the syllabus.
+	pub syllabus: Option<Vec<u8>>,
+	/// The CID of the course materials.
+	pub materials: Option<Vec<u8>>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Schema of the course events and derivation of their topics.
+	///
+	/// Indexers can filter events by account, collection or course with the topics returned here,
+	/// without decoding every block.
+	pub trait CourseEventsApi<AccountId> where AccountId: Codec {
+		/// The events of the course registry.
+		fn event_schema() -> Vec<EventSchema>;
+		/// The `owner` topic of the events about the courses of `who`.
+		fn owner_topic(who: AccountId) -> Block::Hash;
+		/// The `collection` topic of the events about the courses of `year`.
+		fn collection_topic(year: CourseYear) -> Block::Hash;
+		/// The `account` topic of the events `who` is a party of.
+		fn account_topic(who: AccountId) -> Block::Hash;
+		/// The `course` topic of the events about `course_id`.
+		fn course_topic(course_id: Block::Hash) -> Block::Hash;
+	}
+
+	/// The fields of a course, for rendering it as an NFT.
+	pub trait CourseMetadataApi<AccountId, Balance> where AccountId: Codec, Balance: Codec {
+		/// The metadata of `course_id`, if the course exists.
+		fn course_metadata(course_id: Block::Hash) -> Option<CourseMetadata<AccountId, Balance>>;
+	}
+
+	/// All the courses, a page at a time, for exports.
+	pub trait CourseExportApi<AccountId, Balance> where AccountId: Codec, Balance: Codec {
+		/// Up to `limit` courses with their metadata from `cursor`, the cursor of the previous
+		/// page or `None` to start over, and the cursor of the next page unless it was the last.
+		fn courses(
+			cursor: Option<Vec<u8>>,
+			limit: u32,
+		) -> (Vec<(Block::Hash, CourseMetadata<AccountId, Balance>)>, Option<Vec<u8>>);
+	}
+
+	/// The limits applying to an account.
+	pub trait CourseQuotaApi<AccountId> where AccountId: Codec {
+		/// The number of courses `who` can still create, breed or clone this session.
+		fn remaining_mints(who: AccountId) -> u32;
+	}
+
+	/// Statistics of the course market.
+	pub trait CourseMarketApi<Balance> where Balance: Codec {
+		/// The number of sales of courses of `year`, their volume and the lowest price paid, over
+		/// the last sessions.
+		fn market_stats(year: CourseYear) -> MarketSummary<Balance>;
+	}
+
+	/// The latest sales of courses, for showing recent activity without an indexer.
+	pub trait CourseTradesApi<AccountId, Balance> where AccountId: Codec, Balance: Codec {
+		/// Up to `limit` of the latest sales, newest first.
+		fn recent_trades(
+			limit: u32,
+		) -> Vec<Trade<AccountId, Block::Hash, Balance, NumberFor<Block>>>;
+	}
+
+	/// Statistics of the whole registry, for public dashboards.
+	#[api_version(2)]
+	pub trait CourseStatsApi<Balance> where Balance: Codec {
+		/// The number of courses and of their owners, the value of the courses listed at a fixed
+		/// price, and the sales of all years over the last sessions.
+		fn chain_stats() -> ChainStats<Balance>;
+		/// The number of courses of `year` minted, burned and sold, with the volume and average
+		/// price of the sales, since storage version 4.
+		fn year_stats(year: CourseYear) -> YearStats<Balance>;
+	}
+
+	/// Access to the raw storage of courses, for building state proofs.
+	#[api_version(2)]
+	pub trait CourseProofApi {
+		/// The storage key of `course_id` in the `Courses` map and its SCALE-encoded value, if
+		/// the course exists.
+		fn course_storage(course_id: Block::Hash) -> (Vec<u8>, Option<Vec<u8>>);
+		/// The `blob` of `course_id`, from the child trie of the course, if it has one.
+		fn course_blob(course_id: Block::Hash, blob: Blob) -> Option<Vec<u8>>;
+	}
+
+	/// Consistency checks of the course storage, for operators and auditors.
+	#[api_version(2)]
+	pub trait CourseAuditApi<AccountId> where AccountId: Codec {
+		/// Check up to `limit` storage entries from `cursor`, the `next` cursor of the previous
+		/// page, or from the start if it is `None`.
+		fn audit_storage(cursor: Option<Vec<u8>>, limit: u32) -> AuditPage<AccountId, Block::Hash>;
+		/// The storage entries the annual report of `course_year` is checked against, with up to
+		/// `limit` courses from `cursor`, for proving them against the state root.
+		fn report_storage(
+			course_year: CourseYear,
+			cursor: Option<Vec<u8>>,
+			limit: u32,
+		) -> ReportPage;
+	}
+
+	/// The courses of past terms, moved out of the main storage.
+	pub trait CourseArchiveApi<AccountId, Balance> where AccountId: Codec, Balance: Codec {
+		/// `course_id` as archived with the courses of `term`, if it was.
+		fn archived_course(
+			term: Term,
+			course_id: Block::Hash,
+		) -> Option<ArchivedCourse<AccountId, Balance, NumberFor<Block>, Block::Hash>>;
+	}
+}
@@ -0,0 +1,56 @@
+//! Cold storage of the Courses of past terms in child tries.
+//!
+//! Once a term is over, governance archives the Courses cloned for it with `archive_term`, a batch
+//! at a time. Each Course is removed from the main storage, as if burned, and its
+//! [`ArchivedCourse`] written to the child trie of the term under [`key`]. The child trie of a term
+//! is [`child_info`], and its root is kept in `ArchivedTerms` next to the root the state trie
+//! keeps of it, so archived Courses stay provable against the state root with a child read proof.
+//!
+//! The off-chain history and the blobs of an archived Course stay where they are, under its
+//! course ID.
+
+use codec::{Decode, Encode};
+use frame_support::{storage::child::ChildInfo, RuntimeDebug};
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+
+use crate::{dna::Dna, CourseYear, Term};
+
+/// Prefix of the storage keys of the child tries of archived terms.
+pub const ARCHIVE_PREFIX: &[u8] = b"course_passing::archive";
+
+/// A Course as kept in the archive of its term.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ArchivedCourse<AccountId, Balance, BlockNumber, Hash> {
+	pub dna: Dna,
+	pub course_year: CourseYear,
+	/// The last owner of the Course.
+	pub owner: AccountId,
+	pub creator: Option<AccountId>,
+	/// The Course it was cloned from.
+	pub cloned_from: Hash,
+	/// The CID of the syllabus.
+	pub syllabus: Option<Vec<u8>>,
+	/// The CID of the course materials.
+	pub materials: Option<Vec<u8>>,
+	/// The number of times the content was updated.
+	pub content_version: u32,
+	/// The sum of the tips its creator received.
+	pub tips: Balance,
+	/// The number of records of the Course in the off-chain history, mints, transfers and sales.
+	pub history_len: u32,
+	/// The root of the child trie of its blobs, which are kept.
+	pub blobs_root: Option<Hash>,
+	/// The block the Course was archived in.
+	pub archived: BlockNumber,
+}
+
+/// The child trie holding the archive of `term`.
+pub fn child_info(term: Term) -> ChildInfo {
+	ChildInfo::new_default(&(ARCHIVE_PREFIX, term).encode())
+}
+
+/// The key of `course_id` in the child trie of its term.
+pub fn key<Hash: Encode>(course_id: &Hash) -> Vec<u8> {
+	course_id.encode()
+}
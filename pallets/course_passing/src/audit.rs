@@ -0,0 +1,54 @@
+//! Consistency checks of the course storage, for detecting corruption after upgrades.
+//!
+//! `Pallet::audit_storage` walks `Courses` and then `CoursesOwned` a page at a time, so operators
+//! can check the whole storage with many runtime calls that each stay cheap. The page reports the
+//! inconsistencies among the entries it checked, and the cursor to continue from.
+//!
+//! `Pallet::report_storage` pages through the entries an annual report of a year is checked
+//! against in the same way: `CountForCourses`, the `YearStatistics` of the year, the roots of
+//! `ArchivedTerms` and of the `OwnerSnapshots` of the year, then `Courses`. Every page carries the
+//! counts and roots, so a proof of a page against a state root stands on its own.
+
+use codec::{Decode, Encode};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+
+/// An inconsistency between the course storage maps.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum Inconsistency<AccountId, Hash> {
+	/// `CoursesOwned` of the account lists a course that does not exist, or that another account
+	/// owns. \[who, course_id\]
+	OrphanedOwnership(AccountId, Hash),
+	/// The course is missing from `CoursesOwned` of its owner. \[course_id, owner\]
+	MissingFromOwner(Hash, AccountId),
+	/// `OwnedCount` of the account differs from the number of courses in its `CoursesOwned`.
+	/// \[who, count, listed\]
+	OwnedCountMismatch(AccountId, u32, u32),
+}
+
+/// The result of checking a page of the course storage.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct AuditPage<AccountId, Hash> {
+	/// The inconsistencies found among the checked entries.
+	pub inconsistencies: Vec<Inconsistency<AccountId, Hash>>,
+	/// The number of `Courses` entries checked.
+	pub courses_checked: u32,
+	/// The number of `CoursesOwned` entries checked.
+	pub owners_checked: u32,
+	/// `CountForCourses`, to compare with the number of `Courses` entries once all are checked.
+	pub course_count: u64,
+	/// The cursor of the next page, `None` once all entries are checked.
+	pub next: Option<Vec<u8>>,
+}
+
+/// A page of the storage entries an annual report is checked against.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ReportPage {
+	/// The storage keys and SCALE-encoded values of the entries, the counts and roots first.
+	pub entries: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+	/// The number of `Courses` entries among them.
+	pub courses: u32,
+	/// The cursor of the next page, `None` once all courses are listed.
+	pub next: Option<Vec<u8>>,
+}
@@ -0,0 +1,594 @@
+//! Benchmarking setup for pallet-course_passing
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as CourseGrading;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+	traits::{tokens::fungible, Currency},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Hash, Saturating};
+use sp_std::{vec, vec::Vec};
+
+const SEED: u32 = 0;
+
+type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+// Courses minted in the same block share the same random seed, so give each one its own DNA.
+fn dna(index: u32) -> crate::dna::Dna {
+	let mut dna = [0u8; 32];
+	dna[28..].copy_from_slice(&index.to_le_bytes());
+	dna
+}
+
+fn mint_course<T: Config>(owner: &T::AccountId, index: u32) -> T::Hash {
+	CourseGrading::<T>::mint(owner, Some(dna(index)), None).expect("owner has capacity; qed")
+}
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&who, T::Currency::minimum_balance() * 1_000_000u32.into());
+	who
+}
+
+// Puts `filler` made-up courses ahead of the ones `who` owns, so those are found last.
+fn pad_owned<T: Config>(who: &T::AccountId, filler: u32) -> Result<(), &'static str> {
+	let mut owned: Vec<T::Hash> = (0..filler).map(|i| T::Hashing::hash_of(&(who, i))).collect();
+	owned.extend(CoursesOwned::<T>::get(who));
+	let count = owned.len() as u32;
+	let owned: BoundedVec<_, _> = owned.try_into().map_err(|_| "MaxCoursesOwned exceeded")?;
+	CoursesOwned::<T>::insert(who, owned);
+	OwnedCount::<T>::insert(who, count);
+	Ok(())
+}
+
+// Makes the Courses of the first year pay a transfer fee, as in the most expensive transfers.
+fn charge_first_year_fee<T: Config>() {
+	let department: T::AccountId = account("department", 0, SEED);
+	T::Currency::make_free_balance_be(&department, T::Currency::minimum_balance());
+	TransferFees::<T>::insert(
+		CourseYear::First,
+		TransferFee { amount: T::Currency::minimum_balance(), department },
+	);
+}
+
+// Lists a new course for sealed bids, returning it and its floor price.
+fn sealed_listing<T: Config>(second_price: bool) -> Result<(T::Hash, BalanceOf<T>), &'static str> {
+	let seller = funded_account::<T>("seller", 0);
+	let course_id = mint_course::<T>(&seller, 0);
+	let price = T::Currency::minimum_balance();
+	let origin = RawOrigin::Signed(seller).into();
+	if second_price {
+		CourseGrading::<T>::list_second_price(origin, course_id, price)?;
+	} else {
+		CourseGrading::<T>::list_sealed(origin, course_id, price)?;
+	}
+	Ok((course_id, price))
+}
+
+// Commits a sealed bid of `bid` on `course_id`, salted with the default hash.
+fn sealed_bid<T: Config>(
+	course_id: &T::Hash,
+	name: &'static str,
+	bid: BalanceOf<T>,
+) -> Result<T::AccountId, &'static str> {
+	let buyer = funded_account::<T>(name, 0);
+	let commitment = T::Hashing::hash_of(&(course_id, &buyer, bid, T::Hash::default()));
+	CourseGrading::<T>::commit_purchase(
+		RawOrigin::Signed(buyer.clone()).into(),
+		*course_id,
+		commitment,
+		bid,
+	)?;
+	Ok(buyer)
+}
+
+fn end_commit_phase<T: Config>(course_id: &T::Hash) {
+	if let Some(listing) = SealedListings::<T>::get(course_id) {
+		frame_system::Pallet::<T>::set_block_number(listing.commit_end + 1u32.into());
+	}
+}
+
+fn end_reveal_phase<T: Config>(course_id: &T::Hash) {
+	if let Some(listing) = SealedListings::<T>::get(course_id) {
+		frame_system::Pallet::<T>::set_block_number(listing.reveal_end + 1u32.into());
+	}
+}
+
+benchmarks! {
+	create_course {
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(CoursesOwned::<T>::get(&caller).len(), 1);
+	}
+
+	set_price {
+		let caller: T::AccountId = whitelisted_caller();
+		let course_id = mint_course::<T>(&caller, 0);
+		let price = T::Currency::minimum_balance();
+	}: _(RawOrigin::Signed(caller), course_id, Some(price))
+	verify {
+		assert_eq!(CourseGrading::<T>::courses(&course_id).and_then(|c| c.price), Some(price));
+	}
+
+	set_price_unlisted {
+		let caller: T::AccountId = whitelisted_caller();
+		let course_id = mint_course::<T>(&caller, 0);
+	}: set_price(RawOrigin::Signed(caller), course_id, None)
+	verify {
+		assert_eq!(CourseGrading::<T>::courses(&course_id).and_then(|c| c.price), None);
+	}
+
+	transfer {
+		// The sender owns `s` courses, the transferred one last, and the recipient `r`.
+		let s in 1 .. T::MaxCoursesOwned::get();
+		let r in 0 .. T::MaxCoursesOwned::get() - 1;
+		let caller = funded_account::<T>("caller", 0);
+		let course_id = mint_course::<T>(&caller, 0);
+		pad_owned::<T>(&caller, s - 1)?;
+		charge_first_year_fee::<T>();
+		let to: T::AccountId = account("recipient", 0, SEED);
+		AcceptsDirectTransfers::<T>::insert(&to, true);
+		pad_owned::<T>(&to, r)?;
+	}: _(RawOrigin::Signed(caller), to.clone(), course_id)
+	verify {
+		assert_eq!(CourseGrading::<T>::courses(&course_id).map(|c| c.owner), Some(to));
+	}
+
+	buy_course {
+		// The seller owns `s` courses, the one for sale last, and the buyer `r`.
+		let s in 1 .. T::MaxCoursesOwned::get();
+		let r in 0 .. T::MaxCoursesOwned::get() - 1;
+		let seller = funded_account::<T>("seller", 0);
+		let course_id = mint_course::<T>(&seller, 0);
+		pad_owned::<T>(&seller, s - 1)?;
+		let price = T::Currency::minimum_balance();
+		CourseGrading::<T>::set_price(RawOrigin::Signed(seller).into(), course_id, Some(price))?;
+		charge_first_year_fee::<T>();
+		let buyer = funded_account::<T>("buyer", 0);
+		pad_owned::<T>(&buyer, r)?;
+	}: _(RawOrigin::Signed(buyer.clone()), course_id, price, false)
+	verify {
+		assert_eq!(CourseGrading::<T>::courses(&course_id).map(|c| c.owner), Some(buyer));
+	}
+
+	breed_course {
+		let caller = funded_account::<T>("caller", 0);
+		let parent1 = mint_course::<T>(&caller, 0);
+		let parent2 = mint_course::<T>(&caller, 1);
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(now.saturating_add(T::MinBreedingAge::get()));
+	}: _(RawOrigin::Signed(caller.clone()), parent1, parent2)
+	verify {
+		assert_eq!(CoursesOwned::<T>::get(&caller).len(), 3);
+	}
+
+	approve {
+		let caller: T::AccountId = whitelisted_caller();
+		let course_id = mint_course::<T>(&caller, 0);
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+	}: _(RawOrigin::Signed(caller), course_id, Some(delegate.clone()))
+	verify {
+		assert_eq!(CourseApprovals::<T>::get(&course_id), Some(delegate));
+	}
+
+	update_metadata {
+		let caller: T::AccountId = whitelisted_caller();
+		let balance = T::Currency::minimum_balance() * 1_000_000u32.into();
+		T::Currency::make_free_balance_be(&caller, balance);
+		let course_id = mint_course::<T>(&caller, 0);
+		let cid: Cid<T> = b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"
+			.to_vec()
+			.try_into()
+			.map_err(|_| "MaxCidLength is too small")?;
+	}: _(RawOrigin::Signed(caller), course_id, Some(cid.clone()), Some(cid))
+	verify {
+		assert!(CourseContents::<T>::contains_key(&course_id));
+		assert_eq!(MetadataLogs::<T>::get(&course_id).map(|log| log.version), Some(1));
+	}
+
+	offer_transfer {
+		let caller: T::AccountId = whitelisted_caller();
+		let course_id = mint_course::<T>(&caller, 0);
+		let to: T::AccountId = account("recipient", 0, SEED);
+	}: _(RawOrigin::Signed(caller), to.clone(), course_id)
+	verify {
+		assert_eq!(TransferOffers::<T>::get(&course_id).map(|offer| offer.to), Some(to));
+	}
+
+	claim_transfer {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let course_id = mint_course::<T>(&owner, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		CourseGrading::<T>::offer_transfer(
+			RawOrigin::Signed(owner).into(),
+			caller.clone(),
+			course_id,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), course_id)
+	verify {
+		assert_eq!(CourseGrading::<T>::courses(&course_id).map(|c| c.owner), Some(caller));
+	}
+
+	reject_transfer {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let course_id = mint_course::<T>(&owner, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		CourseGrading::<T>::offer_transfer(
+			RawOrigin::Signed(owner).into(),
+			caller.clone(),
+			course_id,
+		)?;
+	}: _(RawOrigin::Signed(caller), course_id)
+	verify {
+		assert!(!TransferOffers::<T>::contains_key(&course_id));
+	}
+
+	set_accepts_transfers {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller.clone()), true)
+	verify {
+		assert!(AcceptsDirectTransfers::<T>::get(&caller));
+	}
+
+	clone_course {
+		let caller = funded_account::<T>("caller", 0);
+		let course_id = mint_course::<T>(&caller, 0);
+	}: _(RawOrigin::Signed(caller.clone()), course_id, 1)
+	verify {
+		assert_eq!(CoursesOwned::<T>::get(&caller).len(), 2);
+	}
+
+	burn_course {
+		let caller = funded_account::<T>("caller", 0);
+		CourseGrading::<T>::create_course(RawOrigin::Signed(caller.clone()).into())?;
+		let course_id = CoursesOwned::<T>::get(&caller)[0];
+	}: _(RawOrigin::Signed(caller.clone()), course_id)
+	verify {
+		assert!(!Courses::<T>::contains_key(&course_id));
+		assert!(CoursesOwned::<T>::get(&caller).is_empty());
+	}
+
+	list_dutch {
+		let caller: T::AccountId = whitelisted_caller();
+		let course_id = mint_course::<T>(&caller, 0);
+		let floor = T::Currency::minimum_balance();
+	}: _(RawOrigin::Signed(caller), course_id, floor * 10u32.into(), floor, floor)
+	verify {
+		assert!(DutchListings::<T>::contains_key(&course_id));
+	}
+
+	create_bundle {
+		let n in 1 .. T::MaxBundleSize::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let course_ids: BoundedVec<_, _> = (0..n)
+			.map(|i| mint_course::<T>(&caller, i))
+			.collect::<Vec<_>>()
+			.try_into()
+			.map_err(|_| "MaxBundleSize exceeded")?;
+	}: _(RawOrigin::Signed(caller), course_ids, T::Currency::minimum_balance())
+	verify {
+		assert!(Bundles::<T>::contains_key(0));
+	}
+
+	cancel_bundle {
+		let caller: T::AccountId = whitelisted_caller();
+		let course_ids =
+			vec![mint_course::<T>(&caller, 0)].try_into().map_err(|_| "MaxBundleSize is 0")?;
+		let price = T::Currency::minimum_balance();
+		CourseGrading::<T>::create_bundle(
+			RawOrigin::Signed(caller.clone()).into(),
+			course_ids,
+			price,
+		)?;
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(!Bundles::<T>::contains_key(0));
+	}
+
+	buy_bundle {
+		let n in 1 .. T::MaxBundleSize::get();
+		let seller = funded_account::<T>("seller", 0);
+		let course_ids: BoundedVec<_, _> = (0..n)
+			.map(|i| mint_course::<T>(&seller, i))
+			.collect::<Vec<_>>()
+			.try_into()
+			.map_err(|_| "MaxBundleSize exceeded")?;
+		let price = T::Currency::minimum_balance();
+		CourseGrading::<T>::create_bundle(RawOrigin::Signed(seller).into(), course_ids, price)?;
+		let buyer = funded_account::<T>("buyer", 0);
+	}: _(RawOrigin::Signed(buyer.clone()), 0, price)
+	verify {
+		assert_eq!(CoursesOwned::<T>::get(&buyer).len() as u32, n);
+	}
+
+	propose_swap {
+		let caller: T::AccountId = whitelisted_caller();
+		let my_course = mint_course::<T>(&caller, 0);
+		let counterparty: T::AccountId = account("counterparty", 0, SEED);
+		let their_course = mint_course::<T>(&counterparty, 1);
+		let sweetener = Some(T::Currency::minimum_balance());
+	}: _(RawOrigin::Signed(caller), my_course, their_course, counterparty, sweetener)
+	verify {
+		assert!(Swaps::<T>::contains_key(0));
+	}
+
+	cancel_swap {
+		let caller: T::AccountId = whitelisted_caller();
+		let my_course = mint_course::<T>(&caller, 0);
+		let counterparty: T::AccountId = account("counterparty", 0, SEED);
+		let their_course = mint_course::<T>(&counterparty, 1);
+		CourseGrading::<T>::propose_swap(
+			RawOrigin::Signed(caller.clone()).into(),
+			my_course,
+			their_course,
+			counterparty,
+			None,
+		)?;
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(!Swaps::<T>::contains_key(0));
+	}
+
+	accept_swap {
+		let proposer = funded_account::<T>("proposer", 0);
+		let my_course = mint_course::<T>(&proposer, 0);
+		let counterparty = funded_account::<T>("counterparty", 0);
+		let their_course = mint_course::<T>(&counterparty, 1);
+		CourseGrading::<T>::propose_swap(
+			RawOrigin::Signed(proposer.clone()).into(),
+			my_course,
+			their_course,
+			counterparty.clone(),
+			Some(T::Currency::minimum_balance()),
+		)?;
+	}: _(RawOrigin::Signed(counterparty.clone()), 0)
+	verify {
+		assert_eq!(CourseGrading::<T>::courses(&my_course).map(|c| c.owner), Some(counterparty));
+		assert_eq!(CourseGrading::<T>::courses(&their_course).map(|c| c.owner), Some(proposer));
+	}
+
+	set_cooldown_exempt {
+		let origin = T::ForceOrigin::successful_origin();
+	}: _<T::Origin>(origin, CourseYear::First, true)
+	verify {
+		assert!(CooldownExempt::<T>::get(&CourseYear::First));
+	}
+
+	ban_account {
+		let origin = T::ForceOrigin::successful_origin();
+		let who: T::AccountId = account("fraudster", 0, SEED);
+	}: _<T::Origin>(origin, who.clone())
+	verify {
+		assert!(BannedAccounts::<T>::contains_key(&who));
+	}
+
+	unban_account {
+		let origin = T::ForceOrigin::successful_origin();
+		let who: T::AccountId = account("fraudster", 0, SEED);
+		BannedAccounts::<T>::insert(&who, ());
+	}: _<T::Origin>(origin, who.clone())
+	verify {
+		assert!(!BannedAccounts::<T>::contains_key(&who));
+	}
+
+	set_year_limit {
+		let origin = T::ForceOrigin::successful_origin();
+	}: _<T::Origin>(origin, CourseYear::First, Some(8))
+	verify {
+		assert_eq!(YearLimits::<T>::get(&CourseYear::First), Some(8));
+	}
+
+	set_paused {
+		let origin = T::PauseOrigin::successful_origin();
+	}: _<T::Origin>(origin, true)
+	verify {
+		assert!(Paused::<T>::get());
+	}
+
+	snapshot_owners {
+		let origin = T::ForceOrigin::successful_origin();
+	}: _<T::Origin>(origin, CourseYear::First)
+	verify {
+		assert!(PendingSnapshot::<T>::exists());
+	}
+
+	tip_creator {
+		let creator = funded_account::<T>("creator", 0);
+		let course_id = mint_course::<T>(&creator, 0);
+		let tipper = funded_account::<T>("tipper", 0);
+		let amount = T::Currency::minimum_balance();
+	}: _(RawOrigin::Signed(tipper), course_id, amount)
+	verify {
+		assert_eq!(Tips::<T>::get(&course_id), amount);
+	}
+
+	set_stable_price {
+		let caller: T::AccountId = whitelisted_caller();
+		let course_id = mint_course::<T>(&caller, 0);
+		let price = <T::StableCurrency as fungible::Inspect<_>>::minimum_balance();
+	}: _(RawOrigin::Signed(caller), course_id, Some(price))
+	verify {
+		assert_eq!(StablePrices::<T>::get(&course_id), Some(price));
+	}
+
+	buy_course_in_stable {
+		// The seller owns `s` courses, the one for sale last, and the buyer `r`.
+		let s in 1 .. T::MaxCoursesOwned::get();
+		let r in 0 .. T::MaxCoursesOwned::get() - 1;
+		let seller = funded_account::<T>("seller", 0);
+		let course_id = mint_course::<T>(&seller, 0);
+		pad_owned::<T>(&seller, s - 1)?;
+		let price = <T::StableCurrency as fungible::Inspect<_>>::minimum_balance();
+		CourseGrading::<T>::set_stable_price(
+			RawOrigin::Signed(seller).into(),
+			course_id,
+			Some(price),
+		)?;
+		let buyer = funded_account::<T>("buyer", 0);
+		<T::StableCurrency as fungible::Mutate<_>>::mint_into(&buyer, price * 1_000u32.into())?;
+		pad_owned::<T>(&buyer, r)?;
+	}: _(RawOrigin::Signed(buyer.clone()), course_id, price)
+	verify {
+		assert_eq!(CourseGrading::<T>::courses(&course_id).map(|c| c.owner), Some(buyer));
+	}
+
+	set_transfer_fee {
+		let origin = T::ForceOrigin::successful_origin();
+		let department: T::AccountId = account("department", 0, SEED);
+		let fee = TransferFee { amount: T::Currency::minimum_balance(), department };
+	}: _<T::Origin>(origin, CourseYear::First, Some(fee.clone()))
+	verify {
+		assert_eq!(TransferFees::<T>::get(&CourseYear::First), Some(fee));
+	}
+
+	list_sealed {
+		let caller: T::AccountId = whitelisted_caller();
+		let course_id = mint_course::<T>(&caller, 0);
+		CourseGrading::<T>::set_price(
+			RawOrigin::Signed(caller.clone()).into(),
+			course_id,
+			Some(T::Currency::minimum_balance()),
+		)?;
+	}: _(RawOrigin::Signed(caller), course_id, T::Currency::minimum_balance())
+	verify {
+		assert!(SealedListings::<T>::contains_key(&course_id));
+	}
+
+	commit_purchase {
+		let (course_id, _) = sealed_listing::<T>(false)?;
+		let buyer = funded_account::<T>("buyer", 0);
+		let commitment = T::Hashing::hash_of(&course_id);
+	}: _(RawOrigin::Signed(buyer.clone()), course_id, commitment, T::Currency::minimum_balance())
+	verify {
+		assert!(SealedBids::<T>::contains_key(&course_id, &buyer));
+	}
+
+	reveal_purchase {
+		// The revealed bid outbids an earlier one, whose reserve is returned.
+		let (course_id, price) = sealed_listing::<T>(true)?;
+		let first = sealed_bid::<T>(&course_id, "first", price)?;
+		let buyer = sealed_bid::<T>(&course_id, "buyer", price + price)?;
+		end_commit_phase::<T>(&course_id);
+		CourseGrading::<T>::reveal_purchase(
+			RawOrigin::Signed(first).into(),
+			course_id,
+			price,
+			T::Hash::default(),
+		)?;
+	}: _(RawOrigin::Signed(buyer.clone()), course_id, price + price, T::Hash::default())
+	verify {
+		let best = SealedListings::<T>::get(&course_id).and_then(|listing| listing.best);
+		assert_eq!(best, Some((buyer, price + price)));
+	}
+
+	settle_sealed {
+		// The seller owns `s` courses, the one for sale last, and the buyer `r`.
+		let s in 1 .. T::MaxCoursesOwned::get();
+		let r in 0 .. T::MaxCoursesOwned::get() - 1;
+		let (course_id, price) = sealed_listing::<T>(true)?;
+		let seller = CourseGrading::<T>::courses(&course_id).map(|c| c.owner).ok_or("no course")?;
+		pad_owned::<T>(&seller, s - 1)?;
+		let buyer = sealed_bid::<T>(&course_id, "buyer", price)?;
+		pad_owned::<T>(&buyer, r)?;
+		charge_first_year_fee::<T>();
+		end_commit_phase::<T>(&course_id);
+		CourseGrading::<T>::reveal_purchase(
+			RawOrigin::Signed(buyer.clone()).into(),
+			course_id,
+			price,
+			T::Hash::default(),
+		)?;
+		end_reveal_phase::<T>(&course_id);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), course_id)
+	verify {
+		assert_eq!(CourseGrading::<T>::courses(&course_id).map(|c| c.owner), Some(buyer));
+	}
+
+	withdraw_sealed_bid {
+		let (course_id, price) = sealed_listing::<T>(true)?;
+		let buyer = sealed_bid::<T>(&course_id, "buyer", price)?;
+		end_reveal_phase::<T>(&course_id);
+	}: _(RawOrigin::Signed(buyer.clone()), course_id)
+	verify {
+		assert!(!SealedBids::<T>::contains_key(&course_id, &buyer));
+	}
+
+	list_second_price {
+		let caller: T::AccountId = whitelisted_caller();
+		let course_id = mint_course::<T>(&caller, 0);
+		CourseGrading::<T>::set_price(
+			RawOrigin::Signed(caller.clone()).into(),
+			course_id,
+			Some(T::Currency::minimum_balance()),
+		)?;
+	}: _(RawOrigin::Signed(caller), course_id, T::Currency::minimum_balance())
+	verify {
+		assert!(SealedListings::<T>::get(&course_id).map_or(false, |listing| listing.second_price));
+	}
+
+	watch {
+		let owner = funded_account::<T>("owner", 0);
+		let course_id = mint_course::<T>(&owner, 0);
+		// The watchlist is one short of full, and searched for the caller.
+		for i in 1 .. T::MaxWatchers::get() {
+			let watcher = funded_account::<T>("watcher", i);
+			CourseGrading::<T>::watch(
+				RawOrigin::Signed(watcher).into(),
+				course_id,
+				T::Currency::minimum_balance(),
+			)?;
+		}
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller), course_id, T::Currency::minimum_balance())
+	verify {
+		assert_eq!(Watches::<T>::get(&course_id).len() as u32, T::MaxWatchers::get());
+	}
+
+	unwatch {
+		let owner = funded_account::<T>("owner", 0);
+		let course_id = mint_course::<T>(&owner, 0);
+		let caller = funded_account::<T>("caller", 0);
+		CourseGrading::<T>::watch(
+			RawOrigin::Signed(caller.clone()).into(),
+			course_id,
+			T::Currency::minimum_balance(),
+		)?;
+	}: _(RawOrigin::Signed(caller), course_id)
+	verify {
+		assert!(!Watches::<T>::contains_key(&course_id));
+	}
+
+	archive_term {
+		let n in 1 .. T::MaxArchiveBatch::get();
+		let mut course_ids = Vec::new();
+		for i in 0 .. n {
+			let owner = funded_account::<T>("owner", i);
+			let source_id = mint_course::<T>(&owner, i);
+			CourseGrading::<T>::clone_course(RawOrigin::Signed(owner).into(), source_id, 1)?;
+			course_ids.push(T::Hashing::hash_of(&(source_id, 1u32)));
+		}
+		let course_ids: BoundedVec<_, _> = course_ids.try_into().map_err(|_| "too many courses")?;
+		let origin = T::ForceOrigin::successful_origin();
+	}: _<T::Origin>(origin, 1, course_ids.clone())
+	verify {
+		assert!(course_ids.iter().all(|id| CourseGrading::<T>::archived_course(1, id).is_some()));
+		assert_eq!(ArchivedTerms::<T>::get(1).map(|archive| archive.courses), Some(n));
+	}
+
+	set_blob {
+		let n in 1 .. T::MaxBlobLen::get();
+		let caller = funded_account::<T>("caller", 0);
+		let course_id = mint_course::<T>(&caller, 0);
+		let value: BoundedVec<_, _> = vec![0u8; n as usize].try_into().map_err(|_| "blob too long")?;
+	}: _(RawOrigin::Signed(caller), course_id, blobs::Blob::ResourceManifest, Some(value))
+	verify {
+		assert!(Courses::<T>::get(&course_id).map_or(false, |course| course.blobs_root.is_some()));
+	}
+}
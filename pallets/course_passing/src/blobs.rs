@@ -0,0 +1,38 @@
+//! Bulky data of a Course, in a child trie of its own.
+//!
+//! Each [`Blob`] of a Course is stored in the child trie [`child_info`] of the Course under
+//! [`key`], and the root of that trie in the `blobs_root` of the Course. Proofs of the ownership
+//! of a Course then only carry the root, however large its blobs, and each blob is proven on its
+//! own with a child read proof.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{storage::child::ChildInfo, RuntimeDebug};
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+
+/// Prefix of the storage keys of the child tries of Course blobs.
+pub const BLOBS_PREFIX: &[u8] = b"course_passing::blobs";
+
+/// The blobs a Course can have.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Blob {
+	/// The hashes of every document of the syllabus.
+	SyllabusHashes,
+	/// The manifest of the resources of the course.
+	ResourceManifest,
+}
+
+impl Blob {
+	/// Every blob, in the order of their keys.
+	pub const ALL: [Blob; 2] = [Blob::SyllabusHashes, Blob::ResourceManifest];
+}
+
+/// The child trie holding the blobs of `course_id`.
+pub fn child_info<Hash: Encode>(course_id: &Hash) -> ChildInfo {
+	ChildInfo::new_default(&(BLOBS_PREFIX, course_id).encode())
+}
+
+/// The key of `blob` in the child trie of its Course.
+pub fn key(blob: Blob) -> Vec<u8> {
+	blob.encode()
+}
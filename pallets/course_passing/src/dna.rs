@@ -0,0 +1,113 @@
+//! Layout of the 32 bytes of a course DNA.
+//!
+//! The traits of a course are read from fixed bytes of its DNA, the remaining bytes being
+//! entropy that tells apart courses with the same traits:
+//!
+//! | Bytes    | Trait                                          |
+//! |----------|------------------------------------------------|
+//! | `0..2`   | department, little-endian                      |
+//! | `2`      | modality, [`Modality`] of the byte modulo 3    |
+//! | `3`      | difficulty, 1 to 5 from the byte modulo 5      |
+//! | `4..6`   | language, little-endian                        |
+//! | `6`      | rarity, [`Rarity`] from the byte's value       |
+//! | `7..32`  | entropy                                        |
+//!
+//! Courses minted before storage version 3 had a 16-byte DNA, which [`extend`] keeps as their
+//! entropy and completes with traits derived from it.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_io::hashing::blake2_128;
+
+/// The DNA of a course.
+pub type Dna = [u8; 32];
+
+/// The bytes of the department.
+pub const DEPARTMENT: core::ops::Range<usize> = 0..2;
+/// The byte of the modality.
+pub const MODALITY: usize = 2;
+/// The byte of the difficulty.
+pub const DIFFICULTY: usize = 3;
+/// The bytes of the language.
+pub const LANGUAGE: core::ops::Range<usize> = 4..6;
+/// The byte of the rarity.
+pub const RARITY: usize = 6;
+/// The bytes of entropy, which carry no trait.
+pub const ENTROPY: core::ops::RangeFrom<usize> = 7..;
+
+/// How a course is taught.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Modality {
+	InPerson,
+	Online,
+	Hybrid,
+}
+
+/// How rare a course is. About three in four courses are common, one in five uncommon, one in 32
+/// rare and one in 128 legendary.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Rarity {
+	Common,
+	Uncommon,
+	Rare,
+	Legendary,
+}
+
+/// The traits encoded in a DNA.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Traits {
+	pub department: u16,
+	pub modality: Modality,
+	pub difficulty: u8,
+	pub language: u16,
+	pub rarity: Rarity,
+}
+
+/// Read the traits of `dna`.
+pub fn traits(dna: &Dna) -> Traits {
+	let modality = match dna[MODALITY] % 3 {
+		0 => Modality::InPerson,
+		1 => Modality::Online,
+		_ => Modality::Hybrid,
+	};
+	let rarity = match dna[RARITY] {
+		0..=191 => Rarity::Common,
+		192..=245 => Rarity::Uncommon,
+		246..=253 => Rarity::Rare,
+		_ => Rarity::Legendary,
+	};
+	Traits {
+		department: u16::from_le_bytes([dna[DEPARTMENT.start], dna[DEPARTMENT.start + 1]]),
+		modality,
+		difficulty: dna[DIFFICULTY] % 5 + 1,
+		language: u16::from_le_bytes([dna[LANGUAGE.start], dna[LANGUAGE.start + 1]]),
+		rarity,
+	}
+}
+
+/// Breed a DNA from `dna1` and `dna2`, taking each trait whole from one parent and mixing the
+/// entropy bit by bit, both as chosen by `random`.
+pub fn breed(dna1: &Dna, dna2: &Dna, random: &Dna) -> Dna {
+	let mut new_dna = *random;
+	let traits = [DEPARTMENT, MODALITY..MODALITY + 1, DIFFICULTY..DIFFICULTY + 1, LANGUAGE];
+	for (i, range) in traits.into_iter().enumerate() {
+		let parent = if random[ENTROPY.start] & (1 << i) == 0 { dna1 } else { dna2 };
+		new_dna[range.clone()].copy_from_slice(&parent[range]);
+	}
+	// Offspring are never rarer than their rarest parent.
+	new_dna[RARITY] = random[RARITY].min(dna1[RARITY].max(dna2[RARITY]));
+	for i in ENTROPY {
+		new_dna[i] = (random[i] & dna1[i]) | (!random[i] & dna2[i]);
+	}
+	new_dna
+}
+
+/// Extend the 16-byte DNA of a course minted before storage version 3, keeping it as the last
+/// 16 bytes of entropy and deriving the rest from its hash.
+pub fn extend(legacy: &[u8; 16]) -> Dna {
+	let mut dna = [0u8; 32];
+	dna[..16].copy_from_slice(&blake2_128(legacy));
+	dna[16..].copy_from_slice(legacy);
+	dna
+}
@@ -0,0 +1,32 @@
+//! Course provenance in the off-chain index.
+//!
+//! Every mint, transfer and sale of a course is written to the off-chain database of nodes run
+//! with `--enable-offchain-indexing`, as the `index`-th [`CourseRecord`] of the course under
+//! [`history_key`]. The records are never read by the runtime; the number of records of each
+//! course is kept on-chain so the next one can be appended.
+
+use codec::{Decode, Encode};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+
+/// Prefix of the off-chain index keys of course records.
+pub const HISTORY_PREFIX: &[u8] = b"course_passing::history";
+
+/// A change of ownership of a course.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct CourseRecord<AccountId, Balance, BlockNumber> {
+	/// The block the change happened in.
+	pub block: BlockNumber,
+	/// The previous owner, `None` when the course was minted.
+	pub from: Option<AccountId>,
+	/// The new owner.
+	pub to: AccountId,
+	/// The price paid, if the course was bought.
+	pub price: Option<Balance>,
+}
+
+/// The off-chain index key of the `index`-th record of `course_id`, counting from 0.
+pub fn history_key<Hash: Encode>(course_id: &Hash, index: u32) -> Vec<u8> {
+	(HISTORY_PREFIX, course_id, index).encode()
+}
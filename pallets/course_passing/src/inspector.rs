@@ -0,0 +1,20 @@
+//! Implementation of `CourseInspector`, the read access of other pallets to the registry.
+
+use super::*;
+use pallet_course_passing_primitives::CourseInspector;
+
+impl<T: Config> CourseInspector<T::AccountId, T::Hash> for Pallet<T> {
+	type Year = CourseYear;
+
+	fn owner_of(course_id: &T::Hash) -> Option<T::AccountId> {
+		Self::courses(course_id).map(|course| course.owner)
+	}
+
+	fn year_of(course_id: &T::Hash) -> Option<CourseYear> {
+		Self::courses(course_id).map(|course| course.course_year)
+	}
+
+	fn exists(course_id: &T::Hash) -> bool {
+		<Courses<T>>::contains_key(course_id)
+	}
+}
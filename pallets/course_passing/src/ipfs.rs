@@ -0,0 +1,52 @@
+//! IPFS helpers: CID format checks and pinning from the off-chain worker.
+
+use frame_support::sp_runtime::offchain::{http, Duration, StorageKind};
+use sp_std::vec::Vec;
+
+/// Persistent off-chain storage key holding the base URL of the IPFS HTTP API as raw bytes, e.g.
+/// `http://127.0.0.1:5001`.
+///
+/// Pinning is skipped while the key is unset.
+pub const IPFS_API_KEY: &[u8] = b"course_passing::ipfs-api";
+
+/// How long the off-chain worker waits for the IPFS node to pin a CID.
+const PIN_TIMEOUT_MS: u64 = 10_000;
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE32_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Check that `cid` is a textual CID: either a base58 CIDv0 (`Qm...`) or a base32 CIDv1 (`ba...`).
+pub fn is_valid_cid(cid: &[u8]) -> bool {
+	match cid {
+		[b'Q', b'm', ..] if cid.len() == 46 => cid.iter().all(|c| BASE58_ALPHABET.contains(c)),
+		// The multibase prefix `b`, followed by the base32 encoded version byte 0x01.
+		[b'b', b'a', rest @ ..] if rest.len() >= 8 =>
+			rest.iter().all(|c| BASE32_ALPHABET.contains(c)),
+		_ => false,
+	}
+}
+
+/// Ask the configured IPFS node to pin `cid`.
+pub fn pin(cid: &[u8]) -> Result<(), http::Error> {
+	let mut url = match sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, IPFS_API_KEY) {
+		Some(api) => api,
+		None => return Ok(()),
+	};
+
+	url.extend_from_slice(b"/api/v0/pin/add?arg=");
+	url.extend_from_slice(cid);
+	let url = sp_std::str::from_utf8(&url).map_err(|_| http::Error::Unknown)?;
+
+	let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(PIN_TIMEOUT_MS));
+	let pending = http::Request::post(url, Vec::<&[u8]>::new())
+		.deadline(deadline)
+		.send()
+		.map_err(|_| http::Error::IoError)?;
+	let response = pending.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
+
+	if response.code != 200 {
+		log::warn!("IPFS node refused to pin a CID, status {}", response.code);
+		return Err(http::Error::Unknown)
+	}
+	Ok(())
+}
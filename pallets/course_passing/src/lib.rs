@@ -2,17 +2,154 @@
 
 pub use pallet::*;
 
+pub mod archive;
+pub mod audit;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod blobs;
+pub mod dna;
+pub mod history;
+mod inspector;
+pub mod ipfs;
+pub mod migrations;
+#[cfg(test)]
+mod mock;
+mod nonfungibles;
+pub mod snapshot;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use pallet_course_passing_primitives::CourseInspector;
+pub use weights::WeightInfo;
+
+/// Description of a `course_passing` event, for indexers.
+pub struct EventInfo {
+	/// The variant name of the event.
+	pub name: &'static str,
+	/// The names of the event's fields, in order.
+	pub fields: &'static [&'static str],
+}
+
+/// The events of the pallet, with the names of their fields.
+pub const EVENT_SCHEMA: &[EventInfo] = &[
+	EventInfo { name: "Created", fields: &["sender", "course_id"] },
+	EventInfo { name: "PriceSet", fields: &["sender", "course_id", "new_price"] },
+	EventInfo { name: "Transferred", fields: &["from", "to", "course_id"] },
+	EventInfo { name: "Bought", fields: &["buyer", "seller", "course_id", "bid_price"] },
+	EventInfo { name: "Approved", fields: &["owner", "course_id", "delegate"] },
+	EventInfo { name: "ContentSet", fields: &["sender", "course_id"] },
+	EventInfo { name: "Burned", fields: &["owner", "course_id", "refund"] },
+	EventInfo { name: "Bred", fields: &["owner", "parent1", "parent2", "course_id"] },
+	EventInfo { name: "Cloned", fields: &["owner", "source_id", "course_id", "term"] },
+	EventInfo {
+		name: "DutchListed",
+		fields: &["sender", "course_id", "start_price", "floor_price", "decay"],
+	},
+	EventInfo { name: "BundleCreated", fields: &["seller", "bundle_id", "price"] },
+	EventInfo { name: "BundleCancelled", fields: &["seller", "bundle_id"] },
+	EventInfo { name: "BundleBought", fields: &["buyer", "seller", "bundle_id", "price"] },
+	EventInfo { name: "TransferOffered", fields: &["from", "to", "course_id", "expires"] },
+	EventInfo { name: "TransferRejected", fields: &["sender", "course_id"] },
+	EventInfo { name: "SwapProposed", fields: &["proposer", "counterparty", "swap_id"] },
+	EventInfo { name: "SwapCancelled", fields: &["proposer", "swap_id"] },
+	EventInfo { name: "SwapAccepted", fields: &["counterparty", "proposer", "swap_id"] },
+	EventInfo { name: "AccountBanned", fields: &["who"] },
+	EventInfo { name: "AccountUnbanned", fields: &["who"] },
+	EventInfo { name: "CooldownExemptionSet", fields: &["course_year", "exempt"] },
+	EventInfo { name: "PauseSet", fields: &["paused"] },
+	EventInfo { name: "YearLimitSet", fields: &["course_year", "limit"] },
+	EventInfo { name: "SnapshotStarted", fields: &["snapshot_id", "course_year", "block"] },
+	EventInfo { name: "SnapshotCompleted", fields: &["snapshot_id", "root", "holders"] },
+	EventInfo { name: "MigrationStarted", fields: &["version"] },
+	EventInfo { name: "MigrationAdvanced", fields: &["steps"] },
+	EventInfo { name: "MigrationCompleted", fields: &["version", "steps"] },
+	EventInfo { name: "Tipped", fields: &["tipper", "creator", "course_id", "amount"] },
+	EventInfo { name: "StablePriceSet", fields: &["sender", "course_id", "new_price"] },
+	EventInfo { name: "BoughtInStable", fields: &["buyer", "seller", "course_id", "price"] },
+	EventInfo { name: "TransferFeeSet", fields: &["course_year", "fee"] },
+	EventInfo { name: "TransferFeePaid", fields: &["payer", "department", "course_id", "amount"] },
+	EventInfo {
+		name: "SealedListed",
+		fields: &["sender", "course_id", "floor_price", "reveal_end", "second_price"],
+	},
+	EventInfo { name: "PurchaseCommitted", fields: &["buyer", "course_id"] },
+	EventInfo { name: "PurchaseRevealed", fields: &["buyer", "course_id", "bid"] },
+	EventInfo { name: "SealedBidWithdrawn", fields: &["buyer", "course_id", "forfeited"] },
+	EventInfo { name: "SealedListingClosed", fields: &["seller", "course_id"] },
+	EventInfo { name: "Watched", fields: &["watcher", "course_id", "target"] },
+	EventInfo { name: "Unwatched", fields: &["watcher", "course_id"] },
+	EventInfo { name: "WatchTriggered", fields: &["watcher", "course_id", "price"] },
+	EventInfo { name: "CourseArchived", fields: &["owner", "course_id", "term"] },
+	EventInfo { name: "TermArchived", fields: &["term", "courses", "root"] },
+	EventInfo { name: "BlobSet", fields: &["sender", "course_id", "blob"] },
+];
+
+/// The topics every course event of the pallet is indexed by, in order. Bundle, swap, burn and
+/// ban events do not have the `collection` topic, their `owner` topic being the account that sent
+/// the transaction or was banned, and other governance and migration events have no topics.
+///
+/// * `owner`: `T::Hashing` of `b"owner"` followed by the SCALE-encoded account owning the course
+///   once the event took effect,
+/// * `collection`: `T::Hashing` of `b"collection"` followed by the SCALE-encoded `CourseYear` of
+///   the course,
+/// * `account`: `T::Hashing` of `b"account"` followed by the SCALE-encoded account, once for every
+///   account in the fields of the event,
+/// * `course`: `T::Hashing` of `b"course"` followed by the course ID, once for every course in the
+///   fields of the event, parents and sources of bred and cloned courses included.
+pub const EVENT_TOPICS: &[&str] = &["owner", "collection", "account", "course"];
+
+/// Notified of every change of ownership of a course, so other pallets can follow courses
+/// without scraping events.
+///
+/// The handlers run inside the calls changing the ownership and their weight is not accounted
+/// for, so they should only do a few storage writes.
+#[impl_trait_for_tuples::impl_for_tuples(8)]
+pub trait CourseTransferHandler<AccountId, Hash> {
+	/// `course_id` passed from `from` to `to`. `from` is `None` when the course was minted.
+	fn on_transfer(course_id: &Hash, from: Option<&AccountId>, to: &AccountId);
+	/// `course_id` of `owner` was burned.
+	fn on_burn(course_id: &Hash, owner: &AccountId);
+}
+
+/// Notified of every sale of courses, single or bundled.
+///
+/// The handlers run inside the purchase and their weight is not accounted for, so they should
+/// only do a few storage writes.
+#[impl_trait_for_tuples::impl_for_tuples(8)]
+pub trait PurchaseHandler<AccountId, Balance> {
+	/// `buyer` paid `price` to `seller`.
+	fn on_purchase(buyer: &AccountId, seller: &AccountId, price: Balance);
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_support::{
-		sp_runtime::traits::Hash,
-		traits::{tokens::ExistenceRequirement, Currency, Randomness},
+		sp_runtime::{
+			traits::{Hash, Saturating, UniqueSaturatedInto, Zero},
+			Perbill,
+		},
+		storage::{child, StoragePrefixedMap},
+		traits::{
+			tokens::{fungible, ExistenceRequirement},
+			BalanceStatus, Contains, Currency, Randomness, ReservableCurrency, VestingSchedule,
+		},
 		transactional,
 	};
 	use frame_system::pallet_prelude::*;
 	use scale_info::TypeInfo;
-	use sp_io::hashing::blake2_128;
+	use sp_io::hashing::blake2_256;
+
+	use crate::{
+		archive::{self, ArchivedCourse},
+		audit::{AuditPage, Inconsistency, ReportPage},
+		blobs::{self, Blob},
+		dna::{self, Dna},
+		history::{history_key, CourseRecord},
+		migrations::{Cursor, MigrationSequence},
+		snapshot::{self, Frontier},
+		CourseTransferHandler, PurchaseHandler, WeightInfo,
+	};
 
 	#[cfg(feature = "std")]
 	use frame_support::serde::{Deserialize, Serialize};
@@ -20,19 +157,304 @@ pub mod pallet {
 	type AccountOf<T> = <T as frame_system::Config>::AccountId;
 	type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	type StableBalanceOf<T> = <<T as Config>::StableCurrency as fungible::Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
 
 	// Struct for holding Course information.
 	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 	#[scale_info(skip_type_params(T))]
 	#[codec(mel_bound())]
 	pub struct Course<T: Config> {
-		pub dna: [u8; 16], // Using 16 bytes to represent a course DNA
+		pub dna: Dna, // Traits laid out as described in `dna`
 		pub price: Option<BalanceOf<T>>,
 		pub course_year: CourseYear,
 		pub owner: AccountOf<T>,
+		pub blobs_root: Option<T::Hash>, // Root of the child trie of `blobs`, if any
+	}
+
+	/// IPFS identifier of a document, in its textual form.
+	pub type Cid<T> = BoundedVec<u8, <T as Config>::MaxCidLength>;
+
+	// Struct for holding the off-chain content of a Course.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct CourseContent<T: Config> {
+		pub syllabus: Option<Cid<T>>,
+		pub materials: Option<Cid<T>>,
+	}
+
+	// Struct for holding a declining-price listing of a Course.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct DutchListing<T: Config> {
+		pub start_price: BalanceOf<T>,
+		pub floor_price: BalanceOf<T>,
+		/// The amount the price drops by every block.
+		pub decay: BalanceOf<T>,
+		pub start: T::BlockNumber,
+	}
+
+	// Struct for holding a sealed-bid listing of a Course.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct SealedListing<T: Config> {
+		pub seller: AccountOf<T>,
+		pub floor_price: BalanceOf<T>,
+		/// The last block bids can be committed in.
+		pub commit_end: T::BlockNumber,
+		/// The last block bids can be revealed in.
+		pub reveal_end: T::BlockNumber,
+		/// The highest bid revealed so far, and its bidder.
+		pub best: Option<(AccountOf<T>, BalanceOf<T>)>,
+		/// The second-highest bid revealed so far, or the floor price.
+		pub second: BalanceOf<T>,
+		/// Whether the winner pays the second-highest bid rather than its own.
+		pub second_price: bool,
+	}
+
+	// Struct for holding a sealed bid until it is revealed.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct SealedBid<T: Config> {
+		/// `T::Hashing` of the SCALE-encoded course ID, bidder, bid and salt.
+		pub commitment: T::Hash,
+		/// The amount reserved, which the bid cannot exceed.
+		pub reserved: BalanceOf<T>,
+		/// The `commit_end` of the listing the bid was committed to.
+		pub commit_end: T::BlockNumber,
+		/// Whether the listing is a second-price one, where a bid that is not revealed forfeits
+		/// `UnrevealedForfeit` of its reserve.
+		pub second_price: bool,
+	}
+
+	// Struct for holding the price an account watches a Course for.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Watch<T: Config> {
+		pub watcher: AccountOf<T>,
+		/// The price at or below which the watcher is alerted.
+		pub target: BalanceOf<T>,
+		/// The amount reserved from the watcher, returned by `unwatch()`.
+		pub deposit: BalanceOf<T>,
+	}
+
+	/// Identifier of a bundle listing.
+	pub type BundleId = u32;
+
+	// Struct for holding a listing of several Courses sold together.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Bundle<T: Config> {
+		pub seller: AccountOf<T>,
+		pub courses: BoundedVec<T::Hash, T::MaxBundleSize>,
+		pub price: BalanceOf<T>,
+	}
+
+	// Struct for holding the versions of the content of a Course.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct MetadataLog<T: Config> {
+		/// The number of times the content was updated.
+		pub version: u32,
+		/// The block the current content was set in.
+		pub updated: T::BlockNumber,
+		/// The hashes of the previous `Option<CourseContent>`s and the blocks they were set in,
+		/// oldest first. The content a Course was minted with is reported as set in block 0.
+		pub previous: BoundedVec<(T::Hash, T::BlockNumber), T::MaxMetadataHistory>,
+	}
+
+	// Struct for holding the deposit reserved for a Course.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Deposit<T: Config> {
+		pub depositor: AccountOf<T>,
+		pub amount: BalanceOf<T>,
+		/// The block the Course was minted in.
+		pub minted: T::BlockNumber,
+	}
+
+	/// How much of a Course deposit is returned when the Course is burned.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct RefundSchedule<BlockNumber> {
+		/// The number of blocks after minting during which the whole deposit is returned, a term.
+		pub full_refund_period: BlockNumber,
+		/// The part of the deposit returned afterwards, the rest is slashed.
+		pub late_refund: Perbill,
+	}
+
+	/// Identifier of the term a Course is offered in.
+	pub type Term = u32;
+
+	// Struct for holding where a cloned Course comes from.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Lineage<T: Config> {
+		pub cloned_from: T::Hash,
+		pub term: Term,
+	}
+
+	// Struct for holding the archive of a term, see `crate::archive`.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+	pub struct ArchivedTerm<Hash> {
+		/// The root of the child trie of the term.
+		pub root: Hash,
+		/// The number of Courses archived.
+		pub courses: u32,
+	}
+
+	type ArchivedCourseOf<T> = ArchivedCourse<
+		AccountOf<T>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+		<T as frame_system::Config>::Hash,
+	>;
+
+	// Struct for holding a Course transfer waiting for the recipient to claim it.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct TransferOffer<T: Config> {
+		pub to: AccountOf<T>,
+		/// The last block the offer can be claimed in.
+		pub expires: T::BlockNumber,
+	}
+
+	/// The sales of Courses of a year in a session.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct SessionStats<Balance> {
+		pub session: u32,
+		pub trades: u32,
+		pub volume: Balance,
+		/// The lowest price paid.
+		pub floor: Balance,
+	}
+
+	/// The sales of Courses of a year over the last `MaxStatsSessions` sessions.
+	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, Default)]
+	pub struct MarketSummary<Balance> {
+		pub trades: u32,
+		pub volume: Balance,
+		/// The lowest price paid, `None` without sales.
+		pub floor: Option<Balance>,
+	}
+
+	/// The Courses of a year, which is also their collection, minted, burned and sold since the
+	/// counters were introduced in storage version 4.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+	pub struct YearStats<Balance> {
+		/// The number of Courses minted, those existing at the upgrade included.
+		pub minted: u64,
+		pub burned: u64,
+		/// The number of sales.
+		pub trades: u64,
+		/// The sum of the prices paid.
+		pub volume: Balance,
+		/// The average price paid, zero without sales.
+		pub average_price: Balance,
+	}
+
+	/// The size of the whole registry and of its market.
+	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, Default)]
+	pub struct ChainStats<Balance> {
+		/// The number of Courses in existence.
+		pub courses: u64,
+		/// The number of accounts owning at least one Course.
+		pub owners: u32,
+		/// The sum of the fixed prices of the Courses for sale.
+		pub listed_value: Balance,
+		/// The sales of Courses of every year.
+		pub market: MarketSummary<Balance>,
+	}
+
+	/// A sale of a Course, as kept in the recent trades.
+	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Trade<AccountId, Hash, Balance, BlockNumber> {
+		pub course_id: Hash,
+		pub seller: AccountId,
+		pub buyer: AccountId,
+		pub price: Balance,
+		/// The block the sale was made in.
+		pub block: BlockNumber,
+	}
+
+	type TradeOf<T> = Trade<
+		AccountOf<T>,
+		<T as frame_system::Config>::Hash,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// The fee charged on transfers and sales of the Courses of a year, paid to the department
+	/// owning the year's collection.
+	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct TransferFee<AccountId, Balance> {
+		pub amount: Balance,
+		/// The account of the department the fee is paid to.
+		pub department: AccountId,
+	}
+
+	type TransferFeeOf<T> = TransferFee<AccountOf<T>, BalanceOf<T>>;
+
+	/// Identifier of a swap proposal.
+	pub type SwapId = u32;
+
+	// Struct for holding a proposal to exchange two Courses.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Swap<T: Config> {
+		pub proposer: AccountOf<T>,
+		pub counterparty: AccountOf<T>,
+		/// The Course the proposer gives.
+		pub offered: T::Hash,
+		/// The Course the proposer receives.
+		pub requested: T::Hash,
+		/// An amount the proposer pays on top of the offered Course.
+		pub sweetener: Option<BalanceOf<T>>,
 	}
 
+	/// Identifier of an ownership snapshot.
+	pub type SnapshotId = u32;
+
+	// Struct for holding a completed ownership snapshot.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct OwnerSnapshot<T: Config> {
+		pub course_year: CourseYear,
+		/// The block the holdings were taken at.
+		pub block: T::BlockNumber,
+		/// The Merkle root of the holdings, see the `snapshot` module.
+		pub root: T::Hash,
+		/// The number of accounts owning Courses of the year.
+		pub holders: u32,
+	}
+
+	// Struct for holding the progress of the ownership snapshot being taken.
 	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct SnapshotProgress<T: Config> {
+		pub id: SnapshotId,
+		pub course_year: CourseYear,
+		pub started: T::BlockNumber,
+		/// The raw `OwnedPerYear` key the walk resumes after, empty before the first step.
+		pub cursor: Cursor,
+		pub frontier: Frontier<T::Hash>,
+		pub holders: u32,
+	}
+
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 	pub enum CourseYear {
 		First,
@@ -41,8 +463,11 @@ pub mod pallet {
 		Fourth,
 	}
 
+	/// The storage version of the pallet. Bump it along with setting a new `Migration`.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+
 	#[pallet::pallet]
-	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types it depends on.
@@ -52,14 +477,158 @@ pub mod pallet {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
 		/// The Currency handler for the Courses pallet.
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The amount reserved from the minter of a Course until it is burned.
+		#[pallet::constant]
+		type CourseDeposit: Get<BalanceOf<Self>>;
+
+		/// How much of the deposit of a burned Course is returned.
+		#[pallet::constant]
+		type DepositRefundSchedule: Get<RefundSchedule<Self::BlockNumber>>;
+
+		/// The amount reserved per byte of the content of a Course, until the content is cleared.
+		#[pallet::constant]
+		type DepositPerByte: Get<BalanceOf<Self>>;
 
 		/// The maximum amount of Courses a single account can own.
 		#[pallet::constant]
 		type MaxCoursesOwned: Get<u32>;
 
+		/// The amount of Courses a single account can own, capped by `MaxCoursesOwned`. Unlike
+		/// `MaxCoursesOwned`, it can change without migrating the lists of owned Courses.
+		type CoursesOwnedLimit: Get<u32>;
+
+		/// The maximum number of Courses an account can create, breed or clone per session.
+		#[pallet::constant]
+		type MaxMintsPerSession: Get<u32>;
+
+		/// The index of the current session.
+		type CurrentSession: Get<u32>;
+
+		/// The maximum amount of Courses of a single `CourseYear` an account can own, unless
+		/// governance set a limit for the year.
+		#[pallet::constant]
+		type MaxCoursesPerYear: Get<u32>;
+
 		/// The type of Randomness we want to specify for this pallet.
 		type CourseRandomness: Randomness<Self::Hash, Self::BlockNumber>;
+
+		/// Whether both parents of a bred Course must be of the same `CourseYear`.
+		#[pallet::constant]
+		type BreedSameYearOnly: Get<bool>;
+
+		/// The number of blocks a Course must have been owned for before breeding it.
+		#[pallet::constant]
+		type MinBreedingAge: Get<Self::BlockNumber>;
+
+		/// The maximum length of a CID referencing course content.
+		#[pallet::constant]
+		type MaxCidLength: Get<u32>;
+
+		/// The maximum number of previous contents remembered per Course.
+		#[pallet::constant]
+		type MaxMetadataHistory: Get<u32>;
+
+		/// The maximum number of CIDs the off-chain worker pins after a single block.
+		#[pallet::constant]
+		type MaxPendingPins: Get<u32>;
+
+		/// The number of recent sale prices kept per `CourseYear`.
+		#[pallet::constant]
+		type MaxRecentSales: Get<u32>;
+
+		/// The number of recent trades kept across all years.
+		#[pallet::constant]
+		type MaxRecentTrades: Get<u32>;
+
+		/// The number of sessions the market statistics of a `CourseYear` cover.
+		#[pallet::constant]
+		type MaxStatsSessions: Get<u32>;
+
+		/// The maximum number of Courses in a bundle.
+		#[pallet::constant]
+		type MaxBundleSize: Get<u32>;
+
+		/// The number of blocks after receiving a Course before it can be transferred or listed
+		/// again.
+		#[pallet::constant]
+		type TransferCooldown: Get<Self::BlockNumber>;
+
+		/// The accounts allowed to buy Courses.
+		type BuyerFilter: Contains<Self::AccountId>;
+
+		/// Origin allowed to change the marketplace rules.
+		type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin allowed to pause and resume the pallet.
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The number of blocks a transfer offer can be claimed for.
+		#[pallet::constant]
+		type OfferExpiry: Get<Self::BlockNumber>;
+
+		/// The migrations bringing the storage to the current storage version, in the order of
+		/// their versions, run over several blocks after the runtime upgrade.
+		type Migration: MigrationSequence;
+
+		/// Pallets reacting to courses being minted, transferred and burned.
+		type OnTransfer: CourseTransferHandler<Self::AccountId, Self::Hash>;
+
+		/// Pallets reacting to courses being bought.
+		type OnPurchase: PurchaseHandler<Self::AccountId, BalanceOf<Self>>;
+
+		/// Locks the proceeds of large sales until they vest.
+		type Vesting: VestingSchedule<
+			Self::AccountId,
+			Currency = Self::Currency,
+			Moment = Self::BlockNumber,
+		>;
+
+		/// The price from which the proceeds of a sale vest over `ProceedsVestingPeriod` instead
+		/// of being available to the seller at once.
+		#[pallet::constant]
+		type VestingThreshold: Get<BalanceOf<Self>>;
+
+		/// The number of blocks the proceeds of a large sale vest over, linearly.
+		#[pallet::constant]
+		type ProceedsVestingPeriod: Get<Self::BlockNumber>;
+
+		/// The stablecoin Courses can also be priced and bought in, next to `Currency`.
+		type StableCurrency: fungible::Mutate<Self::AccountId> + fungible::Transfer<Self::AccountId>;
+
+		/// The number of blocks a sealed-bid listing takes bids for.
+		#[pallet::constant]
+		type SealedCommitPeriod: Get<Self::BlockNumber>;
+
+		/// The number of blocks after the commit period during which sealed bids are revealed.
+		#[pallet::constant]
+		type SealedRevealPeriod: Get<Self::BlockNumber>;
+
+		/// The part of the reserve of a sealed bid on a second-price listing that is slashed if
+		/// the bid is not revealed.
+		#[pallet::constant]
+		type UnrevealedForfeit: Get<Perbill>;
+
+		/// The amount reserved from an account for every Course it watches.
+		#[pallet::constant]
+		type WatchDeposit: Get<BalanceOf<Self>>;
+
+		/// The maximum number of accounts watching a Course. Listing and selling the Course alerts
+		/// each of them, at a cost the weights do not account for.
+		#[pallet::constant]
+		type MaxWatchers: Get<u32>;
+
+		/// The maximum number of Courses archived at once.
+		#[pallet::constant]
+		type MaxArchiveBatch: Get<u32>;
+
+		/// The maximum length of a blob of a Course, in bytes.
+		#[pallet::constant]
+		type MaxBlobLen: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	// Errors.
@@ -67,7 +636,7 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// Handles arithmetic overflow when incrementing the Course counter.
 		CountForCoursesOverflow,
-		/// An account cannot own more Courses than `MaxCourseCount`.
+		/// An account cannot own more Courses than `CoursesOwnedLimit`.
 		ExceedMaxCourseOwned,
 		/// Buyer cannot be the owner.
 		BuyerIsCourseOwner,
@@ -85,11 +654,97 @@ pub mod pallet {
 		CourseBidPriceTooLow,
 		/// Ensures that an account has enough funds to purchase a Course.
 		NotEnoughBalance,
+		/// The account is neither the owner of the Course nor approved to transfer it.
+		NotApproved,
+		/// The content identifier is not a valid CIDv0 or base32 CIDv1.
+		InvalidCid,
+		/// The storage is being migrated, try again once the migration completed.
+		MigrationOngoing,
+		/// The floor price of a listing is above its start price.
+		FloorAboveStart,
+		/// A bundle must contain at least one Course, and each Course at most once.
+		InvalidBundle,
+		/// Handles arithmetic overflow when incrementing the bundle identifier.
+		BundleIdOverflow,
+		/// Handles checking whether the bundle exists.
+		BundleNotExist,
+		/// The seller no longer owns every Course of the bundle.
+		BundleUnavailable,
+		/// Only the seller can cancel a bundle.
+		NotBundleSeller,
+		/// The recipient only accepts Courses through `offer_transfer()`.
+		DirectTransferNotAccepted,
+		/// Handles checking whether a transfer of the Course is offered to the sender.
+		NoTransferOffer,
+		/// The transfer offer was not claimed in time.
+		TransferOfferExpired,
+		/// The buyer is not allowed to buy Courses.
+		NotEligibleBuyer,
+		/// The account, or its counterparty, is banned from the marketplace.
+		Banned,
+		/// The Course was received less than `TransferCooldown` blocks ago.
+		CourseCoolingDown,
+		/// Handles arithmetic overflow when incrementing the swap identifier.
+		SwapIdOverflow,
+		/// Handles checking whether the swap exists.
+		SwapNotExist,
+		/// Only the proposer can cancel a swap, and only the counterparty can accept it.
+		NotSwapParty,
+		/// One of the Courses of the swap changed hands since it was proposed.
+		SwapUnavailable,
+		/// An account cannot own more Courses of this year.
+		ExceedMaxCoursesPerYear,
+		/// The parents of a bred Course must be of the same year.
+		ParentsDifferentYears,
+		/// A parent was not owned for `MinBreedingAge` blocks yet.
+		ParentTooRecent,
+		/// The pallet is paused by governance.
+		PalletPaused,
+		/// The account minted `MaxMintsPerSession` Courses this session already.
+		MintRateLimitExceeded,
+		/// An ownership snapshot is being taken already.
+		SnapshotPending,
+		/// Ran out of snapshot ids.
+		SnapshotIdOverflow,
+		/// The Course was minted before creators were recorded, and nobody reserved its deposit.
+		CreatorUnknown,
+		/// A tip must not be zero.
+		ZeroTip,
+		/// The creator of a Course cannot tip itself.
+		TipToSelf,
+		/// The seller has as many vesting schedules as it can, and must merge some before selling
+		/// for more than `VestingThreshold`.
+		TooManyVestingSchedules,
+		/// The Course has an open sealed-bid listing.
+		SealedListingExists,
+		/// The Course has no sealed-bid listing.
+		NoSealedListing,
+		/// The sealed-bid listing no longer takes bids.
+		NotCommitPhase,
+		/// The sealed-bid listing is not revealing bids.
+		NotRevealPhase,
+		/// The sealed-bid listing is not over yet.
+		SealedListingOpen,
+		/// The account committed a bid for the Course already.
+		SealedBidExists,
+		/// The account has no sealed bid for the Course.
+		NoSealedBid,
+		/// The bid does not match its commitment, exceeds the amount reserved or is below the
+		/// floor price.
+		InvalidSealedReveal,
+		/// The Course has `MaxWatchers` watchers already.
+		TooManyWatchers,
+		/// The account does not watch the Course.
+		NotWatching,
+		/// The Course was not cloned for the term.
+		NotOfTerm,
 	}
 
 	// Events.
+	//
+	// Keep `EVENT_SCHEMA` in sync, indexers rely on it. Events are deposited with
+	// `deposit_course_event`, which indexes them by the topics of `EVENT_TOPICS`.
 	#[pallet::event]
-	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// A new Course was successfully created. \[sender, course_id\]
 		Created(T::AccountId, T::Hash),
@@ -98,7 +753,108 @@ pub mod pallet {
 		/// A Course was successfully transferred. \[from, to, course_id\]
 		Transferred(T::AccountId, T::AccountId, T::Hash),
 		/// A Course was successfully bought. \[buyer, seller, course_id, bid_price\]
+		///
+		/// If the buyer allowed its account to die, the account may have been reaped by the
+		/// payment: its remaining dust is lost and its nonce reset, but it keeps the course, and
+		/// needs to be funded again before it can send transactions.
 		Bought(T::AccountId, T::AccountId, T::Hash, BalanceOf<T>),
+		/// An account was approved to transfer a Course, or the approval was cleared.
+		/// \[owner, course_id, delegate\]
+		Approved(T::AccountId, T::Hash, Option<T::AccountId>),
+		/// The off-chain content of a Course was updated. \[sender, course_id\]
+		ContentSet(T::AccountId, T::Hash),
+		/// A Course was burned and its deposit returned to its minter, in full or in part.
+		/// \[owner, course_id, refund\]
+		Burned(T::AccountId, T::Hash, BalanceOf<T>),
+		/// A Course was bred from two Courses of its owner.
+		/// \[owner, parent1, parent2, course_id\]
+		Bred(T::AccountId, T::Hash, T::Hash, T::Hash),
+		/// A Course was cloned for a new term. \[owner, source_id, course_id, term\]
+		Cloned(T::AccountId, T::Hash, T::Hash, Term),
+		/// A Course was listed at a price declining every block.
+		/// \[sender, course_id, start_price, floor_price, decay\]
+		DutchListed(T::AccountId, T::Hash, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Courses were listed together as a bundle. \[seller, bundle_id, price\]
+		BundleCreated(T::AccountId, BundleId, BalanceOf<T>),
+		/// A bundle listing was withdrawn. \[seller, bundle_id\]
+		BundleCancelled(T::AccountId, BundleId),
+		/// A bundle was bought, each of its Courses is also reported as `Transferred`.
+		/// \[buyer, seller, bundle_id, price\]
+		BundleBought(T::AccountId, T::AccountId, BundleId, BalanceOf<T>),
+		/// A Course was offered to an account, which can claim it until `expires`.
+		/// \[from, to, course_id, expires\]
+		TransferOffered(T::AccountId, T::AccountId, T::Hash, T::BlockNumber),
+		/// A transfer offer was rejected by the recipient or withdrawn by the owner.
+		/// \[sender, course_id\]
+		TransferRejected(T::AccountId, T::Hash),
+		/// An exchange of Courses was proposed. \[proposer, counterparty, swap_id\]
+		SwapProposed(T::AccountId, T::AccountId, SwapId),
+		/// A swap proposal was withdrawn. \[proposer, swap_id\]
+		SwapCancelled(T::AccountId, SwapId),
+		/// A swap was accepted, both Courses are also reported as `Transferred`.
+		/// \[counterparty, proposer, swap_id\]
+		SwapAccepted(T::AccountId, T::AccountId, SwapId),
+		/// An account was banned from the marketplace. \[who\]
+		AccountBanned(T::AccountId),
+		/// An account was allowed back in the marketplace. \[who\]
+		AccountUnbanned(T::AccountId),
+		/// Courses of a year were exempted from, or made subject to, the transfer cooldown.
+		/// \[course_year, exempt\]
+		CooldownExemptionSet(CourseYear, bool),
+		/// The pallet was paused or resumed. \[paused\]
+		PauseSet(bool),
+		/// The limit on the Courses of a year an account can own was set, `None` restoring
+		/// `MaxCoursesPerYear`. \[course_year, limit\]
+		YearLimitSet(CourseYear, Option<u32>),
+		/// An ownership snapshot of the Courses of a year was started.
+		/// \[snapshot_id, course_year, block\]
+		SnapshotStarted(SnapshotId, CourseYear, T::BlockNumber),
+		/// An ownership snapshot was completed. \[snapshot_id, root, holders\]
+		SnapshotCompleted(SnapshotId, T::Hash, u32),
+		/// A storage migration to a new storage version started. \[version\]
+		MigrationStarted(u16),
+		/// A block made progress on the storage migration. \[steps\]
+		MigrationAdvanced(u32),
+		/// The storage migration completed. \[version, steps\]
+		MigrationCompleted(u16, u32),
+		/// The creator of a Course was tipped. \[tipper, creator, course_id, amount\]
+		Tipped(T::AccountId, T::AccountId, T::Hash, BalanceOf<T>),
+		/// The stablecoin price of a Course was set. \[sender, course_id, new_price\]
+		StablePriceSet(T::AccountId, T::Hash, Option<StableBalanceOf<T>>),
+		/// A Course was bought in the stablecoin. \[buyer, seller, course_id, price\]
+		BoughtInStable(T::AccountId, T::AccountId, T::Hash, StableBalanceOf<T>),
+		/// The transfer fee of the Courses of a year was set, or removed if `None`.
+		/// \[course_year, fee\]
+		TransferFeeSet(CourseYear, Option<TransferFeeOf<T>>),
+		/// A transfer fee was paid to a department. \[payer, department, course_id, amount\]
+		TransferFeePaid(T::AccountId, T::AccountId, T::Hash, BalanceOf<T>),
+		/// A Course was listed for sealed bids, paying the second-highest bid if `second_price`.
+		/// \[sender, course_id, floor_price, reveal_end, second_price\]
+		SealedListed(T::AccountId, T::Hash, BalanceOf<T>, T::BlockNumber, bool),
+		/// A sealed bid was committed. \[buyer, course_id\]
+		PurchaseCommitted(T::AccountId, T::Hash),
+		/// A sealed bid was revealed. \[buyer, course_id, bid\]
+		PurchaseRevealed(T::AccountId, T::Hash, BalanceOf<T>),
+		/// A sealed bid that was not revealed was withdrawn, forfeiting part of its reserve on
+		/// second-price listings. \[buyer, course_id, forfeited\]
+		SealedBidWithdrawn(T::AccountId, T::Hash, BalanceOf<T>),
+		/// A sealed-bid listing closed without a sale. \[seller, course_id\]
+		SealedListingClosed(T::AccountId, T::Hash),
+		/// An account started watching a Course, or changed its target price.
+		/// \[watcher, course_id, target\]
+		Watched(T::AccountId, T::Hash, BalanceOf<T>),
+		/// An account stopped watching a Course. \[watcher, course_id\]
+		Unwatched(T::AccountId, T::Hash),
+		/// A watched Course was listed or sold at or below the target price of its watcher.
+		/// \[watcher, course_id, price\]
+		WatchTriggered(T::AccountId, T::Hash, BalanceOf<T>),
+		/// A Course was moved to the archive of its term. \[owner, course_id, term\]
+		CourseArchived(T::AccountId, T::Hash, Term),
+		/// Courses were archived, changing the root of the archive of their term.
+		/// \[term, courses, root\]
+		TermArchived(Term, u32, T::Hash),
+		/// A blob of a Course was set, or removed. \[sender, course_id, blob\]
+		BlobSet(T::AccountId, T::Hash, Blob),
 	}
 
 	// Storage items.
@@ -124,209 +880,1996 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
-	// Our pallet's genesis configuration.
-	#[pallet::genesis_config]
-	pub struct GenesisConfig<T: Config> {
-		pub courses: Vec<(T::AccountId, [u8; 16], CourseYear)>,
-	}
+	#[pallet::storage]
+	#[pallet::getter(fn owned_count)]
+	/// The number of Courses an account owns, the length of its `CoursesOwned` list.
+	pub(super) type OwnedCount<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
 
-	// Required to implement default for GenesisConfig.
-	#[cfg(feature = "std")]
-	impl<T: Config> Default for GenesisConfig<T> {
-		fn default() -> GenesisConfig<T> {
-			GenesisConfig { courses: vec![] }
-		}
-	}
+	#[pallet::storage]
+	#[pallet::getter(fn owner_count)]
+	/// The number of accounts owning at least one Course.
+	pub(super) type OwnerCount<T: Config> = StorageValue<_, u32, ValueQuery>;
 
-	#[pallet::genesis_build]
-	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
-		fn build(&self) {
-			for (acct, dna, course_year) in &self.courses {
-				let _ = <Pallet<T>>::mint(acct, Some(dna.clone()), Some(course_year.clone()));
-			}
-		}
-	}
+	#[pallet::storage]
+	#[pallet::getter(fn listed_value)]
+	/// The sum of the fixed prices of the Courses for sale, declining-price listings aside.
+	pub(super) type ListedValue<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
-	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	#[pallet::storage]
+	#[pallet::getter(fn session_mints)]
+	/// The session an account last minted a Course in, and the number of Courses it minted then.
+	pub(super) type SessionMints<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (u32, u32), ValueQuery>;
 
-	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
-	// These functions materialize as "extrinsics", which are often compared to transactions.
-	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
+	#[pallet::storage]
+	#[pallet::getter(fn owned_per_year)]
+	/// The number of Courses of each year an account owns.
+	pub(super) type OwnedPerYear<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, CourseYear, u32, ValueQuery>;
 
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		/// Create a new unique course.
-		///
-		/// The actual course creation is done in the `mint()` function.
-		#[pallet::weight(100)]
-		pub fn create_course(origin: OriginFor<T>) -> DispatchResult {
-			let sender = ensure_signed(origin)?;
+	#[pallet::storage]
+	#[pallet::getter(fn year_limits)]
+	/// Limits on the Courses of a year an account can own, replacing `MaxCoursesPerYear`.
+	pub(super) type YearLimits<T: Config> = StorageMap<_, Twox64Concat, CourseYear, u32>;
 
-			let course_id = Self::mint(&sender, None, None)?;
+	#[pallet::storage]
+	#[pallet::getter(fn dutch_listings)]
+	/// The declining-price listings, which take the place of the fixed price of a Course.
+	pub(super) type DutchListings<T: Config> =
+		StorageMap<_, Twox64Concat, T::Hash, DutchListing<T>>;
 
-			// Logging to the console
-			log::info!("A course is born with ID: {:?}.", course_id);
-			// Deposit our "Created" event.
-			Self::deposit_event(Event::Created(sender, course_id));
-			Ok(())
-		}
+	#[pallet::storage]
+	#[pallet::getter(fn bundles)]
+	/// Courses listed for sale together, at a single price.
+	pub(super) type Bundles<T: Config> = StorageMap<_, Twox64Concat, BundleId, Bundle<T>>;
 
-		/// Set the price for a Course.
-		///
-		/// Updates Course price and updates storage.
-		#[pallet::weight(100)]
-		pub fn set_price(
-			origin: OriginFor<T>,
-			course_id: T::Hash,
-			new_price: Option<BalanceOf<T>>,
-		) -> DispatchResult {
-			let sender = ensure_signed(origin)?;
+	#[pallet::storage]
+	#[pallet::getter(fn next_bundle_id)]
+	/// The identifier of the next bundle.
+	pub(super) type NextBundleId<T: Config> = StorageValue<_, BundleId, ValueQuery>;
 
-			// Ensure the course exists and is called by the course owner
-			ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+	#[pallet::storage]
+	#[pallet::getter(fn transfer_offers)]
+	/// Transfers waiting for the recipient to claim them, by Course.
+	pub(super) type TransferOffers<T: Config> =
+		StorageMap<_, Twox64Concat, T::Hash, TransferOffer<T>>;
 
-			let mut course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+	#[pallet::storage]
+	#[pallet::getter(fn accepts_direct_transfers)]
+	/// Accounts which accept Courses through `transfer()` without claiming them.
+	pub(super) type AcceptsDirectTransfers<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, bool, ValueQuery>;
 
-			course.price = new_price.clone();
-			<Courses<T>>::insert(&course_id, course);
+	#[pallet::storage]
+	#[pallet::getter(fn banned_accounts)]
+	/// Accounts excluded from buying, listing and offering Courses.
+	pub(super) type BannedAccounts<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, ()>;
 
-			// Deposit a "PriceSet" event.
-			Self::deposit_event(Event::PriceSet(sender, course_id, new_price));
+	#[pallet::storage]
+	#[pallet::getter(fn acquired_at)]
+	/// The block each Course last changed hands in, for the transfer cooldown.
+	pub(super) type AcquiredAt<T: Config> = StorageMap<_, Twox64Concat, T::Hash, T::BlockNumber>;
 
-			Ok(())
-		}
+	#[pallet::storage]
+	#[pallet::getter(fn owned_since)]
+	/// The block each Course was minted in or last changed hands in, for breeding. Not set for
+	/// Courses which have not changed hands since before it was introduced.
+	pub(super) type OwnedSince<T: Config> = StorageMap<_, Twox64Concat, T::Hash, T::BlockNumber>;
 
-		/// Directly transfer a course to another recipient.
-		///
-		/// Any account that holds a course can send it to another Account. This will reset the asking
-		/// price of the course, marking it not for sale.
-		#[pallet::weight(100)]
-		pub fn transfer(
-			origin: OriginFor<T>,
-			to: T::AccountId,
-			course_id: T::Hash,
-		) -> DispatchResult {
-			let from = ensure_signed(origin)?;
+	#[pallet::storage]
+	#[pallet::getter(fn cooldown_exempt)]
+	/// Course years whose Courses can be transferred again right after being received.
+	pub(super) type CooldownExempt<T: Config> =
+		StorageMap<_, Twox64Concat, CourseYear, bool, ValueQuery>;
 
-			// Ensure the course exists and is called by the course owner
-			ensure!(Self::is_course_owner(&course_id, &from)?, <Error<T>>::NotCourseOwner);
+	#[pallet::storage]
+	#[pallet::getter(fn swaps)]
+	/// Pending proposals to exchange two Courses.
+	pub(super) type Swaps<T: Config> = StorageMap<_, Twox64Concat, SwapId, Swap<T>>;
 
-			// Verify the course is not transferring back to its owner.
-			ensure!(from != to, <Error<T>>::TransferToSelf);
+	#[pallet::storage]
+	#[pallet::getter(fn next_swap_id)]
+	/// The identifier of the next swap proposal.
+	pub(super) type NextSwapId<T: Config> = StorageValue<_, SwapId, ValueQuery>;
 
-			// Verify the recipient has the capacity to receive one more course
-			let to_owned = <CoursesOwned<T>>::get(&to);
-			ensure!(
-				(to_owned.len() as u32) < T::MaxCoursesOwned::get(),
-				<Error<T>>::ExceedMaxCourseOwned
-			);
+	#[pallet::storage]
+	#[pallet::getter(fn course_approvals)]
+	/// The account, other than the owner, allowed to transfer a Course.
+	pub(super) type CourseApprovals<T: Config> = StorageMap<_, Twox64Concat, T::Hash, T::AccountId>;
 
-			Self::transfer_course_to(&course_id, &to)?;
+	#[pallet::storage]
+	#[pallet::getter(fn course_content)]
+	/// The IPFS identifiers of a Course's syllabus and materials.
+	pub(super) type CourseContents<T: Config> =
+		StorageMap<_, Twox64Concat, T::Hash, CourseContent<T>>;
 
-			Self::deposit_event(Event::Transferred(from, to, course_id));
+	#[pallet::storage]
+	#[pallet::getter(fn metadata_log)]
+	/// The version of the content of each Course and the hashes of its previous contents.
+	pub(super) type MetadataLogs<T: Config> = StorageMap<_, Twox64Concat, T::Hash, MetadataLog<T>>;
 
-			Ok(())
-		}
+	#[pallet::storage]
+	#[pallet::getter(fn course_deposits)]
+	/// The deposit reserved for each Course minted by an account.
+	pub(super) type CourseDeposits<T: Config> = StorageMap<_, Twox64Concat, T::Hash, Deposit<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn content_deposits)]
+	/// The account that set the content of each Course and the deposit reserved from it.
+	pub(super) type ContentDeposits<T: Config> =
+		StorageMap<_, Twox64Concat, T::Hash, (T::AccountId, BalanceOf<T>)>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn creators)]
+	/// The creator of each Course: the account it was minted for, or the account that funded it
+	/// for Courses given away.
+	pub(super) type Creators<T: Config> = StorageMap<_, Twox64Concat, T::Hash, T::AccountId>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tips)]
+	/// The sum of the tips paid to the creator of each Course.
+	pub(super) type Tips<T: Config> =
+		StorageMap<_, Twox64Concat, T::Hash, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn stable_prices)]
+	/// The stablecoin price of the Courses for sale in `StableCurrency`.
+	pub(super) type StablePrices<T: Config> =
+		StorageMap<_, Twox64Concat, T::Hash, StableBalanceOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn course_lineage)]
+	/// The Course each cloned Course was cloned from, and the term it is offered in.
+	pub(super) type CourseLineage<T: Config> = StorageMap<_, Twox64Concat, T::Hash, Lineage<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn market_stats)]
+	/// The sales of each `CourseYear` per session with sales, oldest first.
+	pub(super) type MarketStats<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		CourseYear,
+		BoundedVec<SessionStats<BalanceOf<T>>, T::MaxStatsSessions>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn recent_sales)]
+	/// The prices of the latest sales of each `CourseYear`, oldest first.
+	pub(super) type RecentSales<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		CourseYear,
+		BoundedVec<BalanceOf<T>, T::MaxRecentSales>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	/// The trades made in the current block, moved to `RecentTrades` when it is finalized.
+	pub(super) type PendingTrades<T: Config> =
+		StorageValue<_, BoundedVec<TradeOf<T>, T::MaxRecentTrades>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn recent_trade)]
+	/// The last `MaxRecentTrades` trades, a ring buffer keeping trade `n` in slot
+	/// `n % MaxRecentTrades`.
+	pub(super) type RecentTrades<T: Config> = StorageMap<_, Twox64Concat, u32, TradeOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn trade_count)]
+	/// The number of trades recorded in `RecentTrades` so far.
+	pub(super) type TradeCount<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn transfer_fee)]
+	/// The fee charged on `transfer` and `buy_course` for the Courses of each year that has one.
+	pub(super) type TransferFees<T: Config> =
+		StorageMap<_, Twox64Concat, CourseYear, TransferFeeOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn sealed_listings)]
+	/// The sealed-bid listings, until they are settled.
+	pub(super) type SealedListings<T: Config> =
+		StorageMap<_, Twox64Concat, T::Hash, SealedListing<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn sealed_bids)]
+	/// The sealed bids of each account on each Course, until they are revealed or withdrawn.
+	pub(super) type SealedBids<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::Hash, Blake2_128Concat, T::AccountId, SealedBid<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn watches)]
+	/// The accounts watching each Course for a price, see `watch()`.
+	pub(super) type Watches<T: Config> =
+		StorageMap<_, Twox64Concat, T::Hash, BoundedVec<Watch<T>, T::MaxWatchers>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn year_stats)]
+	/// The Courses of each year minted, burned and sold, see `YearStats`.
+	pub(super) type YearStatistics<T: Config> =
+		StorageMap<_, Twox64Concat, CourseYear, YearStats<BalanceOf<T>>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn archived_terms)]
+	/// The archive of every term with archived Courses.
+	pub(super) type ArchivedTerms<T: Config> =
+		StorageMap<_, Twox64Concat, Term, ArchivedTerm<T::Hash>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn course_history_len)]
+	/// The number of records of a Course in the off-chain index, see `crate::history`.
+	pub(super) type CourseHistoryLen<T: Config> =
+		StorageMap<_, Twox64Concat, T::Hash, u32, ValueQuery>;
+
+	#[pallet::storage]
+	/// CIDs referenced in the current block, pinned by the off-chain worker once it is imported.
+	pub(super) type PendingPins<T: Config> =
+		StorageValue<_, BoundedVec<Cid<T>, T::MaxPendingPins>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn paused)]
+	/// Whether every call but those of governance is rejected.
+	pub(super) type Paused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn owner_snapshots)]
+	/// The completed ownership snapshots.
+	pub(super) type OwnerSnapshots<T: Config> =
+		StorageMap<_, Twox64Concat, SnapshotId, OwnerSnapshot<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_snapshot_id)]
+	/// The identifier of the next ownership snapshot.
+	pub(super) type NextSnapshotId<T: Config> = StorageValue<_, SnapshotId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_snapshot)]
+	/// The ownership snapshot being taken by `on_idle`, one at a time.
+	pub(super) type PendingSnapshot<T: Config> = StorageValue<_, SnapshotProgress<T>>;
+
+	#[pallet::storage]
+	/// The Courses of the pending snapshot's year owned by accounts whose holdings changed before
+	/// the snapshot reached them, as of the block it was started in.
+	pub(super) type SnapshotOverrides<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn migration_cursor)]
+	/// Where the ongoing storage migration resumes from, and the number of blocks it has run for.
+	/// Calls are rejected while it is set.
+	pub(super) type MigrationCursor<T: Config> = StorageValue<_, (Cursor, u32)>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn last_blocked)]
+	/// The last block calls were rejected in, because the pallet was paused or its storage was
+	/// being migrated.
+	pub(super) type LastBlocked<T: Config> = StorageValue<_, T::BlockNumber>;
+
+	// Our pallet's genesis configuration.
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub courses: Vec<(T::AccountId, Dna, CourseYear)>,
+	}
+
+	// Required to implement default for GenesisConfig.
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> GenesisConfig<T> {
+			GenesisConfig { courses: vec![] }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (acct, dna, course_year) in &self.courses {
+				let _ = <Pallet<T>>::mint(acct, Some(dna.clone()), Some(course_year.clone()));
+			}
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			// The check for trades in `on_finalize`, which are charged to the sales.
+			let mut weight = T::DbWeight::get().reads(1);
+			// Unrevealed sealed bids are not forfeited if reveals were rejected.
+			if Self::paused() || <MigrationCursor<T>>::exists() {
+				<LastBlocked<T>>::put(n);
+				weight += T::DbWeight::get().reads_writes(2, 1);
+			} else {
+				weight += T::DbWeight::get().reads(2);
+			}
+			// The previous block's CIDs were handed to its off-chain worker.
+			if <PendingPins<T>>::exists() {
+				<PendingPins<T>>::kill();
+				weight + T::DbWeight::get().reads_writes(1, 1)
+			} else {
+				weight + T::DbWeight::get().reads(1)
+			}
+		}
+
+		fn on_finalize(_n: T::BlockNumber) {
+			if !<PendingTrades<T>>::exists() {
+				return
+			}
+			let capacity = u64::from(T::MaxRecentTrades::get().max(1));
+			let mut count = Self::trade_count();
+			for trade in <PendingTrades<T>>::take() {
+				<RecentTrades<T>>::insert((count % capacity) as u32, trade);
+				count = count.saturating_add(1);
+			}
+			<TradeCount<T>>::put(count);
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain = Self::on_chain_storage_version();
+			if on_chain >= STORAGE_VERSION || <MigrationCursor<T>>::exists() {
+				return T::DbWeight::get().reads(2)
+			}
+
+			log::info!(
+				"Migrating the course storage from {:?} to {:?}.",
+				on_chain,
+				STORAGE_VERSION
+			);
+			<MigrationCursor<T>>::put((Cursor::default(), 0));
+			Self::deposit_unindexed_event(Event::MigrationStarted(Self::version_number()));
+			T::DbWeight::get().reads_writes(2, 1)
+		}
+
+		fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let overhead = T::DbWeight::get().reads_writes(2, 3);
+			let (cursor, steps) = match <MigrationCursor<T>>::get() {
+				Some(progress) if remaining_weight > overhead => progress,
+				Some(_) => return T::DbWeight::get().reads(1),
+				// Snapshots are only taken of migrated holdings.
+				None =>
+					return Self::step_snapshot(
+						remaining_weight.saturating_sub(T::DbWeight::get().reads(1)),
+					)
+					.saturating_add(T::DbWeight::get().reads(1)),
+			};
+
+			let on_chain = Self::storage_version_number(Self::on_chain_storage_version());
+			let step = T::Migration::step(on_chain, &cursor, remaining_weight - overhead);
+			let steps = steps.saturating_add(1);
+			match step {
+				Some((_, Some(next), used)) => {
+					<MigrationCursor<T>>::put((next, steps));
+					Self::deposit_unindexed_event(Event::MigrationAdvanced(steps));
+					used.saturating_add(overhead)
+				},
+				// The next migration starts over from an empty cursor in the next block.
+				Some((version, None, used)) if version < Self::version_number() => {
+					StorageVersion::new(version).put::<Self>();
+					<MigrationCursor<T>>::put((Cursor::default(), steps));
+					log::info!("Course storage migrated to {:?}.", version);
+					Self::deposit_unindexed_event(Event::MigrationAdvanced(steps));
+					used.saturating_add(overhead)
+				},
+				step => {
+					<MigrationCursor<T>>::kill();
+					STORAGE_VERSION.put::<Self>();
+					log::info!(
+						"Course storage migrated to {:?} in {} blocks.",
+						STORAGE_VERSION,
+						steps
+					);
+					Self::deposit_unindexed_event(Event::MigrationCompleted(
+						Self::version_number(),
+						steps,
+					));
+					step.map_or(0, |(_, _, used)| used).saturating_add(overhead)
+				},
+			}
+		}
+
+		fn offchain_worker(_n: T::BlockNumber) {
+			for cid in <PendingPins<T>>::get() {
+				if let Err(e) = crate::ipfs::pin(&cid) {
+					log::warn!("Failed to pin course content: {:?}", e);
+				}
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
+	}
+
+	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
+	// These functions materialize as "extrinsics", which are often compared to transactions.
+	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a new unique course.
+		///
+		/// The actual course creation is done in the `mint()` function.
+		#[pallet::weight(T::WeightInfo::create_course())]
+		pub fn create_course(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+			Self::ensure_can_mint(&sender)?;
+
+			let course_id = Self::mint(&sender, None, None)?;
+			Self::note_mint(&sender);
+			Self::reserve_deposit(&course_id, &sender)?;
+			Self::record_history(&course_id, None, &sender, None);
+
+			// Logging to the console
+			log::info!("A course is born with ID: {:?}.", course_id);
+			// Deposit our "Created" event.
+			Self::deposit_course_event(
+				Event::Created(sender.clone(), course_id),
+				&sender,
+				&course_id,
+			);
+			Ok(())
+		}
+
+		/// Set the price for a Course.
+		///
+		/// Updates Course price and updates storage. Delisting a Course that is not listed writes
+		/// nothing, and is charged accordingly.
+		#[pallet::weight(T::WeightInfo::set_price())]
+		pub fn set_price(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			new_price: Option<BalanceOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			// Ensure the course exists and is called by the course owner
+			ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+			if new_price.is_some() {
+				Self::ensure_not_banned(&sender)?;
+				Self::ensure_cooled_down(&course_id)?;
+				Self::ensure_not_sealed(&course_id)?;
+			}
+
+			let mut course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+			let unlisted = new_price.is_none() &&
+				course.price.is_none() &&
+				!<DutchListings<T>>::contains_key(&course_id);
+
+			let listed = course.price;
+			if !unlisted {
+				Self::note_price_change(course.price, new_price);
+				course.price = new_price.clone();
+				<Courses<T>>::insert(&course_id, course);
+				<DutchListings<T>>::remove(&course_id);
+			}
+
+			// Deposit a "PriceSet" event.
+			Self::deposit_course_event(
+				Event::PriceSet(sender.clone(), course_id, new_price),
+				&sender,
+				&course_id,
+			);
+			if let Some(price) = new_price {
+				Self::trigger_watches(&course_id, price, listed);
+			}
+
+			if unlisted {
+				Ok(Some(T::WeightInfo::set_price_unlisted()).into())
+			} else {
+				Ok(().into())
+			}
+		}
+
+		/// Directly transfer a course to another recipient.
+		///
+		/// Any account that holds a course can send it to another Account. This will reset the asking
+		/// price of the course, marking it not for sale.
+		///
+		/// Charged for the courses the sender and the recipient own, up to `MaxCoursesOwned` each.
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::transfer(
+			T::MaxCoursesOwned::get(),
+			T::MaxCoursesOwned::get(),
+		))]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			to: T::AccountId,
+			course_id: T::Hash,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			// Ensure the course exists and is called by the course owner
+			ensure!(Self::is_course_owner(&course_id, &from)?, <Error<T>>::NotCourseOwner);
+			Self::ensure_not_sealed(&course_id)?;
+
+			// Verify the course is not transferring back to its owner.
+			ensure!(from != to, <Error<T>>::TransferToSelf);
+
+			// Verify the recipient opted in to receiving courses unannounced
+			ensure!(Self::accepts_direct_transfers(&to), <Error<T>>::DirectTransferNotAccepted);
+
+			// Verify the recipient has the capacity to receive one more course
+			let owned_to = Self::owned_count(&to);
+			ensure!(owned_to < Self::owned_limit(), <Error<T>>::ExceedMaxCourseOwned);
+			let owned_from = Self::owned_count(&from);
+
+			Self::transfer_course_to(&course_id, &to)?;
+			Self::charge_transfer_fee(&from, &to, &course_id)?;
+			Self::record_history(&course_id, Some(&from), &to, None);
+
+			Self::deposit_course_event(
+				Event::Transferred(from, to.clone(), course_id),
+				&to,
+				&course_id,
+			);
+
+			Ok(Some(T::WeightInfo::transfer(owned_from, owned_to)).into())
+		}
 
 		/// Buy a saleable Course. The bid price provided from the buyer has to be equal or higher
 		/// than the ask price from the seller.
 		///
+		/// The buyer pays the bid price, except for declining-price listings where the bid is the
+		/// most the buyer is willing to pay and the current price is paid.
+		///
 		/// This will reset the asking price of the course, marking it not for sale.
 		/// Marking this method `transactional` so when an error is returned, we ensure no storage is changed.
+		///
+		/// The payment keeps the buyer's account alive unless `allow_death` is set, in which case
+		/// the buyer can spend its whole balance and the account is reaped if it drops below the
+		/// existential deposit. See `Event::Bought`.
+		///
+		/// Payments of at least `VestingThreshold` vest to the seller over `ProceedsVestingPeriod`.
+		///
+		/// Charged for the courses the seller and the buyer own, up to `MaxCoursesOwned` each.
 		#[transactional]
-		#[pallet::weight(100)]
+		#[pallet::weight(T::WeightInfo::buy_course(
+			T::MaxCoursesOwned::get(),
+			T::MaxCoursesOwned::get(),
+		))]
 		pub fn buy_course(
 			origin: OriginFor<T>,
 			course_id: T::Hash,
 			bid_price: BalanceOf<T>,
-		) -> DispatchResult {
+			allow_death: bool,
+		) -> DispatchResultWithPostInfo {
 			let buyer = ensure_signed(origin)?;
+			Self::ensure_callable()?;
 
 			// Check the course exists and buyer is not the current course owner
 			let course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
 			ensure!(course.owner != buyer, <Error<T>>::BuyerIsCourseOwner);
+			ensure!(T::BuyerFilter::contains(&buyer), <Error<T>>::NotEligibleBuyer);
+			Self::ensure_not_banned(&buyer)?;
+			Self::ensure_not_banned(&course.owner)?;
 
 			// Check the course is for sale and the course ask price <= bid_price
-			if let Some(ask_price) = course.price {
+			let price = if let Some(ask_price) = Self::dutch_price(&course_id) {
 				ensure!(ask_price <= bid_price, <Error<T>>::CourseBidPriceTooLow);
+				ask_price
+			} else if let Some(ask_price) = course.price {
+				ensure!(ask_price <= bid_price, <Error<T>>::CourseBidPriceTooLow);
+				bid_price
 			} else {
-				Err(<Error<T>>::CourseNotForSale)?;
-			}
-
-			// Check the buyer has enough free balance
-			ensure!(T::Currency::free_balance(&buyer) >= bid_price, <Error<T>>::NotEnoughBalance);
+				return Err(<Error<T>>::CourseNotForSale.into())
+			};
 
-			// Verify the buyer has the capacity to receive one more course
-			let to_owned = <CoursesOwned<T>>::get(&buyer);
+			// Check the buyer has enough free balance for the price and the transfer fee
+			let fee = Self::transfer_fee(&course.course_year).map_or_else(Zero::zero, |f| f.amount);
 			ensure!(
-				(to_owned.len() as u32) < T::MaxCoursesOwned::get(),
-				<Error<T>>::ExceedMaxCourseOwned
+				T::Currency::free_balance(&buyer) >= price.saturating_add(fee),
+				<Error<T>>::NotEnoughBalance
 			);
 
+			// Verify the buyer has the capacity to receive one more course
+			let owned_buyer = Self::owned_count(&buyer);
+			ensure!(owned_buyer < Self::owned_limit(), <Error<T>>::ExceedMaxCourseOwned);
+
 			let seller = course.owner.clone();
+			let owned_seller = Self::owned_count(&seller);
 
 			// Transfer the amount from buyer to seller
-			T::Currency::transfer(&buyer, &seller, bid_price, ExistenceRequirement::KeepAlive)?;
+			let existence = if allow_death {
+				ExistenceRequirement::AllowDeath
+			} else {
+				ExistenceRequirement::KeepAlive
+			};
+			// The fee goes first, so `allow_death` can spend what is left on the price
+			Self::charge_transfer_fee(&buyer, &buyer, &course_id)?;
+			T::Currency::transfer(&buyer, &seller, price, existence)?;
+			Self::vest_proceeds(&seller, price)?;
+			T::OnPurchase::on_purchase(&buyer, &seller, price);
 
 			// Transfer the course from seller to buyer
 			Self::transfer_course_to(&course_id, &buyer)?;
+			Self::record_history(&course_id, Some(&seller), &buyer, Some(price));
 
-			Self::deposit_event(Event::Bought(buyer, seller, course_id, bid_price));
+			Self::note_sale(&course.course_year, price);
+			Self::note_trade(&course_id, &seller, &buyer, price);
 
-			Ok(())
+			Self::deposit_course_event(
+				Event::Bought(buyer.clone(), seller, course_id, price),
+				&buyer,
+				&course_id,
+			);
+			Self::trigger_watches(&course_id, price, None);
+
+			Ok(Some(T::WeightInfo::buy_course(owned_seller, owned_buyer)).into())
 		}
 
 		/// Breed a Course.
 		///
 		/// Breed two courses to create a new generation
 		/// of Courses.
-		#[pallet::weight(100)]
+		#[pallet::weight(T::WeightInfo::breed_course())]
 		pub fn breed_course(
 			origin: OriginFor<T>,
 			parent1: T::Hash,
 			parent2: T::Hash,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			Self::do_breed(&sender, &parent1, &parent2)?;
+
+			Ok(())
+		}
+
+		/// Approve another account to transfer a Course on the owner's behalf.
+		///
+		/// Passing `None` clears the approval. The approval is also cleared whenever the course
+		/// changes hands.
+		#[pallet::weight(T::WeightInfo::approve())]
+		pub fn approve(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			delegate: Option<T::AccountId>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			// Ensure the course exists and is called by the course owner
+			ensure!(Self::is_course_owner(&course_id, &owner)?, <Error<T>>::NotCourseOwner);
+
+			match &delegate {
+				Some(delegate) => <CourseApprovals<T>>::insert(&course_id, delegate),
+				None => <CourseApprovals<T>>::remove(&course_id),
+			}
+
+			Self::deposit_course_event(
+				Event::Approved(owner.clone(), course_id, delegate),
+				&owner,
+				&course_id,
+			);
+
+			Ok(())
+		}
+
+		/// Set the IPFS identifiers of a Course's syllabus and materials.
+		///
+		/// Same as `update_metadata()`, kept for existing clients.
+		#[pallet::weight(T::WeightInfo::update_metadata())]
+		pub fn set_content(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			syllabus: Option<Cid<T>>,
+			materials: Option<Cid<T>>,
+		) -> DispatchResult {
+			Self::update_metadata(origin, course_id, syllabus, materials)
+		}
+
+		/// List a Course at a price starting at `start_price` and dropping by `decay` every block
+		/// until it reaches `floor_price`.
+		///
+		/// The listing replaces the fixed price of the course, and `set_price()` replaces the
+		/// listing.
+		#[pallet::weight(T::WeightInfo::list_dutch())]
+		pub fn list_dutch(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			start_price: BalanceOf<T>,
+			floor_price: BalanceOf<T>,
+			decay: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			// Ensure the course exists and is called by the course owner
+			ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+			Self::ensure_not_sealed(&course_id)?;
+			ensure!(floor_price <= start_price, <Error<T>>::FloorAboveStart);
+			Self::ensure_not_banned(&sender)?;
+			Self::ensure_cooled_down(&course_id)?;
+
+			<Courses<T>>::mutate(&course_id, |course| {
+				if let Some(course) = course {
+					Self::note_price_change(course.price.take(), None);
+				}
+			});
+			let start = <frame_system::Pallet<T>>::block_number();
+			<DutchListings<T>>::insert(
+				&course_id,
+				DutchListing { start_price, floor_price, decay, start },
+			);
+
+			Self::deposit_course_event(
+				Event::DutchListed(sender.clone(), course_id, start_price, floor_price, decay),
+				&sender,
+				&course_id,
+			);
+
+			Ok(())
+		}
+
+		/// List several Courses for sale together, e.g. a full year of a curriculum.
+		///
+		/// The Courses stay with the seller, who can still sell or transfer them individually,
+		/// which makes the bundle unavailable.
+		#[pallet::weight(T::WeightInfo::create_bundle(course_ids.len() as u32))]
+		pub fn create_bundle(
+			origin: OriginFor<T>,
+			course_ids: BoundedVec<T::Hash, T::MaxBundleSize>,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			let seller = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			ensure!(!course_ids.is_empty(), <Error<T>>::InvalidBundle);
+			Self::ensure_not_banned(&seller)?;
+			for (i, course_id) in course_ids.iter().enumerate() {
+				ensure!(Self::is_course_owner(course_id, &seller)?, <Error<T>>::NotCourseOwner);
+				ensure!(!course_ids[..i].contains(course_id), <Error<T>>::InvalidBundle);
+				Self::ensure_not_sealed(course_id)?;
+				Self::ensure_cooled_down(course_id)?;
+			}
+
+			let bundle_id = Self::next_bundle_id();
+			let next_id = bundle_id.checked_add(1).ok_or(<Error<T>>::BundleIdOverflow)?;
+			<Bundles<T>>::insert(
+				bundle_id,
+				Bundle { seller: seller.clone(), courses: course_ids, price },
+			);
+			<NextBundleId<T>>::put(next_id);
+
+			Self::deposit_account_event(
+				Event::BundleCreated(seller.clone(), bundle_id, price),
+				&seller,
+			);
+
+			Ok(())
+		}
+
+		/// Withdraw a bundle listing.
+		#[pallet::weight(T::WeightInfo::cancel_bundle())]
+		pub fn cancel_bundle(origin: OriginFor<T>, bundle_id: BundleId) -> DispatchResult {
+			let seller = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			let bundle = Self::bundles(bundle_id).ok_or(<Error<T>>::BundleNotExist)?;
+			ensure!(bundle.seller == seller, <Error<T>>::NotBundleSeller);
+			<Bundles<T>>::remove(bundle_id);
+
+			Self::deposit_account_event(Event::BundleCancelled(seller.clone(), bundle_id), &seller);
 
-			// Check: Verify `sender` owns both courses (and both courses exist).
-			ensure!(Self::is_course_owner(&parent1, &sender)?, <Error<T>>::NotCourseOwner);
-			ensure!(Self::is_course_owner(&parent2, &sender)?, <Error<T>>::NotCourseOwner);
+			Ok(())
+		}
+
+		/// Buy every Course of a bundle at once. The bid price has to be equal or higher than the
+		/// bundle price, and is paid to the seller.
+		///
+		/// Either all the Courses and the funds change hands, or nothing does. Payments of at
+		/// least `VestingThreshold` vest to the seller over `ProceedsVestingPeriod`.
+		///
+		/// Each Course counts as a sale for an equal share of the price in the market statistics
+		/// and the recent trades. The buyer pays the transfer fee, if any, of each Course on top
+		/// of the price.
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::buy_bundle(T::MaxBundleSize::get()))]
+		pub fn buy_bundle(
+			origin: OriginFor<T>,
+			bundle_id: BundleId,
+			bid_price: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let buyer = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			let bundle = Self::bundles(bundle_id).ok_or(<Error<T>>::BundleNotExist)?;
+			let seller = bundle.seller;
+			ensure!(seller != buyer, <Error<T>>::BuyerIsCourseOwner);
+			ensure!(T::BuyerFilter::contains(&buyer), <Error<T>>::NotEligibleBuyer);
+			Self::ensure_not_banned(&buyer)?;
+			Self::ensure_not_banned(&seller)?;
+			ensure!(bundle.price <= bid_price, <Error<T>>::CourseBidPriceTooLow);
+			ensure!(T::Currency::free_balance(&buyer) >= bid_price, <Error<T>>::NotEnoughBalance);
+
+			// Verify the buyer has the capacity to receive every course of the bundle
+			ensure!(
+				Self::owned_count(&buyer).saturating_add(bundle.courses.len() as u32) <=
+					Self::owned_limit(),
+				<Error<T>>::ExceedMaxCourseOwned
+			);
+
+			T::Currency::transfer(&buyer, &seller, bid_price, ExistenceRequirement::KeepAlive)?;
+			Self::vest_proceeds(&seller, bid_price)?;
+			T::OnPurchase::on_purchase(&buyer, &seller, bid_price);
+
+			// Each Course is counted as sold for its share of the price, the first one taking what
+			// does not divide evenly.
+			let count: BalanceOf<T> = (bundle.courses.len() as u32).unique_saturated_into();
+			let share = bid_price / count;
+			let mut price =
+				bid_price.saturating_sub(share.saturating_mul(count)).saturating_add(share);
+			for course_id in &bundle.courses {
+				let course = Self::courses(course_id).ok_or(<Error<T>>::CourseNotExist)?;
+				ensure!(course.owner == seller, <Error<T>>::BundleUnavailable);
+				Self::transfer_course_to(course_id, &buyer)?;
+				Self::charge_transfer_fee(&buyer, &buyer, course_id)?;
+				Self::record_history(course_id, Some(&seller), &buyer, Some(price));
+				Self::note_sale(&course.course_year, price);
+				Self::note_trade(course_id, &seller, &buyer, price);
+				Self::deposit_course_event(
+					Event::Transferred(seller.clone(), buyer.clone(), *course_id),
+					&buyer,
+					course_id,
+				);
+				price = share;
+			}
+			<Bundles<T>>::remove(bundle_id);
+
+			Self::deposit_account_event(
+				Event::BundleBought(buyer.clone(), seller, bundle_id, bid_price),
+				&buyer,
+			);
+
+			Ok(Some(T::WeightInfo::buy_bundle(bundle.courses.len() as u32)).into())
+		}
+
+		/// Propose to exchange `my_course` for `their_course`, owned by `counterparty`, optionally
+		/// paying `sweetener` on top.
+		///
+		/// Nothing changes hands until the counterparty accepts with `accept_swap()`.
+		#[pallet::weight(T::WeightInfo::propose_swap())]
+		pub fn propose_swap(
+			origin: OriginFor<T>,
+			my_course: T::Hash,
+			their_course: T::Hash,
+			counterparty: T::AccountId,
+			sweetener: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let proposer = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			ensure!(proposer != counterparty, <Error<T>>::TransferToSelf);
+			ensure!(Self::is_course_owner(&my_course, &proposer)?, <Error<T>>::NotCourseOwner);
+			Self::ensure_not_sealed(&my_course)?;
+			Self::ensure_not_banned(&proposer)?;
+			Self::ensure_cooled_down(&my_course)?;
+			ensure!(
+				Self::is_course_owner(&their_course, &counterparty)?,
+				<Error<T>>::NotCourseOwner
+			);
+
+			let swap_id = Self::next_swap_id();
+			let next_id = swap_id.checked_add(1).ok_or(<Error<T>>::SwapIdOverflow)?;
+			<Swaps<T>>::insert(
+				swap_id,
+				Swap {
+					proposer: proposer.clone(),
+					counterparty: counterparty.clone(),
+					offered: my_course,
+					requested: their_course,
+					sweetener,
+				},
+			);
+			<NextSwapId<T>>::put(next_id);
+
+			Self::deposit_account_event(
+				Event::SwapProposed(proposer.clone(), counterparty, swap_id),
+				&proposer,
+			);
+
+			Ok(())
+		}
+
+		/// Withdraw a swap proposal.
+		#[pallet::weight(T::WeightInfo::cancel_swap())]
+		pub fn cancel_swap(origin: OriginFor<T>, swap_id: SwapId) -> DispatchResult {
+			let proposer = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			let swap = Self::swaps(swap_id).ok_or(<Error<T>>::SwapNotExist)?;
+			ensure!(swap.proposer == proposer, <Error<T>>::NotSwapParty);
+			<Swaps<T>>::remove(swap_id);
+
+			Self::deposit_account_event(Event::SwapCancelled(proposer.clone(), swap_id), &proposer);
+
+			Ok(())
+		}
+
+		/// Accept a swap proposed to the sender, exchanging both Courses and paying the sweetener
+		/// in a single transaction.
+		///
+		/// Each party pays the transfer fee, if any, of the Course it gives away.
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::accept_swap())]
+		pub fn accept_swap(origin: OriginFor<T>, swap_id: SwapId) -> DispatchResult {
+			let counterparty = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			let swap = Self::swaps(swap_id).ok_or(<Error<T>>::SwapNotExist)?;
+			ensure!(swap.counterparty == counterparty, <Error<T>>::NotSwapParty);
+			let proposer = swap.proposer;
+			Self::ensure_not_banned(&counterparty)?;
+			Self::ensure_not_banned(&proposer)?;
+			ensure!(Self::is_course_owner(&swap.offered, &proposer)?, <Error<T>>::SwapUnavailable);
+			ensure!(
+				Self::is_course_owner(&swap.requested, &counterparty)?,
+				<Error<T>>::SwapUnavailable
+			);
+
+			if let Some(sweetener) = swap.sweetener {
+				T::Currency::transfer(
+					&proposer,
+					&counterparty,
+					sweetener,
+					ExistenceRequirement::KeepAlive,
+				)?;
+			}
+
+			for (course_id, from, to) in [
+				(swap.offered, &proposer, &counterparty),
+				(swap.requested, &counterparty, &proposer),
+			] {
+				Self::transfer_course_to(&course_id, to)?;
+				Self::charge_transfer_fee(from, to, &course_id)?;
+				Self::record_history(&course_id, Some(from), to, None);
+				Self::deposit_course_event(
+					Event::Transferred(from.clone(), to.clone(), course_id),
+					to,
+					&course_id,
+				);
+			}
+			<Swaps<T>>::remove(swap_id);
+
+			Self::deposit_account_event(
+				Event::SwapAccepted(counterparty.clone(), proposer, swap_id),
+				&counterparty,
+			);
+
+			Ok(())
+		}
+
+		/// Offer a Course to another account, which has `OfferExpiry` blocks to claim it with
+		/// `claim_transfer()`.
+		///
+		/// A new offer of the same Course replaces the previous one.
+		#[pallet::weight(T::WeightInfo::offer_transfer())]
+		pub fn offer_transfer(
+			origin: OriginFor<T>,
+			to: T::AccountId,
+			course_id: T::Hash,
+		) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			ensure!(Self::is_course_owner(&course_id, &from)?, <Error<T>>::NotCourseOwner);
+			ensure!(from != to, <Error<T>>::TransferToSelf);
+			Self::ensure_not_banned(&from)?;
+			Self::ensure_cooled_down(&course_id)?;
+
+			let expires =
+				<frame_system::Pallet<T>>::block_number().saturating_add(T::OfferExpiry::get());
+			<TransferOffers<T>>::insert(&course_id, TransferOffer { to: to.clone(), expires });
+
+			Self::deposit_course_event(
+				Event::TransferOffered(from.clone(), to, course_id, expires),
+				&from,
+				&course_id,
+			);
+
+			Ok(())
+		}
+
+		/// Claim a Course offered to the sender.
+		///
+		/// The transfer fee of the Course, if any, is paid by the account that offered it.
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::claim_transfer())]
+		pub fn claim_transfer(origin: OriginFor<T>, course_id: T::Hash) -> DispatchResult {
+			let to = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			let offer = Self::transfer_offers(&course_id)
+				.filter(|offer| offer.to == to)
+				.ok_or(<Error<T>>::NoTransferOffer)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() <= offer.expires,
+				<Error<T>>::TransferOfferExpired
+			);
+			let course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+
+			// Verify the recipient has the capacity to receive one more course
+			ensure!(Self::owned_count(&to) < Self::owned_limit(), <Error<T>>::ExceedMaxCourseOwned);
+
+			Self::transfer_course_to(&course_id, &to)?;
+			Self::charge_transfer_fee(&course.owner, &to, &course_id)?;
+			Self::record_history(&course_id, Some(&course.owner), &to, None);
+
+			Self::deposit_course_event(
+				Event::Transferred(course.owner, to.clone(), course_id),
+				&to,
+				&course_id,
+			);
+
+			Ok(())
+		}
+
+		/// Reject a Course offered to the sender, or withdraw an offer of a Course the sender
+		/// owns.
+		#[pallet::weight(T::WeightInfo::reject_transfer())]
+		pub fn reject_transfer(origin: OriginFor<T>, course_id: T::Hash) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			let offer = Self::transfer_offers(&course_id).ok_or(<Error<T>>::NoTransferOffer)?;
+			ensure!(
+				offer.to == sender || Self::is_course_owner(&course_id, &sender)?,
+				<Error<T>>::NoTransferOffer
+			);
+			<TransferOffers<T>>::remove(&course_id);
+
+			Self::deposit_course_event(
+				Event::TransferRejected(sender.clone(), course_id),
+				&sender,
+				&course_id,
+			);
+
+			Ok(())
+		}
+
+		/// Set whether the sender accepts Courses sent with `transfer()`, rather than only
+		/// through offers it claims.
+		#[pallet::weight(T::WeightInfo::set_accepts_transfers())]
+		pub fn set_accepts_transfers(origin: OriginFor<T>, accept: bool) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			if accept {
+				<AcceptsDirectTransfers<T>>::insert(&sender, true);
+			} else {
+				<AcceptsDirectTransfers<T>>::remove(&sender);
+			}
+
+			Ok(())
+		}
+
+		/// Exempt the Courses of `course_year` from the transfer cooldown, or make them subject to
+		/// it again.
+		///
+		/// The dispatch origin for this call must be `ForceOrigin`.
+		#[pallet::weight((T::WeightInfo::set_cooldown_exempt(), DispatchClass::Operational))]
+		pub fn set_cooldown_exempt(
+			origin: OriginFor<T>,
+			course_year: CourseYear,
+			exempt: bool,
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			if exempt {
+				<CooldownExempt<T>>::insert(&course_year, true);
+			} else {
+				<CooldownExempt<T>>::remove(&course_year);
+			}
+
+			Self::deposit_unindexed_event(Event::CooldownExemptionSet(course_year, exempt));
+
+			Ok(Pays::No.into())
+		}
+
+		/// Exclude `who` from buying, listing and offering Courses. The Courses it owns stay
+		/// its own.
+		///
+		/// The dispatch origin for this call must be `ForceOrigin`.
+		#[pallet::weight((T::WeightInfo::ban_account(), DispatchClass::Operational))]
+		pub fn ban_account(origin: OriginFor<T>, who: T::AccountId) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			<BannedAccounts<T>>::insert(&who, ());
+
+			Self::deposit_account_event(Event::AccountBanned(who.clone()), &who);
+
+			Ok(Pays::No.into())
+		}
+
+		/// Allow a banned account back in the marketplace.
+		///
+		/// The dispatch origin for this call must be `ForceOrigin`.
+		#[pallet::weight((T::WeightInfo::unban_account(), DispatchClass::Operational))]
+		pub fn unban_account(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			<BannedAccounts<T>>::remove(&who);
+
+			Self::deposit_account_event(Event::AccountUnbanned(who.clone()), &who);
+
+			Ok(Pays::No.into())
+		}
+
+		/// Clone a Course for `new_term`, keeping its DNA, year and content, but not its blobs.
+		///
+		/// The clone is a new Course owned by the sender, recording the Course it was cloned from.
+		/// A Course can be cloned once per term.
+		#[pallet::weight(T::WeightInfo::clone_course())]
+		pub fn clone_course(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			new_term: Term,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			let source = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+			ensure!(source.owner == sender, <Error<T>>::NotCourseOwner);
+			Self::ensure_can_mint(&sender)?;
+			let content = Self::course_content(&course_id);
+			let content_len = content.as_ref().map_or(0, |content| content.encoded_size());
+			ensure!(
+				T::Currency::can_reserve(
+					&sender,
+					T::CourseDeposit::get().saturating_add(Self::content_deposit(content_len))
+				),
+				<Error<T>>::NotEnoughBalance
+			);
+
+			let clone = Course::<T> {
+				dna: source.dna,
+				price: None,
+				course_year: source.course_year,
+				owner: sender.clone(),
+				blobs_root: None,
+			};
+			// The clone has the same fields as the source, so derive its id from the term instead.
+			let clone_id = T::Hashing::hash_of(&(course_id, new_term));
+			Self::insert_course(clone_id, clone)?;
+			Self::note_mint(&sender);
+			Self::reserve_deposit(&clone_id, &sender)?;
+			<CourseLineage<T>>::insert(
+				&clone_id,
+				Lineage { cloned_from: course_id, term: new_term },
+			);
+			if let Some(content) = content {
+				Self::hold_content_deposit(&clone_id, &sender, content_len)?;
+				Self::log_metadata_update(&clone_id);
+				<CourseContents<T>>::insert(&clone_id, content);
+			}
+			Self::record_history(&clone_id, None, &sender, None);
+
+			Self::deposit_course_event(
+				Event::Cloned(sender.clone(), course_id, clone_id, new_term),
+				&sender,
+				&clone_id,
+			);
+
+			Ok(())
+		}
+
+		/// Limit the Courses of `course_year` a single account can own to `limit`, or to
+		/// `MaxCoursesPerYear` if `None`.
+		///
+		/// Accounts already owning more keep their Courses but cannot receive further ones.
+		///
+		/// The dispatch origin for this call must be `ForceOrigin`.
+		#[pallet::weight((T::WeightInfo::set_year_limit(), DispatchClass::Operational))]
+		pub fn set_year_limit(
+			origin: OriginFor<T>,
+			course_year: CourseYear,
+			limit: Option<u32>,
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			match limit {
+				Some(limit) => <YearLimits<T>>::insert(&course_year, limit),
+				None => <YearLimits<T>>::remove(&course_year),
+			}
+
+			Self::deposit_unindexed_event(Event::YearLimitSet(course_year, limit));
+
+			Ok(Pays::No.into())
+		}
+
+		/// Set the IPFS identifiers of a Course's syllabus and materials, bumping the version of
+		/// its content.
+		///
+		/// Passing `None` removes the reference. Newly referenced CIDs are pinned by the off-chain
+		/// workers of nodes that configured an IPFS API endpoint. The hash of the replaced content
+		/// is kept, so auditors can prove what the Course said at a given block.
+		///
+		/// `DepositPerByte` is reserved for every byte of the encoded content and of the blobs,
+		/// and returned when the content shrinks or is removed.
+		#[pallet::weight(T::WeightInfo::update_metadata())]
+		pub fn update_metadata(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			syllabus: Option<Cid<T>>,
+			materials: Option<Cid<T>>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			// Ensure the course exists and is called by the course owner
+			ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+
+			for cid in syllabus.iter().chain(materials.iter()) {
+				ensure!(crate::ipfs::is_valid_cid(cid), <Error<T>>::InvalidCid);
+			}
+			let content = CourseContent { syllabus, materials };
+			let len = if content.syllabus.is_none() && content.materials.is_none() {
+				0
+			} else {
+				content.encoded_size()
+			};
+			Self::hold_content_deposit(&course_id, &sender, len + Self::blobs_len(&course_id))?;
+
+			for cid in content.syllabus.iter().chain(content.materials.iter()) {
+				// Pinning is best effort, the content stays referenced even if the queue is full.
+				if <PendingPins<T>>::try_mutate(|pins| pins.try_push(cid.clone())).is_err() {
+					log::warn!("Too many CIDs to pin in this block, skipping {:?}.", cid);
+				}
+			}
+
+			Self::log_metadata_update(&course_id);
+			if len == 0 {
+				<CourseContents<T>>::remove(&course_id);
+			} else {
+				<CourseContents<T>>::insert(&course_id, content);
+			}
+
+			Self::deposit_course_event(
+				Event::ContentSet(sender.clone(), course_id),
+				&sender,
+				&course_id,
+			);
+
+			Ok(())
+		}
+
+		/// Pause or resume every call of the pallet but those of governance, e.g. to halt trading
+		/// during an exploit investigation.
+		///
+		/// The dispatch origin for this call must be `PauseOrigin`.
+		#[pallet::weight((T::WeightInfo::set_paused(), DispatchClass::Operational))]
+		pub fn set_paused(origin: OriginFor<T>, paused: bool) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			if paused {
+				<Paused<T>>::put(true);
+			} else {
+				<Paused<T>>::kill();
+			}
+
+			Self::deposit_unindexed_event(Event::PauseSet(paused));
+
+			Ok(Pays::No.into())
+		}
+
+		/// Burn a Course of the sender.
+		///
+		/// The deposit reserved when the Course was minted is returned to its minter in full
+		/// within a term, and only partly afterwards. The deposit of its content is returned in
+		/// full.
+		#[pallet::weight(T::WeightInfo::burn_course())]
+		pub fn burn_course(origin: OriginFor<T>, course_id: T::Hash) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			let course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+			ensure!(course.owner == sender, <Error<T>>::NotCourseOwner);
+			Self::ensure_not_sealed(&course_id)?;
+
+			Self::remove_course(&course_id, &course);
+			if course.blobs_root.is_some() {
+				let _ = child::kill_storage(&blobs::child_info(&course_id), None);
+			}
+			<YearStatistics<T>>::mutate(&course.course_year, |stats| {
+				stats.burned = stats.burned.saturating_add(1)
+			});
+
+			let refund = Self::refund_deposit(&course_id);
+			if let Some((depositor, amount)) = <ContentDeposits<T>>::take(&course_id) {
+				T::Currency::unreserve(&depositor, amount);
+			}
+			T::OnTransfer::on_burn(&course_id, &sender);
+
+			Self::deposit_account_event(Event::Burned(sender.clone(), course_id, refund), &sender);
+
+			Ok(())
+		}
+
+		/// Take a snapshot of the Courses of `course_year` every account owns at this block.
+		///
+		/// The snapshot is taken over the next blocks' idle time and stored in `OwnerSnapshots`
+		/// once complete.
+		///
+		/// The dispatch origin for this call must be `ForceOrigin`.
+		#[pallet::weight((T::WeightInfo::snapshot_owners(), DispatchClass::Operational))]
+		pub fn snapshot_owners(
+			origin: OriginFor<T>,
+			course_year: CourseYear,
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+			ensure!(!<MigrationCursor<T>>::exists(), <Error<T>>::MigrationOngoing);
+			ensure!(!<PendingSnapshot<T>>::exists(), <Error<T>>::SnapshotPending);
+
+			let id = Self::next_snapshot_id();
+			let next_id = id.checked_add(1).ok_or(<Error<T>>::SnapshotIdOverflow)?;
+			let started = <frame_system::Pallet<T>>::block_number();
+
+			<PendingSnapshot<T>>::put(SnapshotProgress {
+				id,
+				course_year: course_year.clone(),
+				started,
+				cursor: Default::default(),
+				frontier: Default::default(),
+				holders: 0,
+			});
+			<NextSnapshotId<T>>::put(next_id);
+
+			Self::deposit_unindexed_event(Event::SnapshotStarted(id, course_year, started));
+
+			Ok(Pays::No.into())
+		}
+
+		/// Tip the creator of a Course `amount`, whoever owns the Course now.
+		#[pallet::weight(T::WeightInfo::tip_creator())]
+		pub fn tip_creator(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let tipper = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+			ensure!(!amount.is_zero(), <Error<T>>::ZeroTip);
+
+			let course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+			let creator = Self::creator(&course_id).ok_or(<Error<T>>::CreatorUnknown)?;
+			ensure!(tipper != creator, <Error<T>>::TipToSelf);
+
+			T::Currency::transfer(&tipper, &creator, amount, ExistenceRequirement::KeepAlive)?;
+			<Tips<T>>::mutate(&course_id, |tips| *tips = tips.saturating_add(amount));
+
+			Self::deposit_course_event(
+				Event::Tipped(tipper, creator, course_id, amount),
+				&course.owner,
+				&course_id,
+			);
+
+			Ok(())
+		}
+
+		/// Set the stablecoin price of a Course, `None` withdrawing it from sale in the
+		/// stablecoin.
+		///
+		/// The stablecoin price is independent of the price set with `set_price`, and is cleared
+		/// like it when the Course changes hands.
+		#[pallet::weight(T::WeightInfo::set_stable_price())]
+		pub fn set_stable_price(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			new_price: Option<StableBalanceOf<T>>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			ensure!(Self::is_course_owner(&course_id, &sender)?, <Error<T>>::NotCourseOwner);
+			if new_price.is_some() {
+				Self::ensure_not_banned(&sender)?;
+				Self::ensure_cooled_down(&course_id)?;
+				Self::ensure_not_sealed(&course_id)?;
+			}
+
+			<StablePrices<T>>::set(&course_id, new_price);
+
+			Self::deposit_course_event(
+				Event::StablePriceSet(sender.clone(), course_id, new_price),
+				&sender,
+				&course_id,
+			);
+
+			Ok(())
+		}
+
+		/// Buy a Course for sale in the stablecoin, paying `bid_price` if it is at least the
+		/// stablecoin price of the Course.
+		///
+		/// The payment always keeps the buyer's stablecoin account alive. Sales in the stablecoin
+		/// do not vest, and are not counted in the market statistics nor reported to
+		/// `OnPurchase`, which are in `Currency`. The transfer fee of the Course, if any, is
+		/// still paid in `Currency`.
+		///
+		/// Charged for the courses the seller and the buyer own, up to `MaxCoursesOwned` each.
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::buy_course_in_stable(
+			T::MaxCoursesOwned::get(),
+			T::MaxCoursesOwned::get(),
+		))]
+		pub fn buy_course_in_stable(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			bid_price: StableBalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let buyer = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			let course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+			ensure!(course.owner != buyer, <Error<T>>::BuyerIsCourseOwner);
+			ensure!(T::BuyerFilter::contains(&buyer), <Error<T>>::NotEligibleBuyer);
+			Self::ensure_not_banned(&buyer)?;
+			Self::ensure_not_banned(&course.owner)?;
+
+			let ask_price = Self::stable_prices(&course_id).ok_or(<Error<T>>::CourseNotForSale)?;
+			ensure!(ask_price <= bid_price, <Error<T>>::CourseBidPriceTooLow);
+			ensure!(
+				<T::StableCurrency as fungible::Inspect<_>>::reducible_balance(&buyer, true) >=
+					bid_price,
+				<Error<T>>::NotEnoughBalance
+			);
+
+			let owned_buyer = Self::owned_count(&buyer);
+			ensure!(owned_buyer < Self::owned_limit(), <Error<T>>::ExceedMaxCourseOwned);
+
+			let seller = course.owner.clone();
+			let owned_seller = Self::owned_count(&seller);
+
+			<T::StableCurrency as fungible::Transfer<_>>::transfer(
+				&buyer, &seller, bid_price, true,
+			)?;
+
+			Self::transfer_course_to(&course_id, &buyer)?;
+			Self::charge_transfer_fee(&buyer, &buyer, &course_id)?;
+			Self::record_history(&course_id, Some(&seller), &buyer, None);
+
+			Self::deposit_course_event(
+				Event::BoughtInStable(buyer.clone(), seller, course_id, bid_price),
+				&buyer,
+				&course_id,
+			);
+
+			Ok(Some(T::WeightInfo::buy_course_in_stable(owned_seller, owned_buyer)).into())
+		}
+
+		/// Charge `fee` on every change of owner of the Courses of `course_year`, by transfer,
+		/// claim, swap or sale, paid by the sender or the buyer to the department owning the
+		/// collection, or stop charging a fee if `None`.
+		///
+		/// The dispatch origin for this call must be `ForceOrigin`.
+		#[pallet::weight((T::WeightInfo::set_transfer_fee(), DispatchClass::Operational))]
+		pub fn set_transfer_fee(
+			origin: OriginFor<T>,
+			course_year: CourseYear,
+			fee: Option<TransferFeeOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			match &fee {
+				Some(fee) => <TransferFees<T>>::insert(&course_year, fee),
+				None => <TransferFees<T>>::remove(&course_year),
+			}
+
+			Self::deposit_unindexed_event(Event::TransferFeeSet(course_year, fee));
+
+			Ok(Pays::No.into())
+		}
+
+		/// List a Course for sealed bids of at least `floor_price`, so bots watching the
+		/// transaction pool cannot outbid buyers at the last moment.
+		///
+		/// Buyers commit to their bids with `commit_purchase()` for `SealedCommitPeriod` blocks,
+		/// then reveal them with `reveal_purchase()` for `SealedRevealPeriod` blocks. The listing
+		/// replaces the fixed price and declining-price listing of the Course, which stays with
+		/// the seller until `settle_sealed()`. Until then the seller can neither list, bundle,
+		/// swap, transfer nor burn the Course.
+		#[pallet::weight(T::WeightInfo::list_sealed())]
+		pub fn list_sealed(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			floor_price: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_list_sealed(&sender, &course_id, floor_price, false)
+		}
+
+		/// Commit to a sealed bid on a Course, reserving `reserve`, the most the bid can be.
+		///
+		/// `commitment` is `T::Hashing` of the SCALE-encoded course ID, the sender's account, the
+		/// bid and a secret salt. Binding it to the sender keeps other accounts from copying the
+		/// commitment and revealing the same bid. The amount reserved is public, so it can exceed
+		/// the bid to hide it.
+		#[pallet::weight(T::WeightInfo::commit_purchase())]
+		pub fn commit_purchase(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			commitment: T::Hash,
+			reserve: BalanceOf<T>,
+		) -> DispatchResult {
+			let buyer = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			let listing = Self::sealed_listings(&course_id).ok_or(<Error<T>>::NoSealedListing)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now <= listing.commit_end, <Error<T>>::NotCommitPhase);
+			ensure!(listing.seller != buyer, <Error<T>>::BuyerIsCourseOwner);
+			ensure!(T::BuyerFilter::contains(&buyer), <Error<T>>::NotEligibleBuyer);
+			Self::ensure_not_banned(&buyer)?;
+			ensure!(
+				!<SealedBids<T>>::contains_key(&course_id, &buyer),
+				<Error<T>>::SealedBidExists
+			);
+			ensure!(reserve >= listing.floor_price, <Error<T>>::CourseBidPriceTooLow);
+
+			T::Currency::reserve(&buyer, reserve)?;
+			<SealedBids<T>>::insert(
+				&course_id,
+				&buyer,
+				SealedBid {
+					commitment,
+					reserved: reserve,
+					commit_end: listing.commit_end,
+					second_price: listing.second_price,
+				},
+			);
+
+			Self::deposit_course_event(
+				Event::PurchaseCommitted(buyer, course_id),
+				&listing.seller,
+				&course_id,
+			);
+
+			Ok(())
+		}
+
+		/// Reveal a sealed bid on a Course.
+		///
+		/// The highest bid wins, the earliest revealed among equal ones. The reserve of the
+		/// leading bid is kept down to the bid, the reserves of the others are returned.
+		#[pallet::weight(T::WeightInfo::reveal_purchase())]
+		pub fn reveal_purchase(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			bid: BalanceOf<T>,
+			salt: T::Hash,
+		) -> DispatchResult {
+			let buyer = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			let mut listing =
+				Self::sealed_listings(&course_id).ok_or(<Error<T>>::NoSealedListing)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				listing.commit_end < now && now <= listing.reveal_end,
+				<Error<T>>::NotRevealPhase
+			);
+			let sealed = Self::sealed_bids(&course_id, &buyer).ok_or(<Error<T>>::NoSealedBid)?;
+			ensure!(sealed.commit_end == listing.commit_end, <Error<T>>::NoSealedBid);
+			ensure!(
+				T::Hashing::hash_of(&(course_id, &buyer, bid, salt)) == sealed.commitment &&
+					bid <= sealed.reserved &&
+					bid >= listing.floor_price,
+				<Error<T>>::InvalidSealedReveal
+			);
+
+			<SealedBids<T>>::remove(&course_id, &buyer);
+			if listing.best.as_ref().map_or(true, |(_, best)| bid > *best) {
+				T::Currency::unreserve(&buyer, sealed.reserved.saturating_sub(bid));
+				if let Some((outbid, amount)) = listing.best.replace((buyer.clone(), bid)) {
+					T::Currency::unreserve(&outbid, amount);
+					listing.second = listing.second.max(amount);
+				}
+			} else {
+				T::Currency::unreserve(&buyer, sealed.reserved);
+				listing.second = listing.second.max(bid);
+			}
+			<SealedListings<T>>::insert(&course_id, &listing);
+
+			Self::deposit_course_event(
+				Event::PurchaseRevealed(buyer, course_id, bid),
+				&listing.seller,
+				&course_id,
+			);
+
+			Ok(())
+		}
+
+		/// Settle a sealed-bid listing once its reveal period is over, selling the Course to the
+		/// highest bid, at the second-highest bid on second-price listings. Anyone can settle a
+		/// listing.
+		///
+		/// The winner pays the transfer fee of the Course's year on top of the price, out of its
+		/// free balance. If the sale cannot go through, for example because the winner cannot pay
+		/// the fee or cannot own one more Course, the listing closes without a sale and the
+		/// winner's reserve is returned.
+		///
+		/// Charged for the courses the seller and the buyer own, up to `MaxCoursesOwned` each.
+		#[pallet::weight(T::WeightInfo::settle_sealed(
+			T::MaxCoursesOwned::get(),
+			T::MaxCoursesOwned::get(),
+		))]
+		pub fn settle_sealed(origin: OriginFor<T>, course_id: T::Hash) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			let listing = Self::sealed_listings(&course_id).ok_or(<Error<T>>::NoSealedListing)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now > listing.reveal_end, <Error<T>>::SealedListingOpen);
+
+			<SealedListings<T>>::remove(&course_id);
+			let sold = match &listing.best {
+				Some((buyer, bid)) => {
+					let price = if listing.second_price { listing.second.min(*bid) } else { *bid };
+					let sold = Self::sell_sealed(&course_id, &listing.seller, buyer, price).is_ok();
+					// The winner's reserve is the bid, of which only `price` was paid.
+					T::Currency::unreserve(
+						buyer,
+						if sold { bid.saturating_sub(price) } else { *bid },
+					);
+					sold
+				},
+				None => false,
+			};
+			if !sold {
+				Self::deposit_course_event(
+					Event::SealedListingClosed(listing.seller.clone(), course_id),
+					&listing.seller,
+					&course_id,
+				);
+			}
+
+			Ok(())
+		}
+
+		/// Withdraw a sealed bid that was not revealed, returning its reserve, once the reveal
+		/// period of its listing is over. Bids on second-price listings forfeit
+		/// `UnrevealedForfeit` of their reserve, unless calls were rejected after the commit
+		/// period, while the pallet was paused or its storage migrated, so the bid could not be
+		/// revealed.
+		#[pallet::weight(T::WeightInfo::withdraw_sealed_bid())]
+		pub fn withdraw_sealed_bid(origin: OriginFor<T>, course_id: T::Hash) -> DispatchResult {
+			let buyer = ensure_signed(origin)?;
+
+			let sealed = Self::sealed_bids(&course_id, &buyer).ok_or(<Error<T>>::NoSealedBid)?;
+			if let Some(listing) = Self::sealed_listings(&course_id) {
+				let now = <frame_system::Pallet<T>>::block_number();
+				ensure!(
+					listing.commit_end != sealed.commit_end || now > listing.reveal_end,
+					<Error<T>>::SealedListingOpen
+				);
+			}
+
+			<SealedBids<T>>::remove(&course_id, &buyer);
+			let blocked = Self::last_blocked().map_or(false, |block| block > sealed.commit_end);
+			let forfeited = if sealed.second_price && !blocked {
+				T::UnrevealedForfeit::get() * sealed.reserved
+			} else {
+				Zero::zero()
+			};
+			let _ = T::Currency::slash_reserved(&buyer, forfeited);
+			T::Currency::unreserve(&buyer, sealed.reserved.saturating_sub(forfeited));
+
+			Self::deposit_account_event(
+				Event::SealedBidWithdrawn(buyer.clone(), course_id, forfeited),
+				&buyer,
+			);
+
+			Ok(())
+		}
+
+		/// List a Course for sealed bids of at least `floor_price` like `list_sealed()`, the
+		/// winner paying the second-highest bid, or the floor price if it is the only bid.
+		///
+		/// As the winner pays what the others bid, the best strategy is to bid the most the
+		/// Course is worth to the bidder. Bids that are not revealed forfeit `UnrevealedForfeit`
+		/// of their reserve, so bidders cannot commit several bids and reveal the one that suits
+		/// them once they see the others. The Course is held for the listing like on
+		/// `list_sealed()`, so the seller cannot walk away from the bids either.
+		#[pallet::weight(T::WeightInfo::list_second_price())]
+		pub fn list_second_price(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			floor_price: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_list_sealed(&sender, &course_id, floor_price, true)
+		}
+
+		/// Watch a Course for a price, reserving `WatchDeposit`, or change the price watched.
+		///
+		/// `WatchTriggered` is deposited, indexed by the watcher, when the Course is listed at
+		/// `target` or less after being listed above it or not at all, and when it is sold for
+		/// `target` or less. The watch stays until `unwatch()`.
+		#[pallet::weight(T::WeightInfo::watch())]
+		pub fn watch(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			target: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+			ensure!(<Courses<T>>::contains_key(&course_id), <Error<T>>::CourseNotExist);
+
+			let mut watches = Self::watches(&course_id);
+			if let Some(watch) = watches.iter_mut().find(|watch| watch.watcher == sender) {
+				watch.target = target;
+			} else {
+				ensure!(
+					(watches.len() as u32) < T::MaxWatchers::get(),
+					<Error<T>>::TooManyWatchers
+				);
+				let deposit = T::WatchDeposit::get();
+				T::Currency::reserve(&sender, deposit)?;
+				watches
+					.try_push(Watch { watcher: sender.clone(), target, deposit })
+					.map_err(|_| <Error<T>>::TooManyWatchers)?;
+			}
+			<Watches<T>>::insert(&course_id, watches);
+
+			Self::deposit_account_event(Event::Watched(sender.clone(), course_id, target), &sender);
+			Ok(())
+		}
+
+		/// Stop watching a Course, returning the deposit. Watches of burned Courses are removed
+		/// the same way.
+		#[pallet::weight(T::WeightInfo::unwatch())]
+		pub fn unwatch(origin: OriginFor<T>, course_id: T::Hash) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let watch = <Watches<T>>::try_mutate_exists(&course_id, |maybe_watches| {
+				let watches = maybe_watches.as_mut().ok_or(<Error<T>>::NotWatching)?;
+				let index = watches
+					.iter()
+					.position(|watch| watch.watcher == sender)
+					.ok_or(<Error<T>>::NotWatching)?;
+				let watch = watches.remove(index);
+				if watches.is_empty() {
+					*maybe_watches = None;
+				}
+				Ok::<_, Error<T>>(watch)
+			})?;
+			T::Currency::unreserve(&sender, watch.deposit);
+
+			Self::deposit_account_event(Event::Unwatched(sender.clone(), course_id), &sender);
+			Ok(())
+		}
+
+		/// Move Courses cloned for `term` to the archive of the term, a child trie, see
+		/// `crate::archive`.
+		///
+		/// The Courses are removed as if burned, and their deposits returned in full. Courses in a
+		/// sealed-bid listing are archived once it is settled.
+		///
+		/// The dispatch origin for this call must be `ForceOrigin`.
+		#[transactional]
+		#[pallet::weight((
+			T::WeightInfo::archive_term(course_ids.len() as u32),
+			DispatchClass::Operational,
+		))]
+		pub fn archive_term(
+			origin: OriginFor<T>,
+			term: Term,
+			course_ids: BoundedVec<T::Hash, T::MaxArchiveBatch>,
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+			ensure!(!<MigrationCursor<T>>::exists(), <Error<T>>::MigrationOngoing);
+
+			let child_info = archive::child_info(term);
+			let now = <frame_system::Pallet<T>>::block_number();
+			for course_id in &course_ids {
+				let course = Self::courses(course_id).ok_or(<Error<T>>::CourseNotExist)?;
+				let lineage = Self::course_lineage(course_id)
+					.filter(|lineage| lineage.term == term)
+					.ok_or(<Error<T>>::NotOfTerm)?;
+				ensure!(
+					!<SealedListings<T>>::contains_key(course_id),
+					<Error<T>>::SealedListingOpen
+				);
+
+				let content = Self::course_content(course_id);
+				let archived = ArchivedCourse {
+					dna: course.dna,
+					course_year: course.course_year.clone(),
+					owner: course.owner.clone(),
+					creator: Self::creator(course_id),
+					cloned_from: lineage.cloned_from,
+					syllabus: content.as_ref().and_then(|c| c.syllabus.clone()).map(Into::into),
+					materials: content.and_then(|c| c.materials).map(Into::into),
+					content_version: Self::metadata_log(course_id).map_or(0, |log| log.version),
+					tips: Self::tips(course_id),
+					history_len: <CourseHistoryLen<T>>::take(course_id),
+					blobs_root: course.blobs_root,
+					archived: now,
+				};
+				child::put(&child_info, &archive::key(course_id), &archived);
+
+				Self::remove_course(course_id, &course);
+				if let Some(deposit) = <CourseDeposits<T>>::take(course_id) {
+					T::Currency::unreserve(&deposit.depositor, deposit.amount);
+				}
+				if let Some((depositor, amount)) = <ContentDeposits<T>>::take(course_id) {
+					T::Currency::unreserve(&depositor, amount);
+				}
+				T::OnTransfer::on_burn(course_id, &course.owner);
+
+				Self::deposit_account_event(
+					Event::CourseArchived(course.owner.clone(), *course_id, term),
+					&course.owner,
+				);
+			}
+
+			let root = child::root(&child_info, T::Version::get().state_version());
+			let root = T::Hash::decode(&mut &root[..])
+				.expect("Node is configured to use the same hash; qed");
+			let courses = <ArchivedTerms<T>>::mutate(term, |archive| {
+				let archive = archive.get_or_insert_with(Default::default);
+				archive.root = root;
+				archive.courses = archive.courses.saturating_add(course_ids.len() as u32);
+				archive.courses
+			});
+			Self::deposit_unindexed_event(Event::TermArchived(term, courses, root));
+
+			Ok(Pays::No.into())
+		}
+
+		/// Set `blob` of a Course, or remove it with `None`.
+		///
+		/// Blobs are kept in a child trie of the Course, whose root is the `blobs_root` of the
+		/// Course, see `crate::blobs`. Like the content, they hold `DepositPerByte` for each of
+		/// their bytes.
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::set_blob(T::MaxBlobLen::get()))]
+		pub fn set_blob(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			blob: Blob,
+			value: Option<BoundedVec<u8, T::MaxBlobLen>>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_callable()?;
+
+			let mut course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+			ensure!(course.owner == sender, <Error<T>>::NotCourseOwner);
+
+			let child_info = blobs::child_info(&course_id);
+			let len = value.as_ref().map_or(0, |value| value.len() as u32);
+			match value {
+				Some(value) => child::put_raw(&child_info, &blobs::key(blob), &value),
+				None => child::kill(&child_info, &blobs::key(blob)),
+			}
+			let content_len = Self::course_content(&course_id).map_or(0, |c| c.encoded_size());
+			Self::hold_content_deposit(
+				&course_id,
+				&sender,
+				content_len + Self::blobs_len(&course_id),
+			)?;
+
+			course.blobs_root = if Self::blobs_len(&course_id) == 0 {
+				None
+			} else {
+				let root = child::root(&child_info, T::Version::get().state_version());
+				Some(
+					T::Hash::decode(&mut &root[..])
+						.expect("Node is configured to use the same hash; qed"),
+				)
+			};
+			<Courses<T>>::insert(&course_id, course);
+
+			Self::deposit_course_event(
+				Event::BlobSet(sender.clone(), course_id, blob),
+				&sender,
+				&course_id,
+			);
+
+			Ok(Some(T::WeightInfo::set_blob(len)).into())
+		}
+	}
+
+	//** Our helper functions.**//
+
+	impl<T: Config> Pallet<T> {
+		fn gen_dna() -> Dna {
+			let payload = (
+				T::CourseRandomness::random(&b"dna"[..]).0,
+				<frame_system::Pallet<T>>::extrinsic_index().unwrap_or_default(),
+				<frame_system::Pallet<T>>::block_number(),
+			);
+			payload.using_encoded(blake2_256)
+		}
+
+		/// Mint a Course of `course_year` for `owner`, reserving its deposit from `funder`, for
+		/// pallets giving Courses away. The mint quota of `owner` does not apply.
+		pub fn mint_funded(
+			owner: &T::AccountId,
+			funder: &T::AccountId,
+			course_year: CourseYear,
+		) -> Result<T::Hash, Error<T>> {
+			Self::ensure_callable()?;
+			ensure!(
+				T::Currency::can_reserve(funder, T::CourseDeposit::get()),
+				<Error<T>>::NotEnoughBalance
+			);
+
+			let course_id = Self::mint(owner, None, Some(course_year))?;
+			Self::reserve_deposit(&course_id, funder)?;
+			<Creators<T>>::insert(&course_id, funder);
+			Self::record_history(&course_id, None, owner, None);
+
+			Self::deposit_course_event(Event::Created(owner.clone(), course_id), owner, &course_id);
+
+			Ok(course_id)
+		}
+
+		/// Breed a Course for `owner` from two Courses it owns, returning the new Course's id.
+		pub fn do_breed(
+			owner: &T::AccountId,
+			parent1: &T::Hash,
+			parent2: &T::Hash,
+		) -> Result<T::Hash, Error<T>> {
+			// Check: Verify `owner` owns both courses (and both courses exist).
+			ensure!(Self::is_course_owner(parent1, owner)?, <Error<T>>::NotCourseOwner);
+			ensure!(Self::is_course_owner(parent2, owner)?, <Error<T>>::NotCourseOwner);
+
+			if T::BreedSameYearOnly::get() {
+				let year1 = Self::courses(parent1).map(|course| course.course_year);
+				let year2 = Self::courses(parent2).map(|course| course.course_year);
+				ensure!(year1 == year2, <Error<T>>::ParentsDifferentYears);
+			}
 
-			let new_dna = Self::breed_dna(&parent1, &parent2)?;
-			Self::mint(&sender, Some(new_dna), None)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			for parent in [parent1, parent2] {
+				if let Some(since) = Self::owned_since(parent) {
+					ensure!(
+						now >= since.saturating_add(T::MinBreedingAge::get()),
+						<Error<T>>::ParentTooRecent
+					);
+				}
+			}
 
-			Ok(())
-		}
-	}
+			Self::ensure_can_mint(owner)?;
 
-	//** Our helper functions.**//
+			let new_dna = Self::breed_dna(parent1, parent2)?;
+			let course_id = Self::mint(owner, Some(new_dna), None)?;
+			Self::note_mint(owner);
+			Self::reserve_deposit(&course_id, owner)?;
+			Self::record_history(&course_id, None, owner, None);
 
-	impl<T: Config> Pallet<T> {
-		fn gen_dna() -> [u8; 16] {
-			let payload = (
-				T::CourseRandomness::random(&b"dna"[..]).0,
-				<frame_system::Pallet<T>>::extrinsic_index().unwrap_or_default(),
-				<frame_system::Pallet<T>>::block_number(),
+			Self::deposit_course_event(
+				Event::Bred(owner.clone(), *parent1, *parent2, course_id),
+				owner,
+				&course_id,
 			);
-			payload.using_encoded(blake2_128)
+
+			Ok(course_id)
 		}
 
-		pub fn breed_dna(parent1: &T::Hash, parent2: &T::Hash) -> Result<[u8; 16], Error<T>> {
+		pub fn breed_dna(parent1: &T::Hash, parent2: &T::Hash) -> Result<Dna, Error<T>> {
 			let dna1 = Self::courses(parent1).ok_or(<Error<T>>::CourseNotExist)?.dna;
 			let dna2 = Self::courses(parent2).ok_or(<Error<T>>::CourseNotExist)?.dna;
 
-			let mut new_dna = Self::gen_dna();
-			for i in 0..new_dna.len() {
-				new_dna[i] = (new_dna[i] & dna1[i]) | (!new_dna[i] & dna2[i]);
-			}
-			Ok(new_dna)
+			Ok(dna::breed(&dna1, &dna2, &Self::gen_dna()))
 		}
 
 		// Helper to mint a Course.
 		pub fn mint(
 			owner: &T::AccountId,
-			dna: Option<[u8; 16]>,
+			dna: Option<Dna>,
 			course_year: Option<CourseYear>,
 		) -> Result<T::Hash, Error<T>> {
 			let course_year = match course_year {
@@ -339,10 +2882,16 @@ pub mod pallet {
 				price: None,
 				course_year,
 				owner: owner.clone(),
+				blobs_root: None,
 			};
 
 			let course_id = T::Hashing::hash_of(&course);
+			Self::insert_course(course_id, course)?;
+			Ok(course_id)
+		}
 
+		// Helper to store a new Course under `course_id`.
+		fn insert_course(course_id: T::Hash, course: Course<T>) -> Result<(), Error<T>> {
 			// Performs this operation first as it may fail
 			let new_cnt = Self::count_for_courses()
 				.checked_add(1)
@@ -350,14 +2899,954 @@ pub mod pallet {
 
 			// Check if the course does not already exist in our storage map
 			ensure!(Self::courses(&course_id) == None, <Error<T>>::CourseExists);
+			Self::ensure_can_own(&course.owner, &course.course_year)?;
+			ensure!(
+				Self::owned_count(&course.owner) < Self::owned_limit(),
+				<Error<T>>::ExceedMaxCourseOwned
+			);
 
 			// Performs this operation first because as it may fail
-			<CoursesOwned<T>>::try_mutate(&owner, |course_vec| course_vec.try_push(course_id))
-				.map_err(|_| <Error<T>>::ExceedMaxCourseOwned)?;
-
+			<CoursesOwned<T>>::try_mutate(&course.owner, |course_vec| {
+				course_vec.try_push(course_id)
+			})
+			.map_err(|_| <Error<T>>::ExceedMaxCourseOwned)?;
+
+			Self::note_owned_added(&course.owner);
+			Self::note_holding_change(&course.owner, &course.course_year);
+			<OwnedPerYear<T>>::mutate(&course.owner, &course.course_year, |n| {
+				*n = n.saturating_add(1)
+			});
+			<OwnedSince<T>>::insert(course_id, <frame_system::Pallet<T>>::block_number());
+			<Creators<T>>::insert(course_id, &course.owner);
+			<YearStatistics<T>>::mutate(&course.course_year, |stats| {
+				stats.minted = stats.minted.saturating_add(1)
+			});
+			T::OnTransfer::on_transfer(&course_id, None, &course.owner);
 			<Courses<T>>::insert(course_id, course);
 			<CountForCourses<T>>::put(new_cnt);
-			Ok(course_id)
+			Ok(())
+		}
+
+		/// The creator of `course_id`. For Courses minted before creators were recorded, the
+		/// account that reserved its deposit.
+		pub fn creator(course_id: &T::Hash) -> Option<T::AccountId> {
+			Self::creators(course_id)
+				.or_else(|| Self::course_deposits(course_id).map(|deposit| deposit.depositor))
+		}
+
+		/// The current price of the declining-price listing of `course_id`, if it has one.
+		pub fn dutch_price(course_id: &T::Hash) -> Option<BalanceOf<T>> {
+			let listing = Self::dutch_listings(course_id)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			let elapsed: u32 = now.saturating_sub(listing.start).unique_saturated_into();
+			let price =
+				listing.start_price.saturating_sub(listing.decay.saturating_mul(elapsed.into()));
+			Some(price.max(listing.floor_price))
+		}
+
+		/// The price `course_id` can currently be bought at, if it is for sale.
+		pub fn ask_price(course_id: &T::Hash) -> Option<BalanceOf<T>> {
+			Self::dutch_price(course_id).or_else(|| Self::courses(course_id)?.price)
+		}
+
+		/// The storage key of `course_id` and the SCALE-encoded course stored under it.
+		pub fn course_storage(course_id: &T::Hash) -> (Vec<u8>, Option<Vec<u8>>) {
+			let key = <Courses<T>>::hashed_key_for(course_id);
+			let value = sp_io::storage::get(&key);
+			(key, value)
+		}
+
+		/// The ids of up to `limit` Courses from `cursor`, the cursor of the previous page or
+		/// `None` to start over, and the cursor of the next page unless it was the last.
+		pub fn course_ids(cursor: Option<Vec<u8>>, limit: u32) -> (Vec<T::Hash>, Option<Vec<u8>>) {
+			let mut ids = match cursor {
+				Some(key) => <Courses<T>>::iter_keys_from(key),
+				None => <Courses<T>>::iter_keys(),
+			};
+			let page: Vec<_> = ids.by_ref().take(limit.max(1) as usize).collect();
+			let next = (page.len() as u32 == limit.max(1)).then(|| ids.last_raw_key().to_vec());
+			(page, next)
+		}
+
+		/// Check up to `limit` entries of `Courses`, then of `CoursesOwned`, from `cursor`, the
+		/// `next` cursor of the previous page, or from the start if it is `None`.
+		pub fn audit_storage(
+			cursor: Option<Vec<u8>>,
+			limit: u32,
+		) -> AuditPage<T::AccountId, T::Hash> {
+			let limit = limit.max(1);
+			let owners_prefix = <CoursesOwned<T>>::final_prefix();
+			let mut page = AuditPage {
+				inconsistencies: Vec::new(),
+				courses_checked: 0,
+				owners_checked: 0,
+				course_count: Self::count_for_courses(),
+				next: None,
+			};
+
+			match cursor {
+				Some(key) if key.starts_with(&owners_prefix) => {
+					let mut owners = <CoursesOwned<T>>::iter_from(key);
+					for (who, owned) in owners.by_ref().take(limit as usize) {
+						for course_id in owned.iter() {
+							if Self::courses(course_id).map(|course| course.owner).as_ref() !=
+								Some(&who)
+							{
+								page.inconsistencies.push(Inconsistency::OrphanedOwnership(
+									who.clone(),
+									*course_id,
+								));
+							}
+						}
+						let count = Self::owned_count(&who);
+						if count as usize != owned.len() {
+							page.inconsistencies.push(Inconsistency::OwnedCountMismatch(
+								who,
+								count,
+								owned.len() as u32,
+							));
+						}
+						page.owners_checked += 1;
+					}
+					if page.owners_checked == limit {
+						page.next = Some(owners.last_raw_key().to_vec());
+					}
+				},
+				cursor => {
+					let mut courses = match cursor {
+						Some(key) => <Courses<T>>::iter_from(key),
+						None => <Courses<T>>::iter(),
+					};
+					for (course_id, course) in courses.by_ref().take(limit as usize) {
+						if !Self::courses_owned(&course.owner).contains(&course_id) {
+							page.inconsistencies
+								.push(Inconsistency::MissingFromOwner(course_id, course.owner));
+						}
+						page.courses_checked += 1;
+					}
+					// Carry on with `CoursesOwned` once all courses are checked.
+					page.next = Some(if page.courses_checked == limit {
+						courses.last_raw_key().to_vec()
+					} else {
+						owners_prefix.to_vec()
+					});
+				},
+			}
+			page
+		}
+
+		/// The storage entries the annual report of `course_year` is checked against, with up to
+		/// `limit` Courses from `cursor`, the `next` cursor of the previous page, or from the
+		/// start if it is `None`. See the `audit` module.
+		pub fn report_storage(
+			course_year: CourseYear,
+			cursor: Option<Vec<u8>>,
+			limit: u32,
+		) -> ReportPage {
+			let mut keys = sp_std::vec![
+				<CountForCourses<T>>::hashed_key().to_vec(),
+				<YearStatistics<T>>::hashed_key_for(course_year),
+			];
+			keys.extend(<ArchivedTerms<T>>::iter_keys().map(<ArchivedTerms<T>>::hashed_key_for));
+			keys.extend(
+				<OwnerSnapshots<T>>::iter()
+					.filter(|(_, snapshot)| snapshot.course_year == course_year)
+					.map(|(id, _)| <OwnerSnapshots<T>>::hashed_key_for(id)),
+			);
+			let (course_ids, next) = Self::course_ids(cursor, limit);
+			keys.extend(course_ids.iter().map(<Courses<T>>::hashed_key_for));
+
+			ReportPage {
+				entries: keys
+					.into_iter()
+					.map(|key| {
+						let value = sp_io::storage::get(&key);
+						(key, value)
+					})
+					.collect(),
+				courses: course_ids.len() as u32,
+				next,
+			}
+		}
+
+		/// The topic indexing the events of the courses owned by `who`.
+		pub fn owner_topic(who: &T::AccountId) -> T::Hash {
+			T::Hashing::hash_of(&(b"owner", who))
+		}
+
+		/// The topic indexing the events of the courses of `year`.
+		pub fn collection_topic(year: &CourseYear) -> T::Hash {
+			T::Hashing::hash_of(&(b"collection", year))
+		}
+
+		/// The topic indexing the events about `course_id`.
+		pub fn course_topic(course_id: &T::Hash) -> T::Hash {
+			T::Hashing::hash_of(&(b"course", course_id))
+		}
+
+		/// The topic indexing the events `who` is a party of.
+		pub fn account_topic(who: &T::AccountId) -> T::Hash {
+			T::Hashing::hash_of(&(b"account", who))
+		}
+
+		/// The `account` topics of the parties of `event` and the `course` topics of the courses
+		/// it is about.
+		fn party_topics(event: &Event<T>) -> Vec<T::Hash> {
+			let mut accounts = Vec::new();
+			let mut courses = Vec::new();
+			match event {
+				Event::Created(who, course_id) |
+				Event::PriceSet(who, course_id, _) |
+				Event::StablePriceSet(who, course_id, _) |
+				Event::ContentSet(who, course_id) |
+				Event::Burned(who, course_id, _) |
+				Event::DutchListed(who, course_id, ..) |
+				Event::TransferRejected(who, course_id) |
+				Event::SealedListed(who, course_id, ..) |
+				Event::PurchaseCommitted(who, course_id) |
+				Event::PurchaseRevealed(who, course_id, _) |
+				Event::SealedBidWithdrawn(who, course_id, _) |
+				Event::SealedListingClosed(who, course_id) |
+				Event::Watched(who, course_id, _) |
+				Event::Unwatched(who, course_id) |
+				Event::WatchTriggered(who, course_id, _) |
+				Event::CourseArchived(who, course_id, _) |
+				Event::BlobSet(who, course_id, _) => {
+					accounts.push(who);
+					courses.push(course_id);
+				},
+				Event::Transferred(from, to, course_id) |
+				Event::Bought(to, from, course_id, _) |
+				Event::BoughtInStable(to, from, course_id, _) |
+				Event::Tipped(from, to, course_id, _) |
+				Event::TransferFeePaid(from, to, course_id, _) |
+				Event::TransferOffered(from, to, course_id, _) => {
+					accounts.extend([from, to]);
+					courses.push(course_id);
+				},
+				Event::Approved(owner, course_id, delegate) => {
+					accounts.push(owner);
+					accounts.extend(delegate.as_ref());
+					courses.push(course_id);
+				},
+				Event::Bred(owner, parent1, parent2, course_id) => {
+					accounts.push(owner);
+					courses.extend([parent1, parent2, course_id]);
+				},
+				Event::Cloned(owner, source_id, course_id, _) => {
+					accounts.push(owner);
+					courses.extend([source_id, course_id]);
+				},
+				Event::BundleCreated(who, ..) |
+				Event::BundleCancelled(who, _) |
+				Event::SwapCancelled(who, _) |
+				Event::AccountBanned(who) |
+				Event::AccountUnbanned(who) => accounts.push(who),
+				Event::BundleBought(buyer, seller, ..) |
+				Event::SwapProposed(buyer, seller, _) |
+				Event::SwapAccepted(buyer, seller, _) => accounts.extend([buyer, seller]),
+				_ => {},
+			}
+
+			let mut topics = Vec::with_capacity(accounts.len() + courses.len());
+			for topic in accounts
+				.into_iter()
+				.map(Self::account_topic)
+				.chain(courses.into_iter().map(Self::course_topic))
+			{
+				if !topics.contains(&topic) {
+					topics.push(topic);
+				}
+			}
+			topics
+		}
+
+		/// Deposit an event about `course_id`, indexed by its owner and collection, and by its
+		/// parties and courses.
+		fn deposit_course_event(event: Event<T>, owner: &T::AccountId, course_id: &T::Hash) {
+			let mut topics = sp_std::vec![Self::owner_topic(owner)];
+			if let Some(course) = Self::courses(course_id) {
+				topics.push(Self::collection_topic(&course.course_year));
+			}
+			topics.extend(Self::party_topics(&event));
+
+			let event = <<T as Config>::Event as From<Event<T>>>::from(event);
+			<frame_system::Pallet<T>>::deposit_event_indexed(&topics, event.into());
+		}
+
+		/// Bump the content version of `course_id`, remembering the hash of its current content.
+		///
+		/// Called before the content changes.
+		fn log_metadata_update(course_id: &T::Hash) {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let current = T::Hashing::hash_of(&Self::course_content(course_id));
+			<MetadataLogs<T>>::mutate(course_id, |log| {
+				let log = log.get_or_insert_with(|| MetadataLog {
+					version: 0,
+					updated: Zero::zero(),
+					previous: Default::default(),
+				});
+				// Forget the oldest content once the history is full.
+				if !log.previous.is_empty() &&
+					log.previous.len() as u32 >= T::MaxMetadataHistory::get()
+				{
+					log.previous.remove(0);
+				}
+				let _ = log.previous.try_push((current, log.updated));
+				log.version = log.version.saturating_add(1);
+				log.updated = now;
+			});
+		}
+
+		/// Append a change of ownership of `course_id` to its history in the off-chain index.
+		fn record_history(
+			course_id: &T::Hash,
+			from: Option<&T::AccountId>,
+			to: &T::AccountId,
+			price: Option<BalanceOf<T>>,
+		) {
+			let record = CourseRecord {
+				block: <frame_system::Pallet<T>>::block_number(),
+				from: from.cloned(),
+				to: to.clone(),
+				price,
+			};
+			let index = <CourseHistoryLen<T>>::mutate(course_id, |len| {
+				*len = len.saturating_add(1);
+				*len - 1
+			});
+			sp_io::offchain_index::set(&history_key(course_id, index), &record.encode());
+		}
+
+		/// Lock the `price` `seller` was paid under a vesting schedule if it is at least
+		/// `VestingThreshold`, so that a compromised seller cannot cash out at once.
+		fn vest_proceeds(seller: &T::AccountId, price: BalanceOf<T>) -> DispatchResult {
+			if price < T::VestingThreshold::get() {
+				return Ok(())
+			}
+			let period: u32 = T::ProceedsVestingPeriod::get().unique_saturated_into();
+			let per_block = (price / period.max(1).into()).max(1u32.into());
+			let now = <frame_system::Pallet<T>>::block_number();
+			T::Vesting::add_vesting_schedule(seller, price, per_block, now)
+				.map_err(|_| <Error<T>>::TooManyVestingSchedules.into())
+		}
+
+		/// Record a sale of a Course of `course_year` for `price` in the recent sales, the market
+		/// statistics and the statistics of the year.
+		fn note_sale(course_year: &CourseYear, price: BalanceOf<T>) {
+			<RecentSales<T>>::mutate(course_year, |sales| {
+				// Drop the oldest sale to make room for this one.
+				if !sales.is_empty() && sales.len() as u32 >= T::MaxRecentSales::get() {
+					sales.remove(0);
+				}
+				let _ = sales.try_push(price);
+			});
+
+			let session = T::CurrentSession::get();
+			<MarketStats<T>>::mutate(course_year, |stats| {
+				let mut current =
+					SessionStats { session, trades: 0, volume: Zero::zero(), floor: price };
+				if let Some(last) = stats.last().filter(|last| last.session == session).cloned() {
+					current = last;
+					stats.remove(stats.len() - 1);
+				} else if !stats.is_empty() && stats.len() as u32 >= T::MaxStatsSessions::get() {
+					// Drop the oldest session to make room for this one.
+					stats.remove(0);
+				}
+				current.trades = current.trades.saturating_add(1);
+				current.volume = current.volume.saturating_add(price);
+				current.floor = current.floor.min(price);
+				let _ = stats.try_push(current);
+			});
+
+			<YearStatistics<T>>::mutate(course_year, |stats| {
+				stats.trades = stats.trades.saturating_add(1);
+				stats.volume = stats.volume.saturating_add(price);
+				let trades: BalanceOf<T> = stats.trades.unique_saturated_into();
+				stats.average_price = stats.volume / trades;
+			});
+		}
+
+		/// Record a sale of `course_id` in the trades of the block, added to the recent trades
+		/// when it is finalized.
+		fn note_trade(
+			course_id: &T::Hash,
+			seller: &T::AccountId,
+			buyer: &T::AccountId,
+			price: BalanceOf<T>,
+		) {
+			let trade = Trade {
+				course_id: *course_id,
+				seller: seller.clone(),
+				buyer: buyer.clone(),
+				price,
+				block: <frame_system::Pallet<T>>::block_number(),
+			};
+			<PendingTrades<T>>::mutate(|trades| {
+				// The oldest trades of the block would be overwritten in the ring buffer anyway.
+				if !trades.is_empty() && trades.len() as u32 >= T::MaxRecentTrades::get() {
+					trades.remove(0);
+				}
+				let _ = trades.try_push(trade);
+			});
+		}
+
+		/// Up to `limit` of the recent trades, newest first.
+		pub fn recent_trades(limit: u32) -> Vec<TradeOf<T>> {
+			let capacity = u64::from(T::MaxRecentTrades::get());
+			let count = Self::trade_count();
+			let kept = count.min(capacity).min(limit.into());
+			(1..=kept)
+				.filter_map(|back| <RecentTrades<T>>::get(((count - back) % capacity) as u32))
+				.collect()
+		}
+
+		/// The sales of Courses of `course_year` over the last `MaxStatsSessions` sessions.
+		pub fn market_summary(course_year: &CourseYear) -> MarketSummary<BalanceOf<T>> {
+			let oldest = T::CurrentSession::get()
+				.saturating_sub(T::MaxStatsSessions::get().saturating_sub(1));
+			Self::market_stats(course_year)
+				.into_iter()
+				.filter(|stats| stats.session >= oldest)
+				.fold(MarketSummary::default(), |summary, stats| MarketSummary {
+					trades: summary.trades.saturating_add(stats.trades),
+					volume: summary.volume.saturating_add(stats.volume),
+					floor: Some(summary.floor.map_or(stats.floor, |floor| floor.min(stats.floor))),
+				})
+		}
+
+		/// The size of the registry and of its market, over the last `MaxStatsSessions` sessions
+		/// for the sales.
+		pub fn chain_stats() -> ChainStats<BalanceOf<T>> {
+			let market =
+				[CourseYear::First, CourseYear::Second, CourseYear::Third, CourseYear::Fourth]
+					.iter()
+					.map(Self::market_summary)
+					.fold(MarketSummary::default(), |total, summary| MarketSummary {
+						trades: total.trades.saturating_add(summary.trades),
+						volume: total.volume.saturating_add(summary.volume),
+						floor: match (total.floor, summary.floor) {
+							(Some(a), Some(b)) => Some(a.min(b)),
+							(a, b) => a.or(b),
+						},
+					});
+			ChainStats {
+				courses: Self::count_for_courses(),
+				owners: Self::owner_count(),
+				listed_value: Self::listed_value(),
+				market,
+			}
+		}
+
+		/// The number of Courses `who` can still create, breed or clone this session.
+		pub fn remaining_mints(who: &T::AccountId) -> u32 {
+			let (session, minted) = Self::session_mints(who);
+			let minted = if session == T::CurrentSession::get() { minted } else { 0 };
+			T::MaxMintsPerSession::get().saturating_sub(minted)
+		}
+
+		fn ensure_can_mint(who: &T::AccountId) -> Result<(), Error<T>> {
+			ensure!(Self::remaining_mints(who) > 0, <Error<T>>::MintRateLimitExceeded);
+			ensure!(
+				T::Currency::can_reserve(who, T::CourseDeposit::get()),
+				<Error<T>>::NotEnoughBalance
+			);
+			Ok(())
+		}
+
+		/// Reserve the deposit of `course_id` from its minter `who`.
+		fn reserve_deposit(course_id: &T::Hash, who: &T::AccountId) -> Result<(), Error<T>> {
+			let amount = T::CourseDeposit::get();
+			if amount.is_zero() {
+				return Ok(())
+			}
+
+			T::Currency::reserve(who, amount).map_err(|_| <Error<T>>::NotEnoughBalance)?;
+			<CourseDeposits<T>>::insert(
+				course_id,
+				Deposit {
+					depositor: who.clone(),
+					amount,
+					minted: <frame_system::Pallet<T>>::block_number(),
+				},
+			);
+			Ok(())
+		}
+
+		/// Return the deposit of `course_id` to its minter according to `DepositRefundSchedule`,
+		/// slashing the rest. Returns the amount refunded.
+		fn refund_deposit(course_id: &T::Hash) -> BalanceOf<T> {
+			let deposit = match <CourseDeposits<T>>::take(course_id) {
+				Some(deposit) => deposit,
+				None => return Zero::zero(),
+			};
+
+			let schedule = T::DepositRefundSchedule::get();
+			let now = <frame_system::Pallet<T>>::block_number();
+			let refund = if now <= deposit.minted.saturating_add(schedule.full_refund_period) {
+				deposit.amount
+			} else {
+				schedule.late_refund * deposit.amount
+			};
+
+			T::Currency::unreserve(&deposit.depositor, refund);
+			let _ = T::Currency::slash_reserved(
+				&deposit.depositor,
+				deposit.amount.saturating_sub(refund),
+			);
+			refund
+		}
+
+		/// The deposit of `len` bytes of content.
+		fn content_deposit(len: usize) -> BalanceOf<T> {
+			let len: u32 = len.unique_saturated_into();
+			T::DepositPerByte::get().saturating_mul(len.into())
+		}
+
+		/// Hold the deposit of `len` bytes of content of `course_id` from `who`, who sets it.
+		///
+		/// The deposit held for the previous content is adjusted if `who` set it too, and returned
+		/// otherwise, e.g. to a previous owner of the Course.
+		fn hold_content_deposit(
+			course_id: &T::Hash,
+			who: &T::AccountId,
+			len: usize,
+		) -> Result<(), Error<T>> {
+			let amount = Self::content_deposit(len);
+			match <ContentDeposits<T>>::get(course_id) {
+				Some((depositor, held)) if depositor == *who =>
+					if amount > held {
+						T::Currency::reserve(who, amount - held)
+							.map_err(|_| <Error<T>>::NotEnoughBalance)?;
+					} else {
+						T::Currency::unreserve(who, held - amount);
+					},
+				previous => {
+					T::Currency::reserve(who, amount).map_err(|_| <Error<T>>::NotEnoughBalance)?;
+					if let Some((depositor, held)) = previous {
+						T::Currency::unreserve(&depositor, held);
+					}
+				},
+			}
+
+			if amount.is_zero() {
+				<ContentDeposits<T>>::remove(course_id);
+			} else {
+				<ContentDeposits<T>>::insert(course_id, (who.clone(), amount));
+			}
+			Ok(())
+		}
+
+		/// Count a Course minted by `who` towards its quota of the current session.
+		fn note_mint(who: &T::AccountId) {
+			let current = T::CurrentSession::get();
+			<SessionMints<T>>::mutate(who, |(session, minted)| {
+				if *session != current {
+					*session = current;
+					*minted = 0;
+				}
+				*minted = minted.saturating_add(1);
+			});
+		}
+
+		/// The number of Courses of `course_year` a single account can own.
+		pub fn max_courses_per_year(course_year: &CourseYear) -> u32 {
+			Self::year_limits(course_year).unwrap_or_else(T::MaxCoursesPerYear::get)
+		}
+
+		/// Charge `payer` the transfer fee of the year of `course_id`, if it has one, as the
+		/// Course passes to `new_owner`.
+		fn charge_transfer_fee(
+			payer: &T::AccountId,
+			new_owner: &T::AccountId,
+			course_id: &T::Hash,
+		) -> DispatchResult {
+			let course = Self::courses(course_id).ok_or(<Error<T>>::CourseNotExist)?;
+			let fee = match Self::transfer_fee(&course.course_year) {
+				Some(fee) if !fee.amount.is_zero() && fee.department != *payer => fee,
+				_ => return Ok(()),
+			};
+			T::Currency::transfer(
+				payer,
+				&fee.department,
+				fee.amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			Self::deposit_course_event(
+				Event::TransferFeePaid(payer.clone(), fee.department, *course_id, fee.amount),
+				new_owner,
+				course_id,
+			);
+			Ok(())
+		}
+
+		/// List `course_id` of `sender` for sealed bids, see `list_sealed()`.
+		fn do_list_sealed(
+			sender: &T::AccountId,
+			course_id: &T::Hash,
+			floor_price: BalanceOf<T>,
+			second_price: bool,
+		) -> DispatchResult {
+			Self::ensure_callable()?;
+
+			ensure!(Self::is_course_owner(course_id, sender)?, <Error<T>>::NotCourseOwner);
+			Self::ensure_not_sealed(course_id)?;
+			Self::ensure_not_banned(sender)?;
+			Self::ensure_cooled_down(course_id)?;
+
+			<Courses<T>>::mutate(course_id, |course| {
+				if let Some(course) = course {
+					Self::note_price_change(course.price.take(), None);
+				}
+			});
+			<DutchListings<T>>::remove(course_id);
+			let commit_end = <frame_system::Pallet<T>>::block_number()
+				.saturating_add(T::SealedCommitPeriod::get());
+			let reveal_end = commit_end.saturating_add(T::SealedRevealPeriod::get());
+			<SealedListings<T>>::insert(
+				course_id,
+				SealedListing {
+					seller: sender.clone(),
+					floor_price,
+					commit_end,
+					reveal_end,
+					best: None,
+					second: floor_price,
+					second_price,
+				},
+			);
+
+			Self::deposit_course_event(
+				Event::SealedListed(
+					sender.clone(),
+					*course_id,
+					floor_price,
+					reveal_end,
+					second_price,
+				),
+				sender,
+				course_id,
+			);
+
+			Ok(())
+		}
+
+		/// Sell `course_id` to the winner of its sealed-bid listing, paying `price` out of the
+		/// winner's reserve. Changes nothing if the sale cannot go through.
+		#[transactional]
+		fn sell_sealed(
+			course_id: &T::Hash,
+			seller: &T::AccountId,
+			buyer: &T::AccountId,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			let course = Self::courses(course_id).ok_or(<Error<T>>::CourseNotExist)?;
+			ensure!(course.owner == *seller, <Error<T>>::NotCourseOwner);
+			ensure!(T::BuyerFilter::contains(buyer), <Error<T>>::NotEligibleBuyer);
+			Self::ensure_not_banned(buyer)?;
+			Self::ensure_not_banned(seller)?;
+			ensure!(
+				Self::owned_count(buyer) < Self::owned_limit(),
+				<Error<T>>::ExceedMaxCourseOwned
+			);
+
+			Self::charge_transfer_fee(buyer, buyer, course_id)?;
+			let unpaid =
+				T::Currency::repatriate_reserved(buyer, seller, price, BalanceStatus::Free)?;
+			ensure!(unpaid.is_zero(), <Error<T>>::NotEnoughBalance);
+			Self::vest_proceeds(seller, price)?;
+			T::OnPurchase::on_purchase(buyer, seller, price);
+
+			Self::transfer_course_to(course_id, buyer)?;
+			Self::record_history(course_id, Some(seller), buyer, Some(price));
+
+			Self::note_sale(&course.course_year, price);
+			Self::note_trade(course_id, seller, buyer, price);
+
+			Self::deposit_course_event(
+				Event::Bought(buyer.clone(), seller.clone(), *course_id, price),
+				buyer,
+				course_id,
+			);
+			Self::trigger_watches(course_id, price, None);
+			Ok(())
+		}
+
+		/// Alert the watchers of `course_id` targeting `price` or more, unless they were alerted
+		/// of the `previous` listing already.
+		fn trigger_watches(
+			course_id: &T::Hash,
+			price: BalanceOf<T>,
+			previous: Option<BalanceOf<T>>,
+		) {
+			for watch in Self::watches(course_id) {
+				if price <= watch.target &&
+					previous.map_or(true, |previous| previous > watch.target)
+				{
+					Self::deposit_account_event(
+						Event::WatchTriggered(watch.watcher.clone(), *course_id, price),
+						&watch.watcher,
+					);
+				}
+			}
+		}
+
+		/// Remove `course_id` from the main storage, with everything keyed by it but its off-chain
+		/// history and deposits.
+		fn remove_course(course_id: &T::Hash, course: &Course<T>) {
+			let owner = &course.owner;
+			<CoursesOwned<T>>::mutate(owner, |owned| owned.retain(|id| id != course_id));
+			Self::note_owned_removed(owner);
+			Self::note_price_change(course.price, None);
+			Self::note_holding_change(owner, &course.course_year);
+			<OwnedPerYear<T>>::mutate(owner, &course.course_year, |n| *n = n.saturating_sub(1));
+			<CountForCourses<T>>::mutate(|n| *n = n.saturating_sub(1));
+
+			<Courses<T>>::remove(course_id);
+			<DutchListings<T>>::remove(course_id);
+			<CourseApprovals<T>>::remove(course_id);
+			<TransferOffers<T>>::remove(course_id);
+			<AcquiredAt<T>>::remove(course_id);
+			<OwnedSince<T>>::remove(course_id);
+			<CourseContents<T>>::remove(course_id);
+			<MetadataLogs<T>>::remove(course_id);
+			<CourseLineage<T>>::remove(course_id);
+			<Creators<T>>::remove(course_id);
+			<Tips<T>>::remove(course_id);
+			<StablePrices<T>>::remove(course_id);
+		}
+
+		/// The blob of `course_id`, if it has one.
+		pub fn course_blob(course_id: &T::Hash, blob: Blob) -> Option<Vec<u8>> {
+			child::get_raw(&blobs::child_info(course_id), &blobs::key(blob))
+		}
+
+		/// The length in bytes of the blobs of `course_id`.
+		fn blobs_len(course_id: &T::Hash) -> usize {
+			let child_info = blobs::child_info(course_id);
+			Blob::ALL
+				.iter()
+				.filter_map(|blob| child::len(&child_info, &blobs::key(*blob)))
+				.map(|len| len as usize)
+				.sum()
+		}
+
+		/// The archived `course_id` of `term`, if it was archived. Its blobs stay where they were.
+		pub fn archived_course(term: Term, course_id: &T::Hash) -> Option<ArchivedCourseOf<T>> {
+			child::get(&archive::child_info(term), &archive::key(course_id))
+		}
+
+		/// The amount of Courses an account can own.
+		fn owned_limit() -> u32 {
+			T::CoursesOwnedLimit::get().min(T::MaxCoursesOwned::get())
+		}
+
+		/// Reject giving `who` one more Course of `course_year` if it reached the year's limit.
+		fn ensure_can_own(who: &T::AccountId, course_year: &CourseYear) -> Result<(), Error<T>> {
+			ensure!(
+				Self::owned_per_year(who, course_year) < Self::max_courses_per_year(course_year),
+				<Error<T>>::ExceedMaxCoursesPerYear
+			);
+			Ok(())
+		}
+
+		/// Reject marketplace calls involving a banned account.
+		fn ensure_not_banned(who: &T::AccountId) -> Result<(), Error<T>> {
+			ensure!(!<BannedAccounts<T>>::contains_key(who), <Error<T>>::Banned);
+			Ok(())
+		}
+
+		/// Reject transferring or listing `course_id` within `TransferCooldown` blocks of it
+		/// changing hands, unless its year is exempt.
+		fn ensure_cooled_down(course_id: &T::Hash) -> Result<(), Error<T>> {
+			let acquired = match Self::acquired_at(course_id) {
+				Some(acquired) => acquired,
+				None => return Ok(()),
+			};
+			let course = Self::courses(course_id).ok_or(<Error<T>>::CourseNotExist)?;
+			if Self::cooldown_exempt(&course.course_year) {
+				return Ok(())
+			}
+
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >=
+					acquired.saturating_add(T::TransferCooldown::get()),
+				<Error<T>>::CourseCoolingDown
+			);
+			Ok(())
+		}
+
+		/// Ensure `course_id` has no sealed-bid listing, which keeps it with the seller until the
+		/// listing is settled.
+		fn ensure_not_sealed(course_id: &T::Hash) -> Result<(), Error<T>> {
+			ensure!(!<SealedListings<T>>::contains_key(course_id), <Error<T>>::SealedListingExists);
+			Ok(())
+		}
+
+		/// Reject calls while the pallet is paused or a storage migration is in progress.
+		fn ensure_callable() -> Result<(), Error<T>> {
+			ensure!(!Self::paused(), <Error<T>>::PalletPaused);
+			ensure!(!<MigrationCursor<T>>::exists(), <Error<T>>::MigrationOngoing);
+			Ok(())
+		}
+
+		/// Count one more Course owned by `who`, and `who` as an owner if it is its first.
+		fn note_owned_added(who: &T::AccountId) {
+			<OwnedCount<T>>::mutate(who, |n| {
+				if *n == 0 {
+					<OwnerCount<T>>::mutate(|owners| *owners = owners.saturating_add(1));
+				}
+				*n = n.saturating_add(1)
+			});
+		}
+
+		/// Count one Course less owned by `who`, and `who` as an owner no more if it was its last.
+		fn note_owned_removed(who: &T::AccountId) {
+			<OwnedCount<T>>::mutate(who, |n| {
+				if *n == 1 {
+					<OwnerCount<T>>::mutate(|owners| *owners = owners.saturating_sub(1));
+				}
+				*n = n.saturating_sub(1)
+			});
+		}
+
+		/// Update `ListedValue` for the fixed price of a Course changing from `old` to `new`.
+		fn note_price_change(old: Option<BalanceOf<T>>, new: Option<BalanceOf<T>>) {
+			if old.is_none() && new.is_none() {
+				return
+			}
+			<ListedValue<T>>::mutate(|value| {
+				*value = value
+					.saturating_sub(old.unwrap_or_else(Zero::zero))
+					.saturating_add(new.unwrap_or_else(Zero::zero))
+			});
+		}
+
+		/// Record the holdings of `who` of `course_year` before they change, if the pending
+		/// snapshot is of that year and has not reached `who` yet.
+		fn note_holding_change(who: &T::AccountId, course_year: &CourseYear) {
+			let progress = match Self::pending_snapshot() {
+				Some(progress) if progress.course_year == *course_year => progress,
+				_ => return,
+			};
+			if <SnapshotOverrides<T>>::contains_key(who) {
+				return
+			}
+
+			let key = <OwnedPerYear<T>>::hashed_key_for(who, course_year);
+			if progress.cursor.is_empty() || key.as_slice() > progress.cursor.as_slice() {
+				<SnapshotOverrides<T>>::insert(who, Self::owned_per_year(who, course_year));
+			}
+		}
+
+		/// Add the next holdings to the pending snapshot, consuming at most `limit` weight, and
+		/// complete it once all are in.
+		fn step_snapshot(limit: Weight) -> Weight {
+			let overhead = T::DbWeight::get().reads_writes(1, 2);
+			let mut progress = match Self::pending_snapshot() {
+				Some(progress) if limit > overhead => progress,
+				_ => return T::DbWeight::get().reads(1),
+			};
+
+			let per_entry = T::DbWeight::get().reads_writes(2, 1);
+			let mut entries = if progress.cursor.is_empty() {
+				<OwnedPerYear<T>>::iter()
+			} else {
+				<OwnedPerYear<T>>::iter_from(progress.cursor.to_vec())
+			};
+
+			let mut used = overhead;
+			while used.saturating_add(per_entry) <= limit {
+				let (who, course_year, count) = match entries.next() {
+					Some(entry) => entry,
+					None => {
+						let root = snapshot::root::<T::Hashing>(&progress.frontier);
+						<OwnerSnapshots<T>>::insert(
+							progress.id,
+							OwnerSnapshot {
+								course_year: progress.course_year,
+								block: progress.started,
+								root,
+								holders: progress.holders,
+							},
+						);
+						<PendingSnapshot<T>>::kill();
+						Self::deposit_unindexed_event(Event::SnapshotCompleted(
+							progress.id,
+							root,
+							progress.holders,
+						));
+						return used
+					},
+				};
+				used = used.saturating_add(per_entry);
+				if course_year != progress.course_year {
+					continue
+				}
+
+				let count = <SnapshotOverrides<T>>::take(&who).unwrap_or(count);
+				if count > 0 {
+					snapshot::push::<T::Hashing>(
+						&mut progress.frontier,
+						snapshot::leaf::<T::Hashing, _>(&who, count),
+					);
+					progress.holders = progress.holders.saturating_add(1);
+				}
+			}
+
+			progress.cursor = entries
+				.last_raw_key()
+				.to_vec()
+				.try_into()
+				.expect("an OwnedPerYear key is shorter than MAX_CURSOR_LEN; qed");
+			<PendingSnapshot<T>>::put(progress);
+			used
+		}
+
+		/// Whether `who` owned `count` Courses of the year of snapshot `id` when it was taken.
+		///
+		/// `proof` is the path from the holdings to the root, see `snapshot::verify`.
+		pub fn verify_holding(
+			id: SnapshotId,
+			who: &T::AccountId,
+			count: u32,
+			proof: &[(T::Hash, bool)],
+		) -> bool {
+			Self::owner_snapshots(id).map_or(false, |snapshot| {
+				snapshot::verify::<T::Hashing>(
+					&snapshot.root,
+					snapshot::leaf::<T::Hashing, _>(who, count),
+					proof,
+				)
+			})
+		}
+
+		/// The current storage version as a number, for events.
+		fn version_number() -> u16 {
+			Self::storage_version_number(STORAGE_VERSION)
+		}
+
+		/// The number of `version`.
+		fn storage_version_number(version: StorageVersion) -> u16 {
+			// `StorageVersion` doesn't expose its number, it encodes as a plain `u16` though.
+			u16::decode(&mut &version.encode()[..]).unwrap_or_default()
+		}
+
+		/// Deposit an event about the pallet as a whole, without topics.
+		fn deposit_unindexed_event(event: Event<T>) {
+			let event = <<T as Config>::Event as From<Event<T>>>::from(event);
+			<frame_system::Pallet<T>>::deposit_event(event);
+		}
+
+		/// Deposit an event about an account rather than a single course, indexed by the account,
+		/// and by its parties and courses.
+		fn deposit_account_event(event: Event<T>, who: &T::AccountId) {
+			let mut topics = sp_std::vec![Self::owner_topic(who)];
+			topics.extend(Self::party_topics(&event));
+
+			let event = <<T as Config>::Event as From<Event<T>>>::from(event);
+			<frame_system::Pallet<T>>::deposit_event_indexed(&topics, event.into());
 		}
 
 		pub fn is_course_owner(course_id: &T::Hash, acct: &T::AccountId) -> Result<bool, Error<T>> {
@@ -367,9 +3856,91 @@ pub mod pallet {
 			}
 		}
 
+		/// Check the invariants tying `Courses`, `CoursesOwned` and `CountForCourses` together.
+		///
+		/// Run before and after every runtime upgrade by `try-runtime`.
+		#[cfg(feature = "try-runtime")]
+		pub fn do_try_state() -> Result<(), &'static str> {
+			// Courses, and the counters, are only in their current format once migrated.
+			if Self::on_chain_storage_version() < STORAGE_VERSION {
+				return Ok(())
+			}
+
+			let mut count = 0u64;
+			for (course_id, course) in <Courses<T>>::iter() {
+				ensure!(
+					<CoursesOwned<T>>::get(&course.owner).contains(&course_id),
+					"A course is missing from its owner's list"
+				);
+				count += 1;
+			}
+			ensure!(count == Self::count_for_courses(), "CountForCourses mismatch");
+
+			let owned = <CoursesOwned<T>>::iter_values().map(|owned| owned.len() as u64).sum();
+			ensure!(count == owned, "An owner's list references a course that does not exist");
+
+			for (owner, owned) in <CoursesOwned<T>>::iter() {
+				ensure!(Self::owned_count(&owner) == owned.len() as u32, "OwnedCount mismatch");
+			}
+			let per_year: u64 = <OwnedPerYear<T>>::iter_values().map(|owned| owned as u64).sum();
+			ensure!(count == per_year, "OwnedPerYear mismatch");
+
+			let archived: u64 =
+				<ArchivedTerms<T>>::iter_values().map(|archive| archive.courses as u64).sum();
+			let existing: u64 = <YearStatistics<T>>::iter_values()
+				.map(|stats| stats.minted.saturating_sub(stats.burned))
+				.sum();
+			ensure!(count + archived == existing, "YearStatistics mismatch");
+
+			for course_id in <CourseContents<T>>::iter_keys() {
+				ensure!(
+					<Courses<T>>::contains_key(&course_id),
+					"Content of a course that does not exist"
+				);
+			}
+
+			Ok(())
+		}
+
+		/// Transfer a Course on behalf of its owner.
+		///
+		/// `operator` must either own the course or have been approved for it with `approve()`.
+		pub fn transfer_approved(
+			operator: &T::AccountId,
+			to: &T::AccountId,
+			course_id: &T::Hash,
+		) -> Result<(), Error<T>> {
+			Self::ensure_callable()?;
+			let course = Self::courses(course_id).ok_or(<Error<T>>::CourseNotExist)?;
+			ensure!(
+				course.owner == *operator ||
+					Self::course_approvals(course_id).as_ref() == Some(operator),
+				<Error<T>>::NotApproved
+			);
+			ensure!(course.owner != *to, <Error<T>>::TransferToSelf);
+
+			// Verify the recipient has the capacity to receive one more course
+			ensure!(Self::owned_count(to) < Self::owned_limit(), <Error<T>>::ExceedMaxCourseOwned);
+
+			Self::transfer_course_to(course_id, to)?;
+			Self::record_history(course_id, Some(&course.owner), to, None);
+
+			Self::deposit_course_event(
+				Event::Transferred(course.owner, to.clone(), *course_id),
+				to,
+				course_id,
+			);
+
+			Ok(())
+		}
+
 		#[transactional]
 		pub fn transfer_course_to(course_id: &T::Hash, to: &T::AccountId) -> Result<(), Error<T>> {
+			Self::ensure_cooled_down(course_id)?;
+			// `settle_sealed` closes the listing before selling the Course.
+			Self::ensure_not_sealed(course_id)?;
 			let mut course = Self::courses(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+			Self::ensure_can_own(to, &course.course_year)?;
 
 			let prev_owner = course.owner.clone();
 
@@ -382,18 +3953,37 @@ pub mod pallet {
 				Err(())
 			})
 			.map_err(|_| <Error<T>>::CourseNotExist)?;
+			Self::note_owned_removed(&prev_owner);
+			Self::note_owned_added(to);
+			Self::note_holding_change(&prev_owner, &course.course_year);
+			Self::note_holding_change(to, &course.course_year);
+			<OwnedPerYear<T>>::mutate(&prev_owner, &course.course_year, |n| {
+				*n = n.saturating_sub(1)
+			});
+			<OwnedPerYear<T>>::mutate(to, &course.course_year, |n| *n = n.saturating_add(1));
 
 			// Update the course owner
 			course.owner = to.clone();
 			// Reset the ask price so the course is not for sale until `set_price()` is called
 			// by the current owner.
-			course.price = None;
+			Self::note_price_change(course.price.take(), None);
+			<DutchListings<T>>::remove(course_id);
+			<StablePrices<T>>::remove(course_id);
+			// Approvals and offers made by the previous owner don't carry over.
+			<CourseApprovals<T>>::remove(course_id);
+			<TransferOffers<T>>::remove(course_id);
+			// Restart the transfer cooldown and the breeding age.
+			let now = <frame_system::Pallet<T>>::block_number();
+			<AcquiredAt<T>>::insert(course_id, now);
+			<OwnedSince<T>>::insert(course_id, now);
 
 			<Courses<T>>::insert(course_id, course);
 
 			<CoursesOwned<T>>::try_mutate(to, |vec| vec.try_push(*course_id))
 				.map_err(|_| <Error<T>>::ExceedMaxCourseOwned)?;
 
+			T::OnTransfer::on_transfer(course_id, Some(&prev_owner), to);
+
 			Ok(())
 		}
 	}
@@ -0,0 +1,339 @@
+//! Storage migrations spread over several blocks.
+//!
+//! A migration too large for a single block implements [`SteppedMigration`] and is appended to
+//! the pallet's `Migration`, a tuple of every migration in the order of their versions, together
+//! with a bump of the pallet's storage version. On the runtime upgrade the pallet stores an empty
+//! cursor, then `on_idle` steps the first migration to a version above the on-chain one with the
+//! remaining weight of each block. Once a migration reports it is done, the on-chain version is
+//! set to its version and the next one starts from an empty cursor, so a chain several versions
+//! behind goes through every migration it missed. Calls are rejected with `MigrationOngoing` in
+//! the meantime.
+
+use crate::{
+	dna, Config, Course, CourseYear, Courses, CoursesOwned, ListedValue, OwnedCount, OwnedPerYear,
+	OwnerCount, YearStatistics,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	sp_runtime::traits::Saturating,
+	storage::unhashed,
+	traits::{ConstU32, Currency, Get},
+	weights::Weight,
+	BoundedVec,
+};
+use sp_std::marker::PhantomData;
+
+/// The maximum length of a migration cursor, in bytes.
+pub const MAX_CURSOR_LEN: u32 = 128;
+
+/// Where a migration resumes from, in a format of its choosing.
+pub type Cursor = BoundedVec<u8, ConstU32<MAX_CURSOR_LEN>>;
+
+/// A storage migration executed in batches.
+pub trait SteppedMigration {
+	/// The storage version the migration brings the storage to, from the version below.
+	const VERSION: u16;
+
+	/// Migrate the next batch of items, consuming at most `limit` weight.
+	///
+	/// `cursor` is empty on the first step and otherwise the cursor returned by the previous
+	/// step. Returns the cursor to resume from, or `None` once everything was migrated, along
+	/// with the weight consumed.
+	fn step(cursor: &[u8], limit: Weight) -> (Option<Cursor>, Weight);
+}
+
+/// Migrations run one after the other, in the order of their versions.
+pub trait MigrationSequence {
+	/// Step the first migration to a version above `on_chain` from `cursor`, consuming at most
+	/// `limit` weight.
+	///
+	/// Returns the version of that migration with the result of its step, or `None` if the
+	/// storage is at the version of every migration already.
+	fn step(on_chain: u16, cursor: &[u8], limit: Weight) -> Option<(u16, Option<Cursor>, Weight)>;
+}
+
+impl MigrationSequence for () {
+	fn step(
+		_on_chain: u16,
+		_cursor: &[u8],
+		_limit: Weight,
+	) -> Option<(u16, Option<Cursor>, Weight)> {
+		None
+	}
+}
+
+macro_rules! impl_migration_sequence {
+	($($migration:ident),+) => {
+		impl<$($migration: SteppedMigration),+> MigrationSequence for ($($migration,)+) {
+			fn step(
+				on_chain: u16,
+				cursor: &[u8],
+				limit: Weight,
+			) -> Option<(u16, Option<Cursor>, Weight)> {
+				$(
+					if on_chain < $migration::VERSION {
+						let (next, used) = $migration::step(cursor, limit);
+						return Some(($migration::VERSION, next, used))
+					}
+				)+
+				None
+			}
+		}
+	};
+}
+
+impl_migration_sequence!(A);
+impl_migration_sequence!(A, B);
+impl_migration_sequence!(A, B, C);
+impl_migration_sequence!(A, B, C, D);
+impl_migration_sequence!(A, B, C, D, E);
+impl_migration_sequence!(A, B, C, D, E, F);
+impl_migration_sequence!(A, B, C, D, E, F, G);
+impl_migration_sequence!(A, B, C, D, E, F, G, H);
+
+/// A Course as stored before storage version 3, with a 16-byte DNA.
+#[derive(Decode)]
+struct CourseV2<AccountId, Balance> {
+	dna: [u8; 16],
+	price: Option<Balance>,
+	course_year: CourseYear,
+	owner: AccountId,
+}
+
+type CourseV2Of<T> = CourseV2<
+	<T as frame_system::Config>::AccountId,
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance,
+>;
+
+/// A Course as stored in storage versions 3 and 4, with a 32-byte DNA and without blobs.
+#[derive(Encode, Decode)]
+struct CourseV4<AccountId, Balance> {
+	dna: dna::Dna,
+	price: Option<Balance>,
+	course_year: CourseYear,
+	owner: AccountId,
+}
+
+type CourseV4Of<T> = CourseV4<
+	<T as frame_system::Config>::AccountId,
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance,
+>;
+
+/// Counts the Courses every account owns into `OwnedCount`, and per year into `OwnedPerYear`,
+/// for storage version 1.
+pub struct CountOwnedCourses<T>(PhantomData<T>);
+
+impl<T: Config> SteppedMigration for CountOwnedCourses<T> {
+	const VERSION: u16 = 1;
+
+	fn step(cursor: &[u8], limit: Weight) -> (Option<Cursor>, Weight) {
+		let per_course = T::DbWeight::get().reads_writes(3, 2);
+		// Only the keys are decoded, the values being in the format of version 0.
+		let mut course_ids = if cursor.is_empty() {
+			<Courses<T>>::iter_keys()
+		} else {
+			<Courses<T>>::iter_keys_from(cursor.to_vec())
+		};
+
+		let mut used: Weight = 0;
+		while used.saturating_add(per_course) <= limit {
+			match course_ids.next() {
+				Some(course_id) => {
+					let key = <Courses<T>>::hashed_key_for(&course_id);
+					if let Some(course) = unhashed::get::<CourseV2Of<T>>(&key) {
+						<OwnedCount<T>>::mutate(&course.owner, |n| *n = n.saturating_add(1));
+						<OwnedPerYear<T>>::mutate(&course.owner, &course.course_year, |n| {
+							*n = n.saturating_add(1)
+						});
+					}
+					used = used.saturating_add(per_course);
+				},
+				None => return (None, used),
+			}
+		}
+
+		let cursor = course_ids
+			.last_raw_key()
+			.to_vec()
+			.try_into()
+			.expect("a course key is shorter than MAX_CURSOR_LEN; qed");
+		(Some(cursor), used)
+	}
+}
+
+/// Counts the accounts owning Courses into `OwnerCount`, and sums the fixed prices of the Courses
+/// for sale into `ListedValue`, for storage version 2.
+pub struct CountMarketStats<T>(PhantomData<T>);
+
+impl<T: Config> SteppedMigration for CountMarketStats<T> {
+	const VERSION: u16 = 2;
+
+	fn step(cursor: &[u8], limit: Weight) -> (Option<Cursor>, Weight) {
+		let per_course = T::DbWeight::get().reads_writes(4, 2);
+		// Only the keys are decoded, the values being in the format of version 1.
+		let (mut course_ids, mut used) = if cursor.is_empty() {
+			<OwnerCount<T>>::kill();
+			<ListedValue<T>>::kill();
+			(<Courses<T>>::iter_keys(), T::DbWeight::get().writes(2))
+		} else {
+			(<Courses<T>>::iter_keys_from(cursor.to_vec()), 0)
+		};
+
+		while used.saturating_add(per_course) <= limit {
+			match course_ids.next() {
+				Some(course_id) => {
+					let key = <Courses<T>>::hashed_key_for(&course_id);
+					if let Some(course) = unhashed::get::<CourseV2Of<T>>(&key) {
+						// Every owner is counted at its first Course.
+						if <CoursesOwned<T>>::get(&course.owner).first() == Some(&course_id) {
+							<OwnerCount<T>>::mutate(|n| *n = n.saturating_add(1));
+						}
+						if let Some(price) = course.price {
+							<ListedValue<T>>::mutate(|value| *value = value.saturating_add(price));
+						}
+					}
+					used = used.saturating_add(per_course);
+				},
+				None => return (None, used),
+			}
+		}
+
+		let cursor = course_ids
+			.last_raw_key()
+			.to_vec()
+			.try_into()
+			.expect("a course key is shorter than MAX_CURSOR_LEN; qed");
+		(Some(cursor), used)
+	}
+}
+
+/// Extends the 16-byte DNA of every Course to 32 bytes with `dna::extend`, for storage version 3.
+/// Course IDs are left as they are.
+pub struct ExtendDna<T>(PhantomData<T>);
+
+impl<T: Config> SteppedMigration for ExtendDna<T> {
+	const VERSION: u16 = 3;
+
+	fn step(cursor: &[u8], limit: Weight) -> (Option<Cursor>, Weight) {
+		let per_course = T::DbWeight::get().reads_writes(1, 1);
+		// Only the keys are decoded, the values being in the old format.
+		let mut course_ids = if cursor.is_empty() {
+			<Courses<T>>::iter_keys()
+		} else {
+			<Courses<T>>::iter_keys_from(cursor.to_vec())
+		};
+
+		let mut used: Weight = 0;
+		while used.saturating_add(per_course) <= limit {
+			match course_ids.next() {
+				Some(course_id) => {
+					let key = <Courses<T>>::hashed_key_for(&course_id);
+					if let Some(old) = unhashed::get::<CourseV2Of<T>>(&key) {
+						let course = CourseV4Of::<T> {
+							dna: dna::extend(&old.dna),
+							price: old.price,
+							course_year: old.course_year,
+							owner: old.owner,
+						};
+						unhashed::put(&key, &course);
+					}
+					used = used.saturating_add(per_course);
+				},
+				None => return (None, used),
+			}
+		}
+
+		let cursor = course_ids
+			.last_raw_key()
+			.to_vec()
+			.try_into()
+			.expect("a course key is shorter than MAX_CURSOR_LEN; qed");
+		(Some(cursor), used)
+	}
+}
+
+/// Counts the Courses of every year into the `minted` statistic of `YearStatistics`, for storage
+/// version 4. Courses burned and sold before are not counted.
+pub struct CountYearStats<T>(PhantomData<T>);
+
+impl<T: Config> SteppedMigration for CountYearStats<T> {
+	const VERSION: u16 = 4;
+
+	fn step(cursor: &[u8], limit: Weight) -> (Option<Cursor>, Weight) {
+		let per_course = T::DbWeight::get().reads_writes(2, 1);
+		// Only the keys are decoded, the values being in the format of version 3.
+		let mut course_ids = if cursor.is_empty() {
+			<Courses<T>>::iter_keys()
+		} else {
+			<Courses<T>>::iter_keys_from(cursor.to_vec())
+		};
+
+		let mut used: Weight = 0;
+		while used.saturating_add(per_course) <= limit {
+			match course_ids.next() {
+				Some(course_id) => {
+					let key = <Courses<T>>::hashed_key_for(&course_id);
+					if let Some(course) = unhashed::get::<CourseV4Of<T>>(&key) {
+						<YearStatistics<T>>::mutate(&course.course_year, |stats| {
+							stats.minted = stats.minted.saturating_add(1)
+						});
+					}
+					used = used.saturating_add(per_course);
+				},
+				None => return (None, used),
+			}
+		}
+
+		let cursor = course_ids
+			.last_raw_key()
+			.to_vec()
+			.try_into()
+			.expect("a course key is shorter than MAX_CURSOR_LEN; qed");
+		(Some(cursor), used)
+	}
+}
+
+/// Adds an empty `blobs_root` to every Course, for storage version 5.
+pub struct AddBlobsRoot<T>(PhantomData<T>);
+
+impl<T: Config> SteppedMigration for AddBlobsRoot<T> {
+	const VERSION: u16 = 5;
+
+	fn step(cursor: &[u8], limit: Weight) -> (Option<Cursor>, Weight) {
+		let per_course = T::DbWeight::get().reads_writes(1, 1);
+		// Only the keys are decoded, the values being in the old format.
+		let mut course_ids = if cursor.is_empty() {
+			<Courses<T>>::iter_keys()
+		} else {
+			<Courses<T>>::iter_keys_from(cursor.to_vec())
+		};
+
+		let mut used: Weight = 0;
+		while used.saturating_add(per_course) <= limit {
+			match course_ids.next() {
+				Some(course_id) => {
+					let key = <Courses<T>>::hashed_key_for(&course_id);
+					if let Some(old) = unhashed::get::<CourseV4Of<T>>(&key) {
+						let course = Course::<T> {
+							dna: old.dna,
+							price: old.price,
+							course_year: old.course_year,
+							owner: old.owner,
+							blobs_root: None,
+						};
+						<Courses<T>>::insert(&course_id, course);
+					}
+					used = used.saturating_add(per_course);
+				},
+				None => return (None, used),
+			}
+		}
+
+		let cursor = course_ids
+			.last_raw_key()
+			.to_vec()
+			.try_into()
+			.expect("a course key is shorter than MAX_CURSOR_LEN; qed");
+		(Some(cursor), used)
+	}
+}
@@ -0,0 +1,215 @@
+//! A runtime with the pallet and the pallets it depends on, for the unit tests.
+
+use crate as pallet_course_passing;
+use crate::{migrations, RefundSchedule};
+use frame_support::{
+	parameter_types,
+	traits::{
+		ConstBool, ConstU16, ConstU32, ConstU64, Everything, Hooks, Randomness, StorageMapShim,
+	},
+	weights::constants::RocksDbWeight,
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, ConvertInto, Hash, IdentityLookup},
+	BuildStorage, Perbill,
+};
+use std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
+/// The department collecting the transfer fees.
+pub const DEPARTMENT: u64 = 10;
+
+/// The balance of every account at genesis, in both currencies.
+pub const ENDOWMENT: u64 = 10_000;
+pub const COURSE_DEPOSIT: u64 = 10;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		StableBalances: pallet_balances::<Instance2>::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Vesting: pallet_vesting,
+		CourseGrading: pallet_course_passing,
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	/// Weighs storage accesses, so migrations step through a limited number of Courses.
+	type DbWeight = RocksDbWeight;
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+/// The stablecoin, kept apart from `Balances`.
+impl pallet_balances::Config<pallet_balances::Instance2> for Test {
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = StorageMapShim<
+		pallet_balances::Account<Test, pallet_balances::Instance2>,
+		frame_system::Provider<Test>,
+		u64,
+		pallet_balances::AccountData<u64>,
+	>;
+	type WeightInfo = ();
+}
+
+impl pallet_vesting::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = ConstU64<1>;
+	type WeightInfo = ();
+	const MAX_VESTING_SCHEDULES: u32 = 28;
+}
+
+thread_local! {
+	static NONCE: RefCell<u64> = RefCell::new(0);
+}
+
+/// Randomness that differs on every call, so Courses minted in the same block differ too.
+pub struct TestRandomness;
+
+impl Randomness<H256, u64> for TestRandomness {
+	fn random(subject: &[u8]) -> (H256, u64) {
+		let nonce = NONCE.with(|nonce| {
+			*nonce.borrow_mut() += 1;
+			*nonce.borrow()
+		});
+		(BlakeTwo256::hash_of(&(subject, nonce)), System::block_number())
+	}
+}
+
+parameter_types! {
+	pub CourseRefunds: RefundSchedule<u64> = RefundSchedule {
+		full_refund_period: 10,
+		late_refund: Perbill::from_percent(50),
+	};
+	pub UnrevealedForfeit: Perbill = Perbill::from_percent(10);
+}
+
+impl pallet_course_passing::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type CourseDeposit = ConstU64<COURSE_DEPOSIT>;
+	type DepositRefundSchedule = CourseRefunds;
+	type DepositPerByte = ConstU64<1>;
+	type MaxCoursesOwned = ConstU32<16>;
+	type CoursesOwnedLimit = ConstU32<16>;
+	type MaxMintsPerSession = ConstU32<8>;
+	type CurrentSession = ConstU32<0>;
+	type MaxCoursesPerYear = ConstU32<8>;
+	type CourseRandomness = TestRandomness;
+	type BreedSameYearOnly = ConstBool<true>;
+	type MinBreedingAge = ConstU64<0>;
+	type MaxCidLength = ConstU32<64>;
+	type MaxMetadataHistory = ConstU32<4>;
+	type MaxPendingPins = ConstU32<8>;
+	type MaxRecentSales = ConstU32<8>;
+	type MaxRecentTrades = ConstU32<8>;
+	type MaxStatsSessions = ConstU32<4>;
+	type MaxBundleSize = ConstU32<4>;
+	type TransferCooldown = ConstU64<0>;
+	type BuyerFilter = Everything;
+	type ForceOrigin = EnsureRoot<u64>;
+	type PauseOrigin = EnsureRoot<u64>;
+	type OfferExpiry = ConstU64<10>;
+	type Migration = (
+		migrations::CountOwnedCourses<Test>,
+		migrations::CountMarketStats<Test>,
+		migrations::ExtendDna<Test>,
+		migrations::CountYearStats<Test>,
+		migrations::AddBlobsRoot<Test>,
+	);
+	type OnTransfer = ();
+	type OnPurchase = ();
+	type Vesting = Vesting;
+	type VestingThreshold = ConstU64<1_000>;
+	type ProceedsVestingPeriod = ConstU64<100>;
+	type StableCurrency = StableBalances;
+	type SealedCommitPeriod = ConstU64<5>;
+	type SealedRevealPeriod = ConstU64<5>;
+	type UnrevealedForfeit = UnrevealedForfeit;
+	type WatchDeposit = ConstU64<1>;
+	type MaxWatchers = ConstU32<4>;
+	type MaxArchiveBatch = ConstU32<8>;
+	type MaxBlobLen = ConstU32<64>;
+	type WeightInfo = ();
+}
+
+/// Externalities at block 1 of a chain where Alice, Bob, Charlie and the department hold
+/// `ENDOWMENT` of both currencies.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let endowed =
+		vec![(ALICE, ENDOWMENT), (BOB, ENDOWMENT), (CHARLIE, ENDOWMENT), (DEPARTMENT, ENDOWMENT)];
+	let storage = GenesisConfig {
+		balances: BalancesConfig { balances: endowed.clone() },
+		stable_balances: StableBalancesConfig { balances: endowed },
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(storage);
+	// Events are not recorded in the genesis block.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Run the hooks of the pallet up to block `n`.
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		CourseGrading::on_finalize(System::block_number());
+		System::set_block_number(System::block_number() + 1);
+		CourseGrading::on_initialize(System::block_number());
+	}
+}
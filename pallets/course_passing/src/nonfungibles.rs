@@ -0,0 +1,89 @@
+//! Implementations of the `nonfungibles` traits, exposing courses as non-fungible tokens.
+//!
+//! Each `CourseYear` is a class and the course ID identifies the instance within it. Pallets that
+//! work with any non-fungible asset can use these implementations to handle courses.
+
+use super::*;
+use codec::Encode;
+use frame_support::{
+	dispatch::DispatchResult,
+	traits::tokens::nonfungibles::{Inspect, InspectEnumerable, Transfer},
+};
+use sp_std::{boxed::Box, vec::Vec};
+
+impl<T: Config> Inspect<T::AccountId> for Pallet<T> {
+	type InstanceId = T::Hash;
+	type ClassId = CourseYear;
+
+	fn owner(class: &Self::ClassId, instance: &Self::InstanceId) -> Option<T::AccountId> {
+		Self::courses(instance)
+			.filter(|course| course.course_year == *class)
+			.map(|c| c.owner)
+	}
+
+	/// Courses expose their `dna` and the `traits` it encodes, their current `price`, and the
+	/// `syllabus` and `materials` CIDs.
+	fn attribute(
+		class: &Self::ClassId,
+		instance: &Self::InstanceId,
+		key: &[u8],
+	) -> Option<Vec<u8>> {
+		let course = Self::courses(instance).filter(|course| course.course_year == *class)?;
+		match key {
+			b"dna" => Some(course.dna.encode()),
+			b"traits" => Some(dna::traits(&course.dna).encode()),
+			b"price" => Self::ask_price(instance).map(|price| price.encode()),
+			b"syllabus" => Self::course_content(instance)?.syllabus.map(|cid| cid.into_inner()),
+			b"materials" => Self::course_content(instance)?.materials.map(|cid| cid.into_inner()),
+			_ => None,
+		}
+	}
+}
+
+impl<T: Config> InspectEnumerable<T::AccountId> for Pallet<T> {
+	fn classes() -> Box<dyn Iterator<Item = Self::ClassId>> {
+		Box::new(
+			[CourseYear::First, CourseYear::Second, CourseYear::Third, CourseYear::Fourth]
+				.into_iter(),
+		)
+	}
+
+	fn instances(class: &Self::ClassId) -> Box<dyn Iterator<Item = Self::InstanceId>> {
+		let class = class.clone();
+		Box::new(
+			<Courses<T>>::iter()
+				.filter(move |(_, course)| course.course_year == class)
+				.map(|(course_id, _)| course_id),
+		)
+	}
+
+	fn owned(who: &T::AccountId) -> Box<dyn Iterator<Item = (Self::ClassId, Self::InstanceId)>> {
+		Box::new(<CoursesOwned<T>>::get(who).into_iter().filter_map(|course_id| {
+			Self::courses(&course_id).map(|course| (course.course_year, course_id))
+		}))
+	}
+
+	fn owned_in_class(
+		class: &Self::ClassId,
+		who: &T::AccountId,
+	) -> Box<dyn Iterator<Item = Self::InstanceId>> {
+		let class = class.clone();
+		Box::new(
+			Self::owned(who)
+				.filter(move |(course_year, _)| *course_year == class)
+				.map(|(_, course_id)| course_id),
+		)
+	}
+}
+
+impl<T: Config> Transfer<T::AccountId> for Pallet<T> {
+	fn transfer(
+		class: &Self::ClassId,
+		instance: &Self::InstanceId,
+		destination: &T::AccountId,
+	) -> DispatchResult {
+		let owner = Self::owner(class, instance).ok_or(<Error<T>>::CourseNotExist)?;
+		Self::transfer_approved(&owner, destination, instance)?;
+		Ok(())
+	}
+}
@@ -0,0 +1,75 @@
+//! Snapshots of the Courses every account owns of a year, for airdrops and votes.
+//!
+//! Governance starts a snapshot of a year with `snapshot_owners`, then `on_idle` walks
+//! `OwnedPerYear` over as many blocks as it takes. Accounts whose holdings change while the walk
+//! has not reached them yet are recorded as they were, so the snapshot is of the holdings at the
+//! block it was started in, whenever it completes.
+//!
+//! The snapshot is stored as the root of a binary Merkle tree. Its leaves are the [`leaf`]s of
+//! every account owning at least one Course of the year, in the storage order of `OwnedPerYear`,
+//! and its inner nodes the [`node`]s of their two children. A node without a sibling is carried
+//! up to the next level unchanged. Holdings are checked against the root with [`verify`].
+
+use codec::Encode;
+use frame_support::{sp_runtime::traits::Hash, traits::ConstU32, BoundedVec};
+use sp_std::mem;
+
+/// The maximum height of a snapshot tree, enough for `u32::MAX` leaves.
+pub const MAX_HEIGHT: u32 = 32;
+
+/// The subtrees of a snapshot in progress not yet merged into a larger one, by height.
+pub type Frontier<Output> = BoundedVec<Option<Output>, ConstU32<MAX_HEIGHT>>;
+
+/// The leaf of `who` owning `count` Courses.
+pub fn leaf<H: Hash, AccountId: Encode>(who: &AccountId, count: u32) -> H::Output {
+	H::hash_of(&(who, count))
+}
+
+/// The parent of two nodes.
+pub fn node<H: Hash>(left: &H::Output, right: &H::Output) -> H::Output {
+	H::hash_of(&(left, right))
+}
+
+/// Add the next leaf to the tree.
+pub fn push<H: Hash>(frontier: &mut Frontier<H::Output>, leaf: H::Output) {
+	let mut subtrees = mem::take(frontier).into_inner();
+	let mut hash = leaf;
+	let mut height = 0;
+	// Merge the new subtree with those of equal height until there is none.
+	while let Some(left) = subtrees.get_mut(height).and_then(Option::take) {
+		hash = node::<H>(&left, &hash);
+		height += 1;
+	}
+	match subtrees.get_mut(height) {
+		Some(slot) => *slot = Some(hash),
+		None => subtrees.push(Some(hash)),
+	}
+	*frontier = subtrees.try_into().expect("snapshots have fewer than 2^MAX_HEIGHT leaves; qed");
+}
+
+/// The root of the tree, or the default hash if it has no leaves.
+pub fn root<H: Hash>(frontier: &Frontier<H::Output>) -> H::Output {
+	frontier
+		.iter()
+		.flatten()
+		.fold(None, |right, left| match right {
+			Some(right) => Some(node::<H>(left, &right)),
+			None => Some(*left),
+		})
+		.unwrap_or_default()
+}
+
+/// Whether `leaf` is in the tree of `root`.
+///
+/// `proof` lists the siblings on the path from the leaf to the root, bottom up, each with whether
+/// it is the left one. Levels where the path has no sibling are skipped.
+pub fn verify<H: Hash>(root: &H::Output, leaf: H::Output, proof: &[(H::Output, bool)]) -> bool {
+	let computed = proof.iter().fold(leaf, |hash, (sibling, is_left)| {
+		if *is_left {
+			node::<H>(sibling, &hash)
+		} else {
+			node::<H>(&hash, sibling)
+		}
+	});
+	computed == *root
+}
@@ -0,0 +1,517 @@
+use crate::{
+	migrations::{self, SteppedMigration},
+	mock::*,
+	CourseYear, Courses, Error, TransferFee,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::DispatchResult,
+	storage::unhashed,
+	traits::{Get, Hooks, StorageVersion},
+	weights::Weight,
+};
+use scale_info::{TypeDef, TypeInfo};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+#[test]
+fn call_indices_do_not_change() {
+	// Wallets encode calls by index, which FRAME derives from the order the calls are declared
+	// in. New calls go at the end of the call block.
+	let calls = match crate::Call::<Test>::type_info().type_def() {
+		TypeDef::Variant(calls) => calls
+			.variants()
+			.iter()
+			.map(|call| (*call.name(), call.index()))
+			.collect::<Vec<_>>(),
+		_ => unreachable!("calls are an enum"),
+	};
+	assert_eq!(
+		calls,
+		vec![
+			("create_course", 0),
+			("set_price", 1),
+			("transfer", 2),
+			("buy_course", 3),
+			("breed_course", 4),
+			("approve", 5),
+			("set_content", 6),
+			("list_dutch", 7),
+			("create_bundle", 8),
+			("cancel_bundle", 9),
+			("buy_bundle", 10),
+			("propose_swap", 11),
+			("cancel_swap", 12),
+			("accept_swap", 13),
+			("offer_transfer", 14),
+			("claim_transfer", 15),
+			("reject_transfer", 16),
+			("set_accepts_transfers", 17),
+			("set_cooldown_exempt", 18),
+			("ban_account", 19),
+			("unban_account", 20),
+			("clone_course", 21),
+			("set_year_limit", 22),
+			("update_metadata", 23),
+			("set_paused", 24),
+			("burn_course", 25),
+			("snapshot_owners", 26),
+			("tip_creator", 27),
+			("set_stable_price", 28),
+			("buy_course_in_stable", 29),
+			("set_transfer_fee", 30),
+			("list_sealed", 31),
+			("commit_purchase", 32),
+			("reveal_purchase", 33),
+			("settle_sealed", 34),
+			("withdraw_sealed_bid", 35),
+			("list_second_price", 36),
+			("watch", 37),
+			("unwatch", 38),
+			("archive_term", 39),
+			("set_blob", 40),
+		]
+	);
+}
+
+/// Mint a Course of the first year for `who`.
+fn create_course(who: u64) -> H256 {
+	assert_ok!(CourseGrading::create_course(Origin::signed(who)));
+	*CourseGrading::courses_owned(who).last().unwrap()
+}
+
+/// Charge `FEE` to the department on every change of owner of the Courses of the first year.
+fn set_fee() {
+	assert_ok!(CourseGrading::set_transfer_fee(
+		Origin::root(),
+		CourseYear::First,
+		Some(TransferFee { amount: FEE, department: DEPARTMENT }),
+	));
+}
+
+const FEE: u64 = 5;
+
+fn owner(course_id: &H256) -> u64 {
+	CourseGrading::courses(course_id).unwrap().owner
+}
+
+#[test]
+fn create_course_reserves_the_deposit() {
+	new_test_ext().execute_with(|| {
+		let course_id = create_course(ALICE);
+
+		assert_eq!(owner(&course_id), ALICE);
+		assert_eq!(CourseGrading::count_for_courses(), 1);
+		assert_eq!(CourseGrading::owned_count(ALICE), 1);
+		assert_eq!(Balances::reserved_balance(ALICE), COURSE_DEPOSIT);
+		System::assert_has_event(Event::CourseGrading(crate::Event::Created(ALICE, course_id)));
+	});
+}
+
+#[test]
+fn buy_course_pays_the_seller() {
+	new_test_ext().execute_with(|| {
+		let course_id = create_course(ALICE);
+		assert_ok!(CourseGrading::set_price(Origin::signed(ALICE), course_id, Some(100)));
+
+		assert_noop!(
+			CourseGrading::buy_course(Origin::signed(BOB), course_id, 99, false),
+			Error::<Test>::CourseBidPriceTooLow
+		);
+		assert_ok!(CourseGrading::buy_course(Origin::signed(BOB), course_id, 100, false));
+
+		assert_eq!(owner(&course_id), BOB);
+		assert_eq!(CourseGrading::courses(&course_id).unwrap().price, None);
+		assert_eq!(Balances::free_balance(BOB), ENDOWMENT - 100);
+		assert_eq!(Balances::free_balance(ALICE), ENDOWMENT - COURSE_DEPOSIT + 100);
+		assert_eq!(CourseGrading::year_stats(CourseYear::First).trades, 1);
+		System::assert_has_event(Event::CourseGrading(crate::Event::Bought(
+			BOB, ALICE, course_id, 100,
+		)));
+	});
+}
+
+#[test]
+fn buy_course_rejects_unlisted_courses() {
+	new_test_ext().execute_with(|| {
+		let course_id = create_course(ALICE);
+
+		assert_noop!(
+			CourseGrading::buy_course(Origin::signed(BOB), course_id, 100, false),
+			Error::<Test>::CourseNotForSale
+		);
+		assert_noop!(
+			CourseGrading::buy_course(Origin::signed(ALICE), course_id, 100, false),
+			Error::<Test>::BuyerIsCourseOwner
+		);
+	});
+}
+
+#[test]
+fn transfer_needs_the_recipient_to_accept() {
+	new_test_ext().execute_with(|| {
+		let course_id = create_course(ALICE);
+
+		assert_noop!(
+			CourseGrading::transfer(Origin::signed(ALICE), BOB, course_id),
+			Error::<Test>::DirectTransferNotAccepted
+		);
+		assert_ok!(CourseGrading::set_accepts_transfers(Origin::signed(BOB), true));
+		assert_noop!(
+			CourseGrading::transfer(Origin::signed(CHARLIE), BOB, course_id),
+			Error::<Test>::NotCourseOwner
+		);
+		assert_ok!(CourseGrading::transfer(Origin::signed(ALICE), BOB, course_id));
+
+		assert_eq!(owner(&course_id), BOB);
+		assert_eq!(CourseGrading::owned_count(ALICE), 0);
+		assert_eq!(CourseGrading::owned_count(BOB), 1);
+	});
+}
+
+#[test]
+fn offered_courses_are_claimed_by_the_recipient_only() {
+	new_test_ext().execute_with(|| {
+		let course_id = create_course(ALICE);
+		assert_ok!(CourseGrading::offer_transfer(Origin::signed(ALICE), BOB, course_id));
+
+		assert_noop!(
+			CourseGrading::claim_transfer(Origin::signed(CHARLIE), course_id),
+			Error::<Test>::NoTransferOffer
+		);
+		assert_ok!(CourseGrading::claim_transfer(Origin::signed(BOB), course_id));
+		assert_eq!(owner(&course_id), BOB);
+	});
+}
+
+#[test]
+fn transfer_fee_is_paid_by_the_sender() {
+	new_test_ext().execute_with(|| {
+		set_fee();
+		let course_id = create_course(ALICE);
+		assert_ok!(CourseGrading::set_accepts_transfers(Origin::signed(BOB), true));
+
+		assert_ok!(CourseGrading::transfer(Origin::signed(ALICE), BOB, course_id));
+
+		assert_eq!(Balances::free_balance(ALICE), ENDOWMENT - COURSE_DEPOSIT - FEE);
+		assert_eq!(Balances::free_balance(DEPARTMENT), ENDOWMENT + FEE);
+		System::assert_has_event(Event::CourseGrading(crate::Event::TransferFeePaid(
+			ALICE, DEPARTMENT, course_id, FEE,
+		)));
+	});
+}
+
+#[test]
+fn transfer_fee_of_a_claimed_offer_is_paid_by_the_sender() {
+	new_test_ext().execute_with(|| {
+		set_fee();
+		let course_id = create_course(ALICE);
+		assert_ok!(CourseGrading::offer_transfer(Origin::signed(ALICE), BOB, course_id));
+
+		assert_ok!(CourseGrading::claim_transfer(Origin::signed(BOB), course_id));
+
+		assert_eq!(Balances::free_balance(ALICE), ENDOWMENT - COURSE_DEPOSIT - FEE);
+		assert_eq!(Balances::free_balance(BOB), ENDOWMENT);
+		assert_eq!(Balances::free_balance(DEPARTMENT), ENDOWMENT + FEE);
+	});
+}
+
+#[test]
+fn transfer_fee_of_a_sale_is_paid_by_the_buyer() {
+	new_test_ext().execute_with(|| {
+		set_fee();
+		let course_id = create_course(ALICE);
+		assert_ok!(CourseGrading::set_price(Origin::signed(ALICE), course_id, Some(100)));
+
+		assert_ok!(CourseGrading::buy_course(Origin::signed(BOB), course_id, 100, false));
+
+		assert_eq!(Balances::free_balance(BOB), ENDOWMENT - 100 - FEE);
+		assert_eq!(Balances::free_balance(ALICE), ENDOWMENT - COURSE_DEPOSIT + 100);
+		assert_eq!(Balances::free_balance(DEPARTMENT), ENDOWMENT + FEE);
+	});
+}
+
+#[test]
+fn transfer_fee_is_paid_for_every_course_of_a_bundle() {
+	new_test_ext().execute_with(|| {
+		set_fee();
+		let courses = vec![create_course(ALICE), create_course(ALICE)];
+		assert_ok!(CourseGrading::create_bundle(
+			Origin::signed(ALICE),
+			courses.clone().try_into().unwrap(),
+			100,
+		));
+
+		assert_ok!(CourseGrading::buy_bundle(Origin::signed(BOB), 0, 100));
+
+		assert!(courses.iter().all(|course_id| owner(course_id) == BOB));
+		assert_eq!(Balances::free_balance(BOB), ENDOWMENT - 100 - 2 * FEE);
+		assert_eq!(Balances::free_balance(DEPARTMENT), ENDOWMENT + 2 * FEE);
+	});
+}
+
+#[test]
+fn transfer_fee_of_a_swap_is_paid_by_each_party() {
+	new_test_ext().execute_with(|| {
+		set_fee();
+		let offered = create_course(ALICE);
+		let requested = create_course(BOB);
+		assert_ok!(CourseGrading::propose_swap(
+			Origin::signed(ALICE),
+			offered,
+			requested,
+			BOB,
+			None,
+		));
+
+		assert_ok!(CourseGrading::accept_swap(Origin::signed(BOB), 0));
+
+		assert_eq!((owner(&offered), owner(&requested)), (BOB, ALICE));
+		assert_eq!(Balances::free_balance(ALICE), ENDOWMENT - COURSE_DEPOSIT - FEE);
+		assert_eq!(Balances::free_balance(BOB), ENDOWMENT - COURSE_DEPOSIT - FEE);
+		assert_eq!(Balances::free_balance(DEPARTMENT), ENDOWMENT + 2 * FEE);
+	});
+}
+
+#[test]
+fn transfer_fee_of_a_sale_in_the_stablecoin_is_paid_in_currency() {
+	new_test_ext().execute_with(|| {
+		set_fee();
+		let course_id = create_course(ALICE);
+		assert_ok!(CourseGrading::set_stable_price(Origin::signed(ALICE), course_id, Some(50)));
+
+		assert_ok!(CourseGrading::buy_course_in_stable(Origin::signed(BOB), course_id, 50));
+
+		assert_eq!(owner(&course_id), BOB);
+		assert_eq!(StableBalances::free_balance(BOB), ENDOWMENT - 50);
+		assert_eq!(StableBalances::free_balance(ALICE), ENDOWMENT + 50);
+		assert_eq!(Balances::free_balance(BOB), ENDOWMENT - FEE);
+		assert_eq!(Balances::free_balance(DEPARTMENT), ENDOWMENT + FEE);
+	});
+}
+
+#[test]
+fn failed_fee_payment_reverts_the_transfer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CourseGrading::set_transfer_fee(
+			Origin::root(),
+			CourseYear::First,
+			Some(TransferFee { amount: ENDOWMENT, department: DEPARTMENT }),
+		));
+		let course_id = create_course(ALICE);
+		assert_ok!(CourseGrading::offer_transfer(Origin::signed(ALICE), BOB, course_id));
+
+		assert!(CourseGrading::claim_transfer(Origin::signed(BOB), course_id).is_err());
+		assert_eq!(owner(&course_id), ALICE);
+		assert_eq!(Balances::free_balance(DEPARTMENT), ENDOWMENT);
+	});
+}
+
+/// The commitment of `bidder` to `bid` on `course_id`.
+fn commitment(course_id: H256, bidder: u64, bid: u64, salt: H256) -> H256 {
+	BlakeTwo256::hash_of(&(course_id, &bidder, bid, salt))
+}
+
+/// List a Course of Alice for sealed bids of at least 100 in block 1, its commit period ending
+/// in block 6 and its reveal period in block 11, with bids of Bob and Charlie.
+fn sealed_listing(second_price: bool) -> H256 {
+	let course_id = create_course(ALICE);
+	if second_price {
+		assert_ok!(CourseGrading::list_second_price(Origin::signed(ALICE), course_id, 100));
+	} else {
+		assert_ok!(CourseGrading::list_sealed(Origin::signed(ALICE), course_id, 100));
+	}
+	for (bidder, bid, reserve) in [(BOB, 150, 200), (CHARLIE, 120, 120)] {
+		assert_ok!(CourseGrading::commit_purchase(
+			Origin::signed(bidder),
+			course_id,
+			commitment(course_id, bidder, bid, H256::repeat_byte(bidder as u8)),
+			reserve,
+		));
+	}
+	assert_eq!(Balances::reserved_balance(BOB), 200);
+	course_id
+}
+
+fn reveal(course_id: H256, bidder: u64, bid: u64) -> DispatchResult {
+	CourseGrading::reveal_purchase(
+		Origin::signed(bidder),
+		course_id,
+		bid,
+		H256::repeat_byte(bidder as u8),
+	)
+}
+
+#[test]
+fn sealed_listing_is_sold_to_the_highest_bid() {
+	new_test_ext().execute_with(|| {
+		let course_id = sealed_listing(false);
+
+		assert_noop!(reveal(course_id, BOB, 150), Error::<Test>::NotRevealPhase);
+		run_to_block(7);
+		assert_ok!(reveal(course_id, BOB, 150));
+		assert_ok!(reveal(course_id, CHARLIE, 120));
+		assert_noop!(
+			CourseGrading::settle_sealed(Origin::signed(CHARLIE), course_id),
+			Error::<Test>::SealedListingOpen
+		);
+		run_to_block(12);
+		assert_ok!(CourseGrading::settle_sealed(Origin::signed(CHARLIE), course_id));
+
+		assert_eq!(owner(&course_id), BOB);
+		assert_eq!(Balances::free_balance(BOB), ENDOWMENT - 150);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(CHARLIE), ENDOWMENT);
+		assert_eq!(Balances::free_balance(ALICE), ENDOWMENT - COURSE_DEPOSIT + 150);
+	});
+}
+
+#[test]
+fn second_price_listing_is_sold_at_the_second_bid() {
+	new_test_ext().execute_with(|| {
+		set_fee();
+		let course_id = sealed_listing(true);
+
+		run_to_block(7);
+		assert_ok!(reveal(course_id, BOB, 150));
+		assert_ok!(reveal(course_id, CHARLIE, 120));
+		run_to_block(12);
+		assert_ok!(CourseGrading::settle_sealed(Origin::signed(ALICE), course_id));
+
+		assert_eq!(owner(&course_id), BOB);
+		assert_eq!(Balances::free_balance(BOB), ENDOWMENT - 120 - FEE);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(ALICE), ENDOWMENT - COURSE_DEPOSIT + 120);
+		assert_eq!(Balances::free_balance(DEPARTMENT), ENDOWMENT + FEE);
+	});
+}
+
+#[test]
+fn sealed_commitments_cannot_be_copied() {
+	new_test_ext().execute_with(|| {
+		let course_id = create_course(ALICE);
+		assert_ok!(CourseGrading::list_sealed(Origin::signed(ALICE), course_id, 100));
+		let salt = H256::repeat_byte(1);
+		let bobs = commitment(course_id, BOB, 150, salt);
+		assert_ok!(CourseGrading::commit_purchase(Origin::signed(BOB), course_id, bobs, 200));
+		assert_ok!(CourseGrading::commit_purchase(Origin::signed(CHARLIE), course_id, bobs, 200));
+
+		run_to_block(7);
+		assert_noop!(
+			CourseGrading::reveal_purchase(Origin::signed(CHARLIE), course_id, 150, salt),
+			Error::<Test>::InvalidSealedReveal
+		);
+		assert_ok!(CourseGrading::reveal_purchase(Origin::signed(BOB), course_id, 150, salt));
+	});
+}
+
+#[test]
+fn unrevealed_second_price_bids_forfeit_part_of_their_reserve() {
+	new_test_ext().execute_with(|| {
+		let course_id = sealed_listing(true);
+
+		run_to_block(7);
+		assert_ok!(reveal(course_id, CHARLIE, 120));
+		assert_noop!(
+			CourseGrading::withdraw_sealed_bid(Origin::signed(BOB), course_id),
+			Error::<Test>::SealedListingOpen
+		);
+		run_to_block(12);
+		assert_ok!(CourseGrading::withdraw_sealed_bid(Origin::signed(BOB), course_id));
+
+		// A tenth of the reserve of 200.
+		assert_eq!(Balances::free_balance(BOB), ENDOWMENT - 20);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		System::assert_has_event(Event::CourseGrading(crate::Event::SealedBidWithdrawn(
+			BOB, course_id, 20,
+		)));
+	});
+}
+
+#[test]
+fn bids_that_could_not_be_revealed_are_not_forfeited() {
+	new_test_ext().execute_with(|| {
+		let course_id = sealed_listing(true);
+
+		run_to_block(7);
+		assert_ok!(CourseGrading::set_paused(Origin::root(), true));
+		run_to_block(8);
+		assert_noop!(reveal(course_id, BOB, 150), Error::<Test>::PalletPaused);
+		assert_ok!(CourseGrading::set_paused(Origin::root(), false));
+		run_to_block(12);
+		assert_ok!(CourseGrading::withdraw_sealed_bid(Origin::signed(BOB), course_id));
+
+		assert_eq!(Balances::free_balance(BOB), ENDOWMENT);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+/// Store a Course of Alice in the format of storage versions 3 and 4, with a 32-byte DNA and
+/// without blobs.
+fn insert_course_v4(dna: [u8; 32]) -> H256 {
+	let course_id = H256::repeat_byte(dna[0]);
+	let course = (dna, Some(100u64), CourseYear::First, ALICE);
+	unhashed::put(&Courses::<Test>::hashed_key_for(&course_id), &course);
+	course_id
+}
+
+#[test]
+fn add_blobs_root_migrates_every_course() {
+	new_test_ext().execute_with(|| {
+		let courses = [insert_course_v4([1; 32]), insert_course_v4([2; 32])];
+
+		assert_eq!(migrations::AddBlobsRoot::<Test>::step(&[], Weight::MAX).0, None);
+
+		for (course_id, dna) in courses.iter().zip([[1; 32], [2; 32]]) {
+			let course = CourseGrading::courses(course_id).unwrap();
+			assert_eq!(course.dna, dna);
+			assert_eq!(course.price, Some(100));
+			assert_eq!(course.owner, ALICE);
+			assert_eq!(course.blobs_root, None);
+		}
+	});
+}
+
+#[test]
+fn stepped_migration_resumes_from_its_cursor() {
+	new_test_ext().execute_with(|| {
+		for byte in 1..=3 {
+			insert_course_v4([byte; 32]);
+		}
+
+		let per_course = <Test as frame_system::Config>::DbWeight::get().reads_writes(2, 1);
+		let (cursor, _) = migrations::CountYearStats::<Test>::step(&[], per_course);
+		let cursor = cursor.expect("two Courses are left");
+		assert_eq!(CourseGrading::year_stats(CourseYear::First).minted, 1);
+
+		assert_eq!(migrations::CountYearStats::<Test>::step(&cursor, Weight::MAX).0, None);
+		assert_eq!(CourseGrading::year_stats(CourseYear::First).minted, 3);
+	});
+}
+
+#[test]
+fn runtime_upgrade_migrates_the_storage_over_several_blocks() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(3).put::<CourseGrading>();
+		let course_id = insert_course_v4([1; 32]);
+
+		CourseGrading::on_runtime_upgrade();
+		assert!(CourseGrading::migration_cursor().is_some());
+		assert_noop!(
+			CourseGrading::create_course(Origin::signed(ALICE)),
+			Error::<Test>::MigrationOngoing
+		);
+
+		// One block for each of the two migrations.
+		CourseGrading::on_idle(2, Weight::MAX);
+		assert_eq!(StorageVersion::get::<CourseGrading>(), StorageVersion::new(4));
+		CourseGrading::on_idle(3, Weight::MAX);
+
+		assert!(CourseGrading::migration_cursor().is_none());
+		assert_eq!(StorageVersion::get::<CourseGrading>(), StorageVersion::new(5));
+		assert_eq!(CourseGrading::year_stats(CourseYear::First).minted, 1);
+		assert_eq!(CourseGrading::courses(&course_id).unwrap().blobs_root, None);
+		System::assert_has_event(Event::CourseGrading(crate::Event::MigrationCompleted(5, 2)));
+		assert_ok!(CourseGrading::create_course(Origin::signed(ALICE)));
+	});
+}
@@ -0,0 +1,1250 @@
+//! Weights for pallet_course_passing
+//!
+//! These weights are a conservative starting point until the benchmarks have been run on the
+//! consortium's reference hardware. Regenerate this file with:
+//!
+//! ./target/release/node-template benchmark --chain dev --execution wasm --wasm-execution compiled
+//!   --pallet pallet_course_passing --extrinsic '*' --steps 50 --repeat 20
+//!   --output ./pallets/course_passing/src/weights.rs
+//!   --template ./.maintain/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_course_passing.
+pub trait WeightInfo {
+	fn create_course() -> Weight;
+	fn set_price() -> Weight;
+	fn transfer(s: u32, r: u32, ) -> Weight;
+	fn buy_course(s: u32, r: u32, ) -> Weight;
+	fn breed_course() -> Weight;
+	fn approve() -> Weight;
+	fn update_metadata() -> Weight;
+	fn list_dutch() -> Weight;
+	fn create_bundle(n: u32, ) -> Weight;
+	fn cancel_bundle() -> Weight;
+	fn buy_bundle(n: u32, ) -> Weight;
+	fn propose_swap() -> Weight;
+	fn cancel_swap() -> Weight;
+	fn accept_swap() -> Weight;
+	fn offer_transfer() -> Weight;
+	fn claim_transfer() -> Weight;
+	fn reject_transfer() -> Weight;
+	fn set_accepts_transfers() -> Weight;
+	fn set_cooldown_exempt() -> Weight;
+	fn ban_account() -> Weight;
+	fn unban_account() -> Weight;
+	fn clone_course() -> Weight;
+	fn set_year_limit() -> Weight;
+	fn set_paused() -> Weight;
+	fn burn_course() -> Weight;
+	fn snapshot_owners() -> Weight;
+	fn set_price_unlisted() -> Weight;
+	fn tip_creator() -> Weight;
+	fn set_stable_price() -> Weight;
+	fn buy_course_in_stable(s: u32, r: u32, ) -> Weight;
+	fn set_transfer_fee() -> Weight;
+	fn list_sealed() -> Weight;
+	fn commit_purchase() -> Weight;
+	fn reveal_purchase() -> Weight;
+	fn settle_sealed(s: u32, r: u32, ) -> Weight;
+	fn withdraw_sealed_bid() -> Weight;
+	fn list_second_price() -> Weight;
+	fn watch() -> Weight;
+	fn unwatch() -> Weight;
+	fn archive_term(n: u32, ) -> Weight;
+	fn set_blob(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_course_passing using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Babe NextRandomness (r:1 w:0)
+	// Storage: CourseGrading CountForCourses (r:1 w:1)
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:1 w:1)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading OwnedPerYear (r:1 w:1)
+	// Storage: CourseGrading YearLimits (r:1 w:0)
+	// Storage: CourseGrading OwnedCount (r:1 w:1)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading SessionMints (r:1 w:1)
+	// Storage: CourseGrading CourseDeposits (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:1 w:1)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading Creators (r:0 w:1)
+	// Storage: CourseGrading YearStatistics (r:1 w:1)
+	fn create_course() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:0)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading Watches (r:1 w:0)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	fn set_price() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:2 w:2)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	// Storage: CourseGrading AcceptsDirectTransfers (r:1 w:0)
+	// Storage: CourseGrading TransferOffers (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:1)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading OwnedPerYear (r:2 w:2)
+	// Storage: CourseGrading YearLimits (r:1 w:0)
+	// Storage: CourseGrading OwnedCount (r:2 w:2)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:2 w:2)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading StablePrices (r:0 w:1)
+	// Storage: CourseGrading TransferFees (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	fn transfer(s: u32, r: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((125_000 as Weight).saturating_mul(s as Weight))
+			// Standard Error: 1_000
+			.saturating_add((125_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(21 as Weight))
+			.saturating_add(T::DbWeight::get().writes(19 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: CourseGrading RecentSales (r:1 w:1)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:1 w:1)
+	// Storage: CourseGrading TransferOffers (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:1)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:2 w:0)
+	// Storage: CourseGrading OwnedPerYear (r:2 w:2)
+	// Storage: CourseGrading YearLimits (r:1 w:0)
+	// Storage: CourseGrading OwnedCount (r:2 w:2)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:2 w:2)
+	// Storage: CourseGrading MarketStats (r:1 w:1)
+	// Storage: Session CurrentIndex (r:1 w:0)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: Vesting Vesting (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: CourseGrading StablePrices (r:0 w:1)
+	// Storage: CourseGrading PendingTrades (r:1 w:1)
+	// Storage: CourseGrading RecentTrades (r:0 w:1)
+	// Storage: CourseGrading TradeCount (r:1 w:1)
+	// Storage: CourseGrading TransferFees (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: CourseGrading Watches (r:1 w:0)
+	// Storage: CourseGrading YearStatistics (r:1 w:1)
+	fn buy_course(s: u32, r: u32, ) -> Weight {
+		(80_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((125_000 as Weight).saturating_mul(s as Weight))
+			// Standard Error: 1_000
+			.saturating_add((125_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(32 as Weight))
+			.saturating_add(T::DbWeight::get().writes(28 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:3 w:1)
+	// Storage: Babe NextRandomness (r:1 w:0)
+	// Storage: CourseGrading CountForCourses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:1 w:1)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading OwnedPerYear (r:1 w:1)
+	// Storage: CourseGrading YearLimits (r:1 w:0)
+	// Storage: CourseGrading OwnedCount (r:1 w:1)
+	// Storage: CourseGrading OwnedSince (r:2 w:1)
+	// Storage: CourseGrading SessionMints (r:1 w:1)
+	// Storage: CourseGrading CourseDeposits (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:1 w:1)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading Creators (r:0 w:1)
+	// Storage: CourseGrading YearStatistics (r:1 w:1)
+	fn breed_course() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(18 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:0)
+	// Storage: CourseGrading CourseApprovals (r:0 w:1)
+	fn approve() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:0)
+	// Storage: CourseGrading PendingPins (r:1 w:1)
+	// Storage: CourseGrading CourseContents (r:1 w:1)
+	// Storage: CourseGrading MetadataLogs (r:1 w:1)
+	// Storage: CourseGrading ContentDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Child storage: blobs (r:2 w:0)
+	fn update_metadata() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:0)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	fn list_dutch() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:0)
+	// Storage: CourseGrading NextBundleId (r:1 w:1)
+	// Storage: CourseGrading Bundles (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:0)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	fn create_bundle(n: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			// Standard Error: 5_000
+			.saturating_add((3_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: CourseGrading Bundles (r:1 w:1)
+	fn cancel_bundle() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading Bundles (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	// Storage: CourseGrading CourseApprovals (r:0 w:1)
+	// Storage: CourseGrading TransferOffers (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:1)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:2 w:0)
+	// Storage: CourseGrading OwnedPerYear (r:2 w:2)
+	// Storage: CourseGrading YearLimits (r:1 w:0)
+	// Storage: CourseGrading OwnedCount (r:2 w:2)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:2 w:2)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: Vesting Vesting (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: CourseGrading StablePrices (r:0 w:1)
+	// Storage: CourseGrading RecentSales (r:1 w:1)
+	// Storage: CourseGrading MarketStats (r:1 w:1)
+	// Storage: CourseGrading YearStatistics (r:1 w:1)
+	// Storage: CourseGrading PendingTrades (r:1 w:1)
+	// Storage: CourseGrading TransferFees (r:1 w:0)
+	fn buy_bundle(n: u32, ) -> Weight {
+		(60_000_000 as Weight)
+			// Standard Error: 10_000
+			.saturating_add((30_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().reads((17 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes((19 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: CourseGrading Courses (r:2 w:0)
+	// Storage: CourseGrading NextSwapId (r:1 w:1)
+	// Storage: CourseGrading Swaps (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:0)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	fn propose_swap() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: CourseGrading Swaps (r:1 w:1)
+	fn cancel_swap() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading Swaps (r:1 w:1)
+	// Storage: CourseGrading Courses (r:2 w:2)
+	// Storage: System Account (r:4 w:4)
+	// Storage: CourseGrading CoursesOwned (r:2 w:2)
+	// Storage: CourseGrading CourseHistoryLen (r:2 w:2)
+	// Storage: CourseGrading DutchListings (r:0 w:2)
+	// Storage: CourseGrading CourseApprovals (r:0 w:2)
+	// Storage: CourseGrading TransferOffers (r:0 w:2)
+	// Storage: CourseGrading AcquiredAt (r:2 w:2)
+	// Storage: CourseGrading CooldownExempt (r:2 w:0)
+	// Storage: CourseGrading BannedAccounts (r:2 w:0)
+	// Storage: CourseGrading OwnedPerYear (r:4 w:4)
+	// Storage: CourseGrading YearLimits (r:2 w:0)
+	// Storage: CourseGrading OwnedCount (r:4 w:4)
+	// Storage: CourseGrading OwnedSince (r:0 w:2)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:4 w:4)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading StablePrices (r:0 w:2)
+	// Storage: CourseGrading TransferFees (r:2 w:0)
+	fn accept_swap() -> Weight {
+		(95_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(36 as Weight))
+			.saturating_add(T::DbWeight::get().writes(37 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:0)
+	// Storage: CourseGrading TransferOffers (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:0)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	fn offer_transfer() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading TransferOffers (r:1 w:1)
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:2 w:2)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	// Storage: CourseGrading CourseApprovals (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:1)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading OwnedPerYear (r:2 w:2)
+	// Storage: CourseGrading YearLimits (r:1 w:0)
+	// Storage: CourseGrading OwnedCount (r:2 w:2)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:2 w:2)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading StablePrices (r:0 w:1)
+	// Storage: CourseGrading TransferFees (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn claim_transfer() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(20 as Weight))
+			.saturating_add(T::DbWeight::get().writes(20 as Weight))
+	}
+	// Storage: CourseGrading TransferOffers (r:1 w:1)
+	// Storage: CourseGrading Courses (r:1 w:0)
+	fn reject_transfer() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading AcceptsDirectTransfers (r:0 w:1)
+	fn set_accepts_transfers() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading CooldownExempt (r:0 w:1)
+	fn set_cooldown_exempt() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading BannedAccounts (r:0 w:1)
+	fn ban_account() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading BannedAccounts (r:0 w:1)
+	fn unban_account() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:2 w:1)
+	// Storage: CourseGrading CountForCourses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:1 w:1)
+	// Storage: CourseGrading CourseContents (r:1 w:1)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading CourseLineage (r:0 w:1)
+	// Storage: CourseGrading OwnedPerYear (r:1 w:1)
+	// Storage: CourseGrading YearLimits (r:1 w:0)
+	// Storage: CourseGrading OwnedCount (r:1 w:1)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading MetadataLogs (r:1 w:1)
+	// Storage: CourseGrading SessionMints (r:1 w:1)
+	// Storage: CourseGrading CourseDeposits (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:1 w:1)
+	// Storage: CourseGrading ContentDeposits (r:0 w:1)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading Creators (r:0 w:1)
+	// Storage: CourseGrading YearStatistics (r:1 w:1)
+	fn clone_course() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(16 as Weight))
+			.saturating_add(T::DbWeight::get().writes(18 as Weight))
+	}
+	// Storage: CourseGrading YearLimits (r:0 w:1)
+	fn set_year_limit() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading Paused (r:0 w:1)
+	fn set_paused() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:1 w:1)
+	// Storage: CourseGrading OwnedCount (r:1 w:1)
+	// Storage: CourseGrading OwnedPerYear (r:1 w:1)
+	// Storage: CourseGrading CountForCourses (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	// Storage: CourseGrading CourseApprovals (r:0 w:1)
+	// Storage: CourseGrading TransferOffers (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:0 w:1)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading CourseContents (r:0 w:1)
+	// Storage: CourseGrading MetadataLogs (r:0 w:1)
+	// Storage: CourseGrading CourseLineage (r:0 w:1)
+	// Storage: CourseGrading CourseDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:1 w:1)
+	// Storage: CourseGrading ContentDeposits (r:1 w:1)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading Creators (r:0 w:1)
+	// Storage: CourseGrading Tips (r:0 w:1)
+	// Storage: CourseGrading StablePrices (r:0 w:1)
+	// Storage: CourseGrading YearStatistics (r:1 w:1)
+	// Child storage: blobs (r:0 w:2)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	fn burn_course() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(25 as Weight))
+	}
+	// Storage: CourseGrading MigrationCursor (r:1 w:0)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:1)
+	// Storage: CourseGrading NextSnapshotId (r:1 w:1)
+	fn snapshot_owners() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:0)
+	// Storage: CourseGrading DutchListings (r:1 w:0)
+	fn set_price_unlisted() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+	}
+	// Storage: CourseGrading MigrationCursor (r:1 w:0)
+	// Storage: CourseGrading Paused (r:1 w:0)
+	// Storage: CourseGrading Courses (r:1 w:0)
+	// Storage: CourseGrading Creators (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: CourseGrading Tips (r:1 w:1)
+	fn tip_creator() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: CourseGrading MigrationCursor (r:1 w:0)
+	// Storage: CourseGrading Paused (r:1 w:0)
+	// Storage: CourseGrading Courses (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	// Storage: CourseGrading AcquiredAt (r:1 w:0)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading StablePrices (r:0 w:1)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	fn set_stable_price() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:2 w:2)
+	// Storage: CourseGrading StablePrices (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:1 w:1)
+	// Storage: CourseGrading TransferOffers (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:1)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:2 w:0)
+	// Storage: CourseGrading OwnedPerYear (r:2 w:2)
+	// Storage: CourseGrading YearLimits (r:1 w:0)
+	// Storage: CourseGrading OwnedCount (r:2 w:2)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:2 w:2)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading TransferFees (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn buy_course_in_stable(s: u32, r: u32, ) -> Weight {
+		(75_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((125_000 as Weight).saturating_mul(s as Weight))
+			// Standard Error: 1_000
+			.saturating_add((125_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(26 as Weight))
+			.saturating_add(T::DbWeight::get().writes(22 as Weight))
+	}
+	// Storage: CourseGrading TransferFees (r:0 w:1)
+	fn set_transfer_fee() -> Weight {
+		(9_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading SealedListings (r:1 w:1)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	// Storage: CourseGrading AcquiredAt (r:1 w:0)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	fn list_sealed() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	// Storage: CourseGrading SealedBids (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn commit_purchase() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: CourseGrading SealedListings (r:1 w:1)
+	// Storage: CourseGrading SealedBids (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn reveal_purchase() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: CourseGrading SealedListings (r:1 w:1)
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:2 w:2)
+	// Storage: System Account (r:3 w:3)
+	// Storage: CourseGrading RecentSales (r:1 w:1)
+	// Storage: CourseGrading PendingTrades (r:1 w:1)
+	// Storage: CourseGrading TradeCount (r:1 w:1)
+	// Storage: CourseGrading Watches (r:1 w:0)
+	// Storage: CourseGrading YearStatistics (r:1 w:1)
+	// Storage: CourseGrading TransferFees (r:1 w:0)
+	fn settle_sealed(s: u32, r: u32, ) -> Weight {
+		(85_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((125_000 as Weight).saturating_mul(s as Weight))
+			// Standard Error: 1_000
+			.saturating_add((125_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(33 as Weight))
+			.saturating_add(T::DbWeight::get().writes(29 as Weight))
+	}
+	// Storage: CourseGrading SealedBids (r:1 w:1)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: CourseGrading LastBlocked (r:1 w:0)
+	fn withdraw_sealed_bid() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading SealedListings (r:1 w:1)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	// Storage: CourseGrading AcquiredAt (r:1 w:0)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	fn list_second_price() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: CourseGrading MigrationCursor (r:1 w:0)
+	// Storage: CourseGrading Paused (r:1 w:0)
+	// Storage: CourseGrading Courses (r:1 w:0)
+	// Storage: CourseGrading Watches (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn watch() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: CourseGrading Watches (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn unwatch() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: CourseGrading MigrationCursor (r:1 w:0)
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CourseLineage (r:1 w:1)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	// Storage: CourseGrading CourseContents (r:1 w:1)
+	// Storage: CourseGrading Creators (r:1 w:1)
+	// Storage: CourseGrading MetadataLogs (r:1 w:1)
+	// Storage: CourseGrading Tips (r:1 w:1)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:1 w:1)
+	// Storage: CourseGrading OwnedCount (r:1 w:1)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading OwnedPerYear (r:1 w:1)
+	// Storage: CourseGrading CountForCourses (r:1 w:1)
+	// Storage: CourseGrading CourseDeposits (r:1 w:1)
+	// Storage: CourseGrading ContentDeposits (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: CourseGrading ArchivedTerms (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	// Storage: CourseGrading CourseApprovals (r:0 w:1)
+	// Storage: CourseGrading TransferOffers (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:0 w:1)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading StablePrices (r:0 w:1)
+	fn archive_term(n: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			// Standard Error: 15_000
+			.saturating_add((45_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((19 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((24 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: CourseGrading MigrationCursor (r:1 w:0)
+	// Storage: CourseGrading Paused (r:1 w:0)
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CourseContents (r:1 w:0)
+	// Storage: CourseGrading ContentDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Child storage: blobs (r:2 w:1)
+	fn set_blob(n: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((2_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: Babe NextRandomness (r:1 w:0)
+	// Storage: CourseGrading CountForCourses (r:1 w:1)
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:1 w:1)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading OwnedPerYear (r:1 w:1)
+	// Storage: CourseGrading YearLimits (r:1 w:0)
+	// Storage: CourseGrading OwnedCount (r:1 w:1)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading SessionMints (r:1 w:1)
+	// Storage: CourseGrading CourseDeposits (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:1 w:1)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading Creators (r:0 w:1)
+	// Storage: CourseGrading YearStatistics (r:1 w:1)
+	fn create_course() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(14 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:0)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading Watches (r:1 w:0)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	fn set_price() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:2 w:2)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	// Storage: CourseGrading AcceptsDirectTransfers (r:1 w:0)
+	// Storage: CourseGrading TransferOffers (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:1)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading OwnedPerYear (r:2 w:2)
+	// Storage: CourseGrading YearLimits (r:1 w:0)
+	// Storage: CourseGrading OwnedCount (r:2 w:2)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:2 w:2)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading StablePrices (r:0 w:1)
+	// Storage: CourseGrading TransferFees (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	fn transfer(s: u32, r: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((125_000 as Weight).saturating_mul(s as Weight))
+			// Standard Error: 1_000
+			.saturating_add((125_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(21 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(19 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: CourseGrading RecentSales (r:1 w:1)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:1 w:1)
+	// Storage: CourseGrading TransferOffers (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:1)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:2 w:0)
+	// Storage: CourseGrading OwnedPerYear (r:2 w:2)
+	// Storage: CourseGrading YearLimits (r:1 w:0)
+	// Storage: CourseGrading OwnedCount (r:2 w:2)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:2 w:2)
+	// Storage: CourseGrading MarketStats (r:1 w:1)
+	// Storage: Session CurrentIndex (r:1 w:0)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: Vesting Vesting (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: CourseGrading StablePrices (r:0 w:1)
+	// Storage: CourseGrading PendingTrades (r:1 w:1)
+	// Storage: CourseGrading RecentTrades (r:0 w:1)
+	// Storage: CourseGrading TradeCount (r:1 w:1)
+	// Storage: CourseGrading TransferFees (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: CourseGrading Watches (r:1 w:0)
+	// Storage: CourseGrading YearStatistics (r:1 w:1)
+	fn buy_course(s: u32, r: u32, ) -> Weight {
+		(80_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((125_000 as Weight).saturating_mul(s as Weight))
+			// Standard Error: 1_000
+			.saturating_add((125_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(32 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(28 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:3 w:1)
+	// Storage: Babe NextRandomness (r:1 w:0)
+	// Storage: CourseGrading CountForCourses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:1 w:1)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading OwnedPerYear (r:1 w:1)
+	// Storage: CourseGrading YearLimits (r:1 w:0)
+	// Storage: CourseGrading OwnedCount (r:1 w:1)
+	// Storage: CourseGrading OwnedSince (r:2 w:1)
+	// Storage: CourseGrading SessionMints (r:1 w:1)
+	// Storage: CourseGrading CourseDeposits (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:1 w:1)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading Creators (r:0 w:1)
+	// Storage: CourseGrading YearStatistics (r:1 w:1)
+	fn breed_course() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(18 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(14 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:0)
+	// Storage: CourseGrading CourseApprovals (r:0 w:1)
+	fn approve() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:0)
+	// Storage: CourseGrading PendingPins (r:1 w:1)
+	// Storage: CourseGrading CourseContents (r:1 w:1)
+	// Storage: CourseGrading MetadataLogs (r:1 w:1)
+	// Storage: CourseGrading ContentDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Child storage: blobs (r:2 w:0)
+	fn update_metadata() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:0)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	fn list_dutch() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:0)
+	// Storage: CourseGrading NextBundleId (r:1 w:1)
+	// Storage: CourseGrading Bundles (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:0)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	fn create_bundle(n: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			// Standard Error: 5_000
+			.saturating_add((3_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: CourseGrading Bundles (r:1 w:1)
+	fn cancel_bundle() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading Bundles (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	// Storage: CourseGrading CourseApprovals (r:0 w:1)
+	// Storage: CourseGrading TransferOffers (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:1)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:2 w:0)
+	// Storage: CourseGrading OwnedPerYear (r:2 w:2)
+	// Storage: CourseGrading YearLimits (r:1 w:0)
+	// Storage: CourseGrading OwnedCount (r:2 w:2)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:2 w:2)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: Vesting Vesting (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: CourseGrading StablePrices (r:0 w:1)
+	// Storage: CourseGrading RecentSales (r:1 w:1)
+	// Storage: CourseGrading MarketStats (r:1 w:1)
+	// Storage: CourseGrading YearStatistics (r:1 w:1)
+	// Storage: CourseGrading PendingTrades (r:1 w:1)
+	// Storage: CourseGrading TransferFees (r:1 w:0)
+	fn buy_bundle(n: u32, ) -> Weight {
+		(60_000_000 as Weight)
+			// Standard Error: 10_000
+			.saturating_add((30_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((17 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((19 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: CourseGrading Courses (r:2 w:0)
+	// Storage: CourseGrading NextSwapId (r:1 w:1)
+	// Storage: CourseGrading Swaps (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:0)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	fn propose_swap() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: CourseGrading Swaps (r:1 w:1)
+	fn cancel_swap() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading Swaps (r:1 w:1)
+	// Storage: CourseGrading Courses (r:2 w:2)
+	// Storage: System Account (r:4 w:4)
+	// Storage: CourseGrading CoursesOwned (r:2 w:2)
+	// Storage: CourseGrading CourseHistoryLen (r:2 w:2)
+	// Storage: CourseGrading DutchListings (r:0 w:2)
+	// Storage: CourseGrading CourseApprovals (r:0 w:2)
+	// Storage: CourseGrading TransferOffers (r:0 w:2)
+	// Storage: CourseGrading AcquiredAt (r:2 w:2)
+	// Storage: CourseGrading CooldownExempt (r:2 w:0)
+	// Storage: CourseGrading BannedAccounts (r:2 w:0)
+	// Storage: CourseGrading OwnedPerYear (r:4 w:4)
+	// Storage: CourseGrading YearLimits (r:2 w:0)
+	// Storage: CourseGrading OwnedCount (r:4 w:4)
+	// Storage: CourseGrading OwnedSince (r:0 w:2)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:4 w:4)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading StablePrices (r:0 w:2)
+	// Storage: CourseGrading TransferFees (r:2 w:0)
+	fn accept_swap() -> Weight {
+		(95_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(36 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(37 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:0)
+	// Storage: CourseGrading TransferOffers (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:0)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	fn offer_transfer() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading TransferOffers (r:1 w:1)
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:2 w:2)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	// Storage: CourseGrading CourseApprovals (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:1)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading OwnedPerYear (r:2 w:2)
+	// Storage: CourseGrading YearLimits (r:1 w:0)
+	// Storage: CourseGrading OwnedCount (r:2 w:2)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:2 w:2)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading StablePrices (r:0 w:1)
+	// Storage: CourseGrading TransferFees (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn claim_transfer() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(20 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(20 as Weight))
+	}
+	// Storage: CourseGrading TransferOffers (r:1 w:1)
+	// Storage: CourseGrading Courses (r:1 w:0)
+	fn reject_transfer() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading AcceptsDirectTransfers (r:0 w:1)
+	fn set_accepts_transfers() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading CooldownExempt (r:0 w:1)
+	fn set_cooldown_exempt() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading BannedAccounts (r:0 w:1)
+	fn ban_account() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading BannedAccounts (r:0 w:1)
+	fn unban_account() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:2 w:1)
+	// Storage: CourseGrading CountForCourses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:1 w:1)
+	// Storage: CourseGrading CourseContents (r:1 w:1)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading CourseLineage (r:0 w:1)
+	// Storage: CourseGrading OwnedPerYear (r:1 w:1)
+	// Storage: CourseGrading YearLimits (r:1 w:0)
+	// Storage: CourseGrading OwnedCount (r:1 w:1)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading MetadataLogs (r:1 w:1)
+	// Storage: CourseGrading SessionMints (r:1 w:1)
+	// Storage: CourseGrading CourseDeposits (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:1 w:1)
+	// Storage: CourseGrading ContentDeposits (r:0 w:1)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading Creators (r:0 w:1)
+	// Storage: CourseGrading YearStatistics (r:1 w:1)
+	fn clone_course() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(16 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(18 as Weight))
+	}
+	// Storage: CourseGrading YearLimits (r:0 w:1)
+	fn set_year_limit() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading Paused (r:0 w:1)
+	fn set_paused() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:1 w:1)
+	// Storage: CourseGrading OwnedCount (r:1 w:1)
+	// Storage: CourseGrading OwnedPerYear (r:1 w:1)
+	// Storage: CourseGrading CountForCourses (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	// Storage: CourseGrading CourseApprovals (r:0 w:1)
+	// Storage: CourseGrading TransferOffers (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:0 w:1)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading CourseContents (r:0 w:1)
+	// Storage: CourseGrading MetadataLogs (r:0 w:1)
+	// Storage: CourseGrading CourseLineage (r:0 w:1)
+	// Storage: CourseGrading CourseDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:1 w:1)
+	// Storage: CourseGrading ContentDeposits (r:1 w:1)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading Creators (r:0 w:1)
+	// Storage: CourseGrading Tips (r:0 w:1)
+	// Storage: CourseGrading StablePrices (r:0 w:1)
+	// Storage: CourseGrading YearStatistics (r:1 w:1)
+	// Child storage: blobs (r:0 w:2)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	fn burn_course() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(25 as Weight))
+	}
+	// Storage: CourseGrading MigrationCursor (r:1 w:0)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:1)
+	// Storage: CourseGrading NextSnapshotId (r:1 w:1)
+	fn snapshot_owners() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:0)
+	// Storage: CourseGrading DutchListings (r:1 w:0)
+	fn set_price_unlisted() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+	}
+	// Storage: CourseGrading MigrationCursor (r:1 w:0)
+	// Storage: CourseGrading Paused (r:1 w:0)
+	// Storage: CourseGrading Courses (r:1 w:0)
+	// Storage: CourseGrading Creators (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: CourseGrading Tips (r:1 w:1)
+	fn tip_creator() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: CourseGrading MigrationCursor (r:1 w:0)
+	// Storage: CourseGrading Paused (r:1 w:0)
+	// Storage: CourseGrading Courses (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	// Storage: CourseGrading AcquiredAt (r:1 w:0)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading StablePrices (r:0 w:1)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	fn set_stable_price() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:2 w:2)
+	// Storage: CourseGrading StablePrices (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:1 w:1)
+	// Storage: CourseGrading TransferOffers (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:1 w:1)
+	// Storage: CourseGrading CooldownExempt (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:2 w:0)
+	// Storage: CourseGrading OwnedPerYear (r:2 w:2)
+	// Storage: CourseGrading YearLimits (r:1 w:0)
+	// Storage: CourseGrading OwnedCount (r:2 w:2)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading SnapshotOverrides (r:2 w:2)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading TransferFees (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn buy_course_in_stable(s: u32, r: u32, ) -> Weight {
+		(75_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((125_000 as Weight).saturating_mul(s as Weight))
+			// Standard Error: 1_000
+			.saturating_add((125_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(26 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(22 as Weight))
+	}
+	// Storage: CourseGrading TransferFees (r:0 w:1)
+	fn set_transfer_fee() -> Weight {
+		(9_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading SealedListings (r:1 w:1)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	// Storage: CourseGrading AcquiredAt (r:1 w:0)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	fn list_sealed() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	// Storage: CourseGrading SealedBids (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn commit_purchase() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: CourseGrading SealedListings (r:1 w:1)
+	// Storage: CourseGrading SealedBids (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn reveal_purchase() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: CourseGrading SealedListings (r:1 w:1)
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:2 w:2)
+	// Storage: System Account (r:3 w:3)
+	// Storage: CourseGrading RecentSales (r:1 w:1)
+	// Storage: CourseGrading PendingTrades (r:1 w:1)
+	// Storage: CourseGrading TradeCount (r:1 w:1)
+	// Storage: CourseGrading Watches (r:1 w:0)
+	// Storage: CourseGrading YearStatistics (r:1 w:1)
+	// Storage: CourseGrading TransferFees (r:1 w:0)
+	fn settle_sealed(s: u32, r: u32, ) -> Weight {
+		(85_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((125_000 as Weight).saturating_mul(s as Weight))
+			// Standard Error: 1_000
+			.saturating_add((125_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(33 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(29 as Weight))
+	}
+	// Storage: CourseGrading SealedBids (r:1 w:1)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: CourseGrading LastBlocked (r:1 w:0)
+	fn withdraw_sealed_bid() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading SealedListings (r:1 w:1)
+	// Storage: CourseGrading BannedAccounts (r:1 w:0)
+	// Storage: CourseGrading AcquiredAt (r:1 w:0)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	fn list_second_price() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: CourseGrading MigrationCursor (r:1 w:0)
+	// Storage: CourseGrading Paused (r:1 w:0)
+	// Storage: CourseGrading Courses (r:1 w:0)
+	// Storage: CourseGrading Watches (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn watch() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: CourseGrading Watches (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn unwatch() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: CourseGrading MigrationCursor (r:1 w:0)
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CourseLineage (r:1 w:1)
+	// Storage: CourseGrading SealedListings (r:1 w:0)
+	// Storage: CourseGrading CourseContents (r:1 w:1)
+	// Storage: CourseGrading Creators (r:1 w:1)
+	// Storage: CourseGrading MetadataLogs (r:1 w:1)
+	// Storage: CourseGrading Tips (r:1 w:1)
+	// Storage: CourseGrading CourseHistoryLen (r:1 w:1)
+	// Storage: CourseGrading CoursesOwned (r:1 w:1)
+	// Storage: CourseGrading OwnedCount (r:1 w:1)
+	// Storage: CourseGrading OwnerCount (r:1 w:1)
+	// Storage: CourseGrading ListedValue (r:1 w:1)
+	// Storage: CourseGrading PendingSnapshot (r:1 w:0)
+	// Storage: CourseGrading OwnedPerYear (r:1 w:1)
+	// Storage: CourseGrading CountForCourses (r:1 w:1)
+	// Storage: CourseGrading CourseDeposits (r:1 w:1)
+	// Storage: CourseGrading ContentDeposits (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: CourseGrading ArchivedTerms (r:1 w:1)
+	// Storage: CourseGrading DutchListings (r:0 w:1)
+	// Storage: CourseGrading CourseApprovals (r:0 w:1)
+	// Storage: CourseGrading TransferOffers (r:0 w:1)
+	// Storage: CourseGrading AcquiredAt (r:0 w:1)
+	// Storage: CourseGrading OwnedSince (r:0 w:1)
+	// Storage: CourseGrading StablePrices (r:0 w:1)
+	fn archive_term(n: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			// Standard Error: 15_000
+			.saturating_add((45_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((19 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((24 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: CourseGrading MigrationCursor (r:1 w:0)
+	// Storage: CourseGrading Paused (r:1 w:0)
+	// Storage: CourseGrading Courses (r:1 w:1)
+	// Storage: CourseGrading CourseContents (r:1 w:0)
+	// Storage: CourseGrading ContentDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Child storage: blobs (r:2 w:1)
+	fn set_blob(n: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((2_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+}
@@ -0,0 +1,16 @@
+//! Runtime API definitions for the student registry.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// The handles of accounts, for showing them instead of addresses.
+	pub trait DidHandlesApi<AccountId> where AccountId: Codec {
+		/// The handle of each of `accounts`, in order, `None` for those without one.
+		fn handles(accounts: Vec<AccountId>) -> Vec<Option<Vec<u8>>>;
+		/// The account of `handle`, if an account claimed it.
+		fn account_of(handle: Vec<u8>) -> Option<AccountId>;
+	}
+}
@@ -0,0 +1,495 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Decentralized identifiers for students, professors and the institution.
+//!
+//! Any account registers a DID document with `create_did`, reserving a deposit, and becomes the
+//! DID `did:uni-chain:0x<hex of the SCALE-encoded account>`. The account alone manages the
+//! document: it adds verification keys, rotates their key material, revokes them, and publishes
+//! service endpoints. Revoked keys stay in the document so that signatures made while they were
+//! valid can still be judged. Deactivating the DID removes the document and returns the deposit.
+//!
+//! An account with a DID can also claim a unique handle, such as `j.smith`, for explorers and
+//! class lists to show instead of its address, reserving another deposit.
+
+pub use pallet::*;
+
+use codec::Encode;
+use frame_support::{traits::ConstU32, BoundedVec};
+use sp_std::vec::Vec;
+
+/// The prefix of the DIDs of this chain.
+pub const DID_PREFIX: &[u8] = b"did:uni-chain:";
+
+/// The maximum length of a service id or type, in bytes.
+pub const MAX_NAME_LEN: u32 = 64;
+
+/// The maximum length of a service endpoint URL, in bytes.
+pub const MAX_URL_LEN: u32 = 256;
+
+/// The id or type of a service.
+pub type Name = BoundedVec<u8, ConstU32<MAX_NAME_LEN>>;
+
+/// The URL of a service endpoint.
+pub type Url = BoundedVec<u8, ConstU32<MAX_URL_LEN>>;
+
+/// Identifier of a verification key within its DID document.
+pub type KeyId = u32;
+
+/// The minimum length of a handle, in bytes.
+pub const MIN_HANDLE_LEN: u32 = 3;
+
+/// The maximum length of a handle, in bytes.
+pub const MAX_HANDLE_LEN: u32 = 32;
+
+/// The display handle of an account, e.g. `j.smith`.
+pub type Handle = BoundedVec<u8, ConstU32<MAX_HANDLE_LEN>>;
+
+/// Whether `handle` is a lowercase ASCII letter followed by lowercase letters, digits, `.`, `_`
+/// or `-`, so that handles are unique regardless of case and cannot pass for addresses.
+pub fn is_valid_handle(handle: &[u8]) -> bool {
+	handle.len() >= MIN_HANDLE_LEN as usize &&
+		handle[0].is_ascii_lowercase() &&
+		handle.iter().all(|byte| {
+			byte.is_ascii_lowercase() || byte.is_ascii_digit() || b"._-".contains(byte)
+		})
+}
+
+/// The DID of `who`.
+pub fn did_of<AccountId: Encode>(who: &AccountId) -> Vec<u8> {
+	const HEX: &[u8; 16] = b"0123456789abcdef";
+	let mut did = DID_PREFIX.to_vec();
+	did.extend_from_slice(b"0x");
+	for byte in who.encode() {
+		did.push(HEX[(byte >> 4) as usize]);
+		did.push(HEX[(byte & 0xf) as usize]);
+	}
+	did
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::{is_valid_handle, Handle, KeyId, Name, Url};
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ReservableCurrency},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_std::vec::Vec;
+
+	type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// The algorithm of a verification key.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum KeyType {
+		Sr25519,
+		Ed25519,
+		X25519,
+	}
+
+	/// What a verification key may be used for, as in the DID core verification relationships.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum KeyPurpose {
+		Authentication,
+		AssertionMethod,
+		KeyAgreement,
+	}
+
+	// Struct for holding a verification key of a DID.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct VerificationKey<T: Config> {
+		pub id: KeyId,
+		pub key_type: KeyType,
+		pub purpose: KeyPurpose,
+		pub public: [u8; 32],
+		/// The block the key material was added or last rotated in.
+		pub valid_from: T::BlockNumber,
+		/// The block the key was revoked in, if it was.
+		pub revoked: Option<T::BlockNumber>,
+	}
+
+	// Struct for holding a service endpoint of a DID.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Service {
+		pub id: Name,
+		pub service_type: Name,
+		pub endpoint: Url,
+	}
+
+	// Struct for holding a DID document.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct DidDocument<T: Config> {
+		pub keys: BoundedVec<VerificationKey<T>, T::MaxKeys>,
+		pub services: BoundedVec<Service, T::MaxServices>,
+		/// The id of the next key added.
+		pub next_key_id: KeyId,
+		/// The deposit reserved from the account.
+		pub deposit: BalanceOf<T>,
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types it depends on.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency the deposits are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved for a DID document.
+		#[pallet::constant]
+		type DidDeposit: Get<BalanceOf<Self>>;
+
+		/// The maximum number of keys of a DID document, revoked ones included.
+		#[pallet::constant]
+		type MaxKeys: Get<u32>;
+
+		/// The maximum number of service endpoints of a DID document.
+		#[pallet::constant]
+		type MaxServices: Get<u32>;
+
+		/// The deposit reserved for a handle.
+		#[pallet::constant]
+		type HandleDeposit: Get<BalanceOf<Self>>;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn did_documents)]
+	/// The DID document of each account having a DID.
+	pub(super) type DidDocuments<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, DidDocument<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn handles)]
+	/// The handle of each account having one, with the deposit reserved for it.
+	pub(super) type Handles<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (Handle, BalanceOf<T>)>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn handle_owners)]
+	/// The account of each handle.
+	pub(super) type HandleOwners<T: Config> = StorageMap<_, Blake2_128Concat, Handle, T::AccountId>;
+
+	// Our pallet's genesis configuration.
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Accounts registered with an empty DID document, reserving `DidDeposit` from each.
+		pub dids: Vec<T::AccountId>,
+	}
+
+	// Required to implement default for GenesisConfig.
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> GenesisConfig<T> {
+			GenesisConfig { dids: vec![] }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let deposit = T::DidDeposit::get();
+			for who in &self.dids {
+				assert!(!<DidDocuments<T>>::contains_key(who), "Genesis DID registered twice");
+				T::Currency::reserve(who, deposit)
+					.expect("Genesis DID accounts must be able to reserve the deposit");
+				<DidDocuments<T>>::insert(
+					who,
+					DidDocument {
+						keys: Default::default(),
+						services: Default::default(),
+						next_key_id: 0,
+						deposit,
+					},
+				);
+			}
+		}
+	}
+
+	// Errors.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account has a DID already.
+		DidExists,
+		/// The account has no DID.
+		DidNotExist,
+		/// The account cannot reserve the deposit.
+		NotEnoughBalance,
+		/// The DID document has `MaxKeys` keys already.
+		TooManyKeys,
+		/// Ran out of key ids.
+		KeyIdOverflow,
+		/// The DID document has no such key.
+		KeyNotExist,
+		/// The key is revoked.
+		KeyRevoked,
+		/// The DID document has `MaxServices` services already.
+		TooManyServices,
+		/// The DID document has a service with this id already.
+		ServiceExists,
+		/// The DID document has no such service.
+		ServiceNotExist,
+		/// Handles start with a lowercase letter, followed by lowercase letters, digits, `.`, `_`
+		/// or `-`, at least `MIN_HANDLE_LEN` bytes in all.
+		InvalidHandle,
+		/// Another account has this handle.
+		HandleTaken,
+		/// The account has no handle.
+		HandleNotExist,
+	}
+
+	// Events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A DID was created. \[who\]
+		DidCreated(T::AccountId),
+		/// A DID was deactivated. \[who\]
+		DidDeactivated(T::AccountId),
+		/// A verification key was added. \[who, key_id\]
+		KeyAdded(T::AccountId, KeyId),
+		/// The key material of a verification key was replaced. \[who, key_id\]
+		KeyRotated(T::AccountId, KeyId),
+		/// A verification key was revoked. \[who, key_id\]
+		KeyRevoked(T::AccountId, KeyId),
+		/// A service endpoint was published. \[who, service_id\]
+		ServiceAdded(T::AccountId, Name),
+		/// A service endpoint was removed. \[who, service_id\]
+		ServiceRemoved(T::AccountId, Name),
+		/// An account claimed a handle, releasing its previous one. \[who, handle\]
+		HandleSet(T::AccountId, Handle),
+		/// An account released its handle. \[who, handle\]
+		HandleCleared(T::AccountId, Handle),
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create the DID of the sender, reserving `DidDeposit`.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn create_did(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!<DidDocuments<T>>::contains_key(&who), <Error<T>>::DidExists);
+
+			let deposit = T::DidDeposit::get();
+			T::Currency::reserve(&who, deposit).map_err(|_| <Error<T>>::NotEnoughBalance)?;
+			<DidDocuments<T>>::insert(
+				&who,
+				DidDocument {
+					keys: Default::default(),
+					services: Default::default(),
+					next_key_id: 0,
+					deposit,
+				},
+			);
+
+			Self::deposit_event(Event::DidCreated(who));
+			Ok(())
+		}
+
+		/// Deactivate the DID of the sender, removing its document and returning the deposit.
+		///
+		/// The handle of the sender is released too.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 4))]
+		pub fn deactivate_did(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let document = <DidDocuments<T>>::take(&who).ok_or(<Error<T>>::DidNotExist)?;
+
+			T::Currency::unreserve(&who, document.deposit);
+			Self::release_handle(&who);
+
+			Self::deposit_event(Event::DidDeactivated(who));
+			Ok(())
+		}
+
+		/// Add a verification key to the DID of the sender.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn add_key(
+			origin: OriginFor<T>,
+			key_type: KeyType,
+			purpose: KeyPurpose,
+			public: [u8; 32],
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let id = <DidDocuments<T>>::try_mutate(&who, |document| {
+				let document = document.as_mut().ok_or(<Error<T>>::DidNotExist)?;
+				let id = document.next_key_id;
+				document.next_key_id = id.checked_add(1).ok_or(<Error<T>>::KeyIdOverflow)?;
+				document
+					.keys
+					.try_push(VerificationKey {
+						id,
+						key_type,
+						purpose,
+						public,
+						valid_from: <frame_system::Pallet<T>>::block_number(),
+						revoked: None,
+					})
+					.map_err(|_| <Error<T>>::TooManyKeys)?;
+				Ok::<_, Error<T>>(id)
+			})?;
+
+			Self::deposit_event(Event::KeyAdded(who, id));
+			Ok(())
+		}
+
+		/// Replace the key material of a verification key of the DID of the sender, keeping its
+		/// type and purpose.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn rotate_key(origin: OriginFor<T>, key_id: KeyId, public: [u8; 32]) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::mutate_key(&who, key_id, |key| {
+				key.public = public;
+				key.valid_from = <frame_system::Pallet<T>>::block_number();
+			})?;
+
+			Self::deposit_event(Event::KeyRotated(who, key_id));
+			Ok(())
+		}
+
+		/// Revoke a verification key of the DID of the sender.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn revoke_key(origin: OriginFor<T>, key_id: KeyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::mutate_key(&who, key_id, |key| {
+				key.revoked = Some(<frame_system::Pallet<T>>::block_number());
+			})?;
+
+			Self::deposit_event(Event::KeyRevoked(who, key_id));
+			Ok(())
+		}
+
+		/// Publish a service endpoint in the DID document of the sender.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn add_service(
+			origin: OriginFor<T>,
+			id: Name,
+			service_type: Name,
+			endpoint: Url,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			<DidDocuments<T>>::try_mutate(&who, |document| {
+				let document = document.as_mut().ok_or(<Error<T>>::DidNotExist)?;
+				ensure!(
+					!document.services.iter().any(|service| service.id == id),
+					<Error<T>>::ServiceExists
+				);
+				document
+					.services
+					.try_push(Service { id: id.clone(), service_type, endpoint })
+					.map_err(|_| <Error<T>>::TooManyServices)
+			})?;
+
+			Self::deposit_event(Event::ServiceAdded(who, id));
+			Ok(())
+		}
+
+		/// Remove a service endpoint from the DID document of the sender.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn remove_service(origin: OriginFor<T>, id: Name) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			<DidDocuments<T>>::try_mutate(&who, |document| {
+				let document = document.as_mut().ok_or(<Error<T>>::DidNotExist)?;
+				let index = document
+					.services
+					.iter()
+					.position(|service| service.id == id)
+					.ok_or(<Error<T>>::ServiceNotExist)?;
+				document.services.remove(index);
+				Ok::<_, Error<T>>(())
+			})?;
+
+			Self::deposit_event(Event::ServiceRemoved(who, id));
+			Ok(())
+		}
+
+		/// Claim `handle` for the sender, which must have a DID, reserving `HandleDeposit`.
+		///
+		/// The previous handle of the sender, if any, is released and its deposit kept for the new
+		/// one.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 4))]
+		pub fn set_handle(origin: OriginFor<T>, handle: Handle) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(<DidDocuments<T>>::contains_key(&who), <Error<T>>::DidNotExist);
+			ensure!(is_valid_handle(&handle), <Error<T>>::InvalidHandle);
+			ensure!(!<HandleOwners<T>>::contains_key(&handle), <Error<T>>::HandleTaken);
+
+			let deposit = match <Handles<T>>::get(&who) {
+				Some((previous, deposit)) => {
+					<HandleOwners<T>>::remove(&previous);
+					deposit
+				},
+				None => {
+					let deposit = T::HandleDeposit::get();
+					T::Currency::reserve(&who, deposit)
+						.map_err(|_| <Error<T>>::NotEnoughBalance)?;
+					deposit
+				},
+			};
+			<Handles<T>>::insert(&who, (handle.clone(), deposit));
+			<HandleOwners<T>>::insert(&handle, &who);
+
+			Self::deposit_event(Event::HandleSet(who, handle));
+			Ok(())
+		}
+
+		/// Release the handle of the sender, returning its deposit.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2))]
+		pub fn clear_handle(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::release_handle(&who), <Error<T>>::HandleNotExist);
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Apply `f` to the unrevoked key `key_id` of the DID of `who`.
+		fn mutate_key(
+			who: &T::AccountId,
+			key_id: KeyId,
+			f: impl FnOnce(&mut VerificationKey<T>),
+		) -> Result<(), Error<T>> {
+			<DidDocuments<T>>::try_mutate(who, |document| {
+				let document = document.as_mut().ok_or(<Error<T>>::DidNotExist)?;
+				let index = document
+					.keys
+					.iter()
+					.position(|key| key.id == key_id)
+					.ok_or(<Error<T>>::KeyNotExist)?;
+				let mut key = document.keys[index].clone();
+				ensure!(key.revoked.is_none(), <Error<T>>::KeyRevoked);
+				f(&mut key);
+				document.keys.remove(index);
+				document.keys.try_insert(index, key).map_err(|_| <Error<T>>::TooManyKeys)
+			})
+		}
+
+		/// Release the handle of `who`, if it has one, returning its deposit.
+		fn release_handle(who: &T::AccountId) -> bool {
+			match <Handles<T>>::take(who) {
+				Some((handle, deposit)) => {
+					<HandleOwners<T>>::remove(&handle);
+					T::Currency::unreserve(who, deposit);
+					Self::deposit_event(Event::HandleCleared(who.clone(), handle));
+					true
+				},
+				None => false,
+			}
+		}
+
+		/// Whether `public` is an unrevoked key of the DID of `who` for `purpose`.
+		pub fn has_valid_key(who: &T::AccountId, purpose: KeyPurpose, public: &[u8; 32]) -> bool {
+			Self::did_documents(who).map_or(false, |document| {
+				document.keys.iter().any(|key| {
+					key.purpose == purpose && key.public == *public && key.revoked.is_none()
+				})
+			})
+		}
+	}
+}
@@ -0,0 +1,155 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Delayed enactment of governance decisions.
+//!
+//! Calls needing the root origin, such as parameter changes, membership changes and forced
+//! balance moves, are only dispatched through here. The call is first stored with
+//! `pallet-preimage`, then `ScheduleOrigin` schedules its hash, which the scheduler dispatches
+//! with the root origin `EnactmentDelay` blocks later. Until then `CancelOrigin` can cancel it.
+//! Only the hash goes through the motion and the scheduler, so large calls don't bloat them, and
+//! anyone can check the preimage against it during the delay.
+
+pub use pallet::*;
+
+use codec::Encode;
+use sp_std::vec::Vec;
+
+/// The name enactment `id` is scheduled under.
+pub fn schedule_name(id: u32) -> Vec<u8> {
+	(b"enactment", id).encode()
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::schedule_name;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::schedule::{v2::Named as ScheduleNamed, DispatchTime, MaybeHashed, HARD_DEADLINE},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Saturating;
+
+	/// Identifier of a scheduled enactment.
+	pub type EnactmentId = u32;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types it depends on.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The runtime call the preimages decode to.
+		type Call;
+
+		/// The origin the scheduler dispatches the calls with.
+		type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+
+		/// The scheduler enacting the calls, looking their preimages up by hash.
+		type Scheduler: ScheduleNamed<
+			Self::BlockNumber,
+			<Self as Config>::Call,
+			Self::PalletsOrigin,
+			Hash = Self::Hash,
+		>;
+
+		/// Origin allowed to schedule calls.
+		type ScheduleOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin allowed to cancel scheduled calls.
+		type CancelOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The number of blocks between scheduling a call and its enactment.
+		#[pallet::constant]
+		type EnactmentDelay: Get<Self::BlockNumber>;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_enactment_id)]
+	/// The identifier of the next scheduled enactment.
+	pub(super) type NextEnactmentId<T: Config> = StorageValue<_, EnactmentId, ValueQuery>;
+
+	// Errors.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Ran out of enactment ids.
+		EnactmentIdOverflow,
+		/// The scheduler refused the call.
+		ScheduleFailed,
+		/// The enactment was dispatched or cancelled already, or never scheduled.
+		NotScheduled,
+	}
+
+	// Events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A call was scheduled. \[enactment_id, call_hash, enactment_block\]
+		Scheduled(EnactmentId, T::Hash, T::BlockNumber),
+		/// A scheduled call was cancelled. \[enactment_id\]
+		Cancelled(EnactmentId),
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Schedule the call with preimage hash `call_hash` to be dispatched with the root origin
+		/// in `EnactmentDelay` blocks.
+		///
+		/// The preimage should be noted before the enactment block, or the call is postponed.
+		///
+		/// The dispatch origin for this call must be `ScheduleOrigin`.
+		#[pallet::weight((
+			10_000 + T::DbWeight::get().reads_writes(3, 3),
+			DispatchClass::Operational,
+		))]
+		pub fn schedule(origin: OriginFor<T>, call_hash: T::Hash) -> DispatchResultWithPostInfo {
+			T::ScheduleOrigin::ensure_origin(origin)?;
+
+			let id = Self::next_enactment_id();
+			let next_id = id.checked_add(1).ok_or(<Error<T>>::EnactmentIdOverflow)?;
+			let when =
+				<frame_system::Pallet<T>>::block_number().saturating_add(T::EnactmentDelay::get());
+			T::Scheduler::schedule_named(
+				schedule_name(id),
+				DispatchTime::At(when),
+				None,
+				HARD_DEADLINE,
+				frame_system::RawOrigin::Root.into(),
+				MaybeHashed::Hash(call_hash),
+			)
+			.map_err(|_| <Error<T>>::ScheduleFailed)?;
+			<NextEnactmentId<T>>::put(next_id);
+
+			Self::deposit_event(Event::Scheduled(id, call_hash, when));
+			Ok(Pays::No.into())
+		}
+
+		/// Cancel a scheduled call before its enactment.
+		///
+		/// The dispatch origin for this call must be `CancelOrigin`.
+		#[pallet::weight((
+			10_000 + T::DbWeight::get().reads_writes(1, 1),
+			DispatchClass::Operational,
+		))]
+		pub fn cancel(
+			origin: OriginFor<T>,
+			enactment_id: EnactmentId,
+		) -> DispatchResultWithPostInfo {
+			T::CancelOrigin::ensure_origin(origin)?;
+			T::Scheduler::cancel_named(schedule_name(enactment_id))
+				.map_err(|_| <Error<T>>::NotScheduled)?;
+
+			Self::deposit_event(Event::Cancelled(enactment_id));
+			Ok(Pays::No.into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The block enactment `id` is dispatched at, if it is still scheduled.
+		pub fn enactment_block(id: EnactmentId) -> Option<T::BlockNumber> {
+			T::Scheduler::next_dispatch_time(schedule_name(id)).ok()
+		}
+	}
+}
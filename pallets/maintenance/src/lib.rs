@@ -0,0 +1,130 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Maintenance mode for individual calls.
+//!
+//! Governance disables calls by pallet and function name, e.g. `CourseGrading` and `buy_course`,
+//! while the rest of the chain keeps running. The pallet implements `Contains` for any call with
+//! metadata, so the runtime can consult it from its `BaseCallFilter`; calls of the pallet itself
+//! are never filtered, so maintenance can always be lifted.
+
+pub use pallet::*;
+
+use frame_support::{
+	dispatch::{CallMetadata, GetCallMetadata},
+	traits::{ConstU32, Contains, PalletInfoAccess},
+	BoundedVec,
+};
+use sp_std::convert::TryFrom;
+
+/// The maximum length of a pallet or function name, in bytes.
+pub const MAX_NAME_LEN: u32 = 64;
+
+/// The name of a pallet or of one of its functions, as in the call metadata.
+pub type Name = BoundedVec<u8, ConstU32<MAX_NAME_LEN>>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::Name;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types it depends on.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Origin allowed to disable and enable calls.
+		type ControlOrigin: EnsureOrigin<Self::Origin>;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn disabled_calls)]
+	/// The disabled calls, by pallet and function name.
+	pub(super) type DisabledCalls<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, Name, Blake2_128Concat, Name, ()>;
+
+	// Errors.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The call is already disabled.
+		AlreadyDisabled,
+		/// The call is not disabled.
+		NotDisabled,
+	}
+
+	// Events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A call was disabled. \[pallet, function\]
+		CallDisabled(Name, Name),
+		/// A call was enabled again. \[pallet, function\]
+		CallEnabled(Name, Name),
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Reject every dispatch of `function` of `pallet` until it is enabled again.
+		#[pallet::weight((
+			10_000 + T::DbWeight::get().reads_writes(1, 1),
+			DispatchClass::Operational,
+		))]
+		pub fn disable_call(
+			origin: OriginFor<T>,
+			pallet: Name,
+			function: Name,
+		) -> DispatchResultWithPostInfo {
+			T::ControlOrigin::ensure_origin(origin)?;
+			ensure!(
+				!<DisabledCalls<T>>::contains_key(&pallet, &function),
+				<Error<T>>::AlreadyDisabled
+			);
+
+			<DisabledCalls<T>>::insert(&pallet, &function, ());
+
+			Self::deposit_event(Event::CallDisabled(pallet, function));
+			Ok(Pays::No.into())
+		}
+
+		/// Enable a disabled call.
+		#[pallet::weight((
+			10_000 + T::DbWeight::get().reads_writes(1, 1),
+			DispatchClass::Operational,
+		))]
+		pub fn enable_call(
+			origin: OriginFor<T>,
+			pallet: Name,
+			function: Name,
+		) -> DispatchResultWithPostInfo {
+			T::ControlOrigin::ensure_origin(origin)?;
+			ensure!(<DisabledCalls<T>>::contains_key(&pallet, &function), <Error<T>>::NotDisabled);
+
+			<DisabledCalls<T>>::remove(&pallet, &function);
+
+			Self::deposit_event(Event::CallEnabled(pallet, function));
+			Ok(Pays::No.into())
+		}
+	}
+}
+
+impl<T: Config, Call: GetCallMetadata> Contains<Call> for Pallet<T> {
+	fn contains(call: &Call) -> bool {
+		let CallMetadata { pallet_name, function_name } = call.get_call_metadata();
+		if pallet_name == <Self as PalletInfoAccess>::name() {
+			return true
+		}
+
+		// Names too long to be stored cannot have been disabled.
+		match (
+			Name::try_from(pallet_name.as_bytes().to_vec()),
+			Name::try_from(function_name.as_bytes().to_vec()),
+		) {
+			(Ok(pallet), Ok(function)) => !<DisabledCalls<T>>::contains_key(&pallet, &function),
+			_ => true,
+		}
+	}
+}
@@ -0,0 +1,308 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Academic misconduct cases and their due process.
+//!
+//! A reporter opens a case against the owner of a course with the hash of the evidence, and can
+//! add more evidence until the case is decided. The student has `ResponsePeriod` blocks to answer
+//! with the hash of their response. Once they answered, or the period elapsed, the committee
+//! upholds the case with a sanction or dismisses it. Upheld sanctions are handed to `OnSanction`
+//! to enforce.
+
+pub use pallet::*;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+
+/// The sanction of an upheld case.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Sanction {
+	/// A formal warning, recorded with the case.
+	Warning,
+	/// The grade of the course is void.
+	GradeVoided,
+	/// No credential can be issued for the course.
+	CredentialsBlocked,
+}
+
+/// Enforces the sanctions of upheld cases.
+#[impl_trait_for_tuples::impl_for_tuples(8)]
+pub trait SanctionHandler<AccountId, CourseId> {
+	/// `student` was sanctioned with `sanction` in `course_id`.
+	fn on_sanction(student: &AccountId, course_id: &CourseId, sanction: Sanction);
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::{Sanction, SanctionHandler};
+	use frame_support::{pallet_prelude::*, sp_runtime::traits::Saturating};
+	use frame_system::pallet_prelude::*;
+	use pallet_course_passing_primitives::CourseInspector;
+
+	/// Identifier of a case.
+	pub type CaseId = u32;
+
+	/// Where a case is in its due process.
+	#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum CaseStatus {
+		/// Waiting for the student to respond.
+		Open,
+		/// The student responded, or the response period elapsed.
+		UnderReview,
+		/// The committee upheld the case with a sanction.
+		Upheld(Sanction),
+		/// The committee dismissed the case.
+		Dismissed,
+	}
+
+	// Struct for holding a case.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Case<T: Config> {
+		pub student: T::AccountId,
+		pub course_id: T::Hash,
+		pub reporter: T::AccountId,
+		/// The hashes of the evidence, kept off-chain.
+		pub evidence: BoundedVec<T::Hash, T::MaxEvidence>,
+		/// The hash of the student's response, kept off-chain.
+		pub response: Option<T::Hash>,
+		/// The last block the student can respond in.
+		pub response_deadline: T::BlockNumber,
+		pub status: CaseStatus,
+	}
+
+	/// The storage version of the pallet.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types it depends on.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The courses cases are opened in.
+		type Courses: CourseInspector<Self::AccountId, Self::Hash>;
+
+		/// Origin allowed to open cases, resolving to the reporter's account.
+		type ReporterOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
+		/// Origin deciding cases.
+		type CommitteeOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Enforces the sanctions of upheld cases.
+		type OnSanction: SanctionHandler<Self::AccountId, Self::Hash>;
+
+		/// The number of blocks the student has to respond to a case.
+		#[pallet::constant]
+		type ResponsePeriod: Get<Self::BlockNumber>;
+
+		/// The maximum number of evidence hashes of a case.
+		#[pallet::constant]
+		type MaxEvidence: Get<u32>;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn cases)]
+	/// The cases, including decided ones.
+	pub(super) type Cases<T: Config> = StorageMap<_, Twox64Concat, CaseId, Case<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_case_id)]
+	/// The identifier of the next case.
+	pub(super) type NextCaseId<T: Config> = StorageValue<_, CaseId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn open_cases)]
+	/// The number of cases that were not decided yet.
+	pub(super) type OpenCases<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	// Errors.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The course does not exist.
+		CourseNotExist,
+		/// Ran out of case ids.
+		CaseIdOverflow,
+		/// The case does not exist.
+		CaseNotExist,
+		/// Only the reporter of the case can do this.
+		NotReporter,
+		/// Only the student of the case can do this.
+		NotStudent,
+		/// The case has `MaxEvidence` evidence hashes already.
+		TooMuchEvidence,
+		/// The case was decided already.
+		CaseDecided,
+		/// The student responded already, or the response period elapsed.
+		ResponseClosed,
+		/// The student can still respond.
+		AwaitingResponse,
+	}
+
+	// Events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A case was opened. \[case_id, student, course_id, response_deadline\]
+		CaseOpened(CaseId, T::AccountId, T::Hash, T::BlockNumber),
+		/// Evidence was added to a case. \[case_id, evidence\]
+		EvidenceAdded(CaseId, T::Hash),
+		/// The student responded to a case. \[case_id, response\]
+		Responded(CaseId, T::Hash),
+		/// A case was upheld. \[case_id, sanction\]
+		CaseUpheld(CaseId, Sanction),
+		/// A case was dismissed. \[case_id\]
+		CaseDismissed(CaseId),
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Self::on_chain_storage_version() >= STORAGE_VERSION {
+				return 0
+			}
+
+			// Cases opened before the counter are counted once.
+			let (mut count, mut open) = (0, 0u32);
+			for case in <Cases<T>>::iter_values() {
+				count += 1;
+				if !Self::is_decided(&case) {
+					open += 1;
+				}
+			}
+			<OpenCases<T>>::put(open);
+			STORAGE_VERSION.put::<Self>();
+
+			T::DbWeight::get().reads_writes(count + 1, 2)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Open a case against the owner of `course_id` with the hash of the evidence.
+		///
+		/// The dispatch origin for this call must be `ReporterOrigin`.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn open_case(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			evidence: T::Hash,
+		) -> DispatchResult {
+			let reporter = T::ReporterOrigin::ensure_origin(origin)?;
+			let student = T::Courses::owner_of(&course_id).ok_or(<Error<T>>::CourseNotExist)?;
+
+			let case_id = Self::next_case_id();
+			let next_id = case_id.checked_add(1).ok_or(<Error<T>>::CaseIdOverflow)?;
+			let mut evidences = BoundedVec::default();
+			evidences.try_push(evidence).map_err(|_| <Error<T>>::TooMuchEvidence)?;
+			let response_deadline =
+				<frame_system::Pallet<T>>::block_number().saturating_add(T::ResponsePeriod::get());
+			<Cases<T>>::insert(
+				case_id,
+				Case {
+					student: student.clone(),
+					course_id,
+					reporter,
+					evidence: evidences,
+					response: None,
+					response_deadline,
+					status: CaseStatus::Open,
+				},
+			);
+			<NextCaseId<T>>::put(next_id);
+			<OpenCases<T>>::mutate(|count| *count = count.saturating_add(1));
+
+			Self::deposit_event(Event::CaseOpened(case_id, student, course_id, response_deadline));
+			Ok(())
+		}
+
+		/// Add the hash of more evidence to a case of the sender that was not decided yet.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn add_evidence(
+			origin: OriginFor<T>,
+			case_id: CaseId,
+			evidence: T::Hash,
+		) -> DispatchResult {
+			let reporter = ensure_signed(origin)?;
+			let mut case = Self::cases(case_id).ok_or(<Error<T>>::CaseNotExist)?;
+			ensure!(case.reporter == reporter, <Error<T>>::NotReporter);
+			ensure!(!Self::is_decided(&case), <Error<T>>::CaseDecided);
+
+			case.evidence.try_push(evidence).map_err(|_| <Error<T>>::TooMuchEvidence)?;
+			<Cases<T>>::insert(case_id, case);
+
+			Self::deposit_event(Event::EvidenceAdded(case_id, evidence));
+			Ok(())
+		}
+
+		/// Respond to a case against the sender with the hash of the response, within the
+		/// response period.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn respond(origin: OriginFor<T>, case_id: CaseId, response: T::Hash) -> DispatchResult {
+			let student = ensure_signed(origin)?;
+			let mut case = Self::cases(case_id).ok_or(<Error<T>>::CaseNotExist)?;
+			ensure!(case.student == student, <Error<T>>::NotStudent);
+			ensure!(
+				case.status == CaseStatus::Open &&
+					<frame_system::Pallet<T>>::block_number() <= case.response_deadline,
+				<Error<T>>::ResponseClosed
+			);
+
+			case.response = Some(response);
+			case.status = CaseStatus::UnderReview;
+			<Cases<T>>::insert(case_id, case);
+
+			Self::deposit_event(Event::Responded(case_id, response));
+			Ok(())
+		}
+
+		/// Decide a case once the student responded or the response period elapsed, upholding it
+		/// with `sanction` or dismissing it if `sanction` is `None`.
+		///
+		/// The dispatch origin for this call must be `CommitteeOrigin`.
+		#[pallet::weight((
+			10_000 + T::DbWeight::get().reads_writes(2, 2),
+			DispatchClass::Operational,
+		))]
+		pub fn decide(
+			origin: OriginFor<T>,
+			case_id: CaseId,
+			sanction: Option<Sanction>,
+		) -> DispatchResultWithPostInfo {
+			T::CommitteeOrigin::ensure_origin(origin)?;
+			let mut case = Self::cases(case_id).ok_or(<Error<T>>::CaseNotExist)?;
+			ensure!(!Self::is_decided(&case), <Error<T>>::CaseDecided);
+			ensure!(
+				case.status == CaseStatus::UnderReview ||
+					<frame_system::Pallet<T>>::block_number() > case.response_deadline,
+				<Error<T>>::AwaitingResponse
+			);
+
+			match sanction {
+				Some(sanction) => {
+					case.status = CaseStatus::Upheld(sanction);
+					T::OnSanction::on_sanction(&case.student, &case.course_id, sanction);
+					Self::deposit_event(Event::CaseUpheld(case_id, sanction));
+				},
+				None => {
+					case.status = CaseStatus::Dismissed;
+					Self::deposit_event(Event::CaseDismissed(case_id));
+				},
+			}
+			<Cases<T>>::insert(case_id, case);
+			<OpenCases<T>>::mutate(|count| *count = count.saturating_sub(1));
+			Ok(Pays::No.into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn is_decided(case: &Case<T>) -> bool {
+			matches!(case.status, CaseStatus::Upheld(_) | CaseStatus::Dismissed)
+		}
+	}
+}
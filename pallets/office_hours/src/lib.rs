@@ -0,0 +1,316 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Office hours booked on-chain.
+//!
+//! Hosts publish bookable slots for a course. The owner of the course books at most one slot of
+//! the course per week, reserving `BookingDeposit`, and can cancel the booking until the slot
+//! starts. Once the slot started the host settles it: the deposit is returned if the student
+//! came and slashed if they did not. If the host does not settle a slot within a week of its
+//! end, the student reclaims the deposit.
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		sp_runtime::traits::{Saturating, UniqueSaturatedInto, Zero},
+		traits::{Currency, ReservableCurrency},
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_course_passing_primitives::CourseInspector;
+
+	type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// Identifier of a slot.
+	pub type SlotId = u32;
+
+	// Struct for holding a booking of a slot.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Booking<T: Config> {
+		pub student: T::AccountId,
+		pub deposit: BalanceOf<T>,
+	}
+
+	// Struct for holding a slot of office hours.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Slot<T: Config> {
+		pub host: T::AccountId,
+		pub course_id: T::Hash,
+		pub start: T::BlockNumber,
+		pub end: T::BlockNumber,
+		pub booking: Option<Booking<T>>,
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types it depends on.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency the booking deposits are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The courses office hours are held for.
+		type Courses: CourseInspector<Self::AccountId, Self::Hash>;
+
+		/// Origin allowed to publish slots, resolving to the host's account.
+		type HostOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
+		/// The deposit reserved for a booking, slashed if the student does not come.
+		#[pallet::constant]
+		type BookingDeposit: Get<BalanceOf<Self>>;
+
+		/// The number of blocks in a week, the period a student books one slot of a course in.
+		#[pallet::constant]
+		type BlocksPerWeek: Get<Self::BlockNumber>;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn slots)]
+	/// The published slots, until they are settled or cancelled.
+	pub(super) type Slots<T: Config> = StorageMap<_, Twox64Concat, SlotId, Slot<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_slot_id)]
+	/// The identifier of the next slot.
+	pub(super) type NextSlotId<T: Config> = StorageValue<_, SlotId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn weekly_bookings)]
+	/// The slot each student booked per course and week, weeks counted in `BlocksPerWeek` from
+	/// genesis.
+	pub(super) type WeeklyBookings<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, (T::Hash, u32), SlotId>;
+
+	// Errors.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The course does not exist.
+		CourseNotExist,
+		/// A slot must start in the future and end after it starts.
+		InvalidSlot,
+		/// Ran out of slot ids.
+		SlotIdOverflow,
+		/// The slot does not exist.
+		SlotNotExist,
+		/// Only the host of the slot can do this.
+		NotHost,
+		/// Only the owner of the course can book its slots.
+		NotCourseOwner,
+		/// The slot is booked already.
+		SlotTaken,
+		/// The student booked a slot of the course this week already.
+		WeeklyBookingTaken,
+		/// The student cannot reserve the deposit.
+		NotEnoughBalance,
+		/// The slot is not booked.
+		NotBooked,
+		/// The slot is booked by another account.
+		NotBooker,
+		/// The slot started already.
+		SlotStarted,
+		/// The slot has not started yet.
+		SlotNotStarted,
+		/// The host can still settle the slot.
+		SettlementPending,
+	}
+
+	// Events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A slot was published. \[slot_id, host, course_id, start\]
+		SlotPublished(SlotId, T::AccountId, T::Hash, T::BlockNumber),
+		/// A slot was cancelled by its host. \[slot_id\]
+		SlotCancelled(SlotId),
+		/// A slot was booked. \[slot_id, student\]
+		SlotBooked(SlotId, T::AccountId),
+		/// A booking was cancelled by the student. \[slot_id, student\]
+		BookingCancelled(SlotId, T::AccountId),
+		/// The student came to the booked slot. \[slot_id, student\]
+		Attended(SlotId, T::AccountId),
+		/// The student did not come to the booked slot and lost the deposit.
+		/// \[slot_id, student, forfeited\]
+		NoShow(SlotId, T::AccountId, BalanceOf<T>),
+		/// The student reclaimed the deposit of an unsettled slot. \[slot_id, student\]
+		DepositReclaimed(SlotId, T::AccountId),
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Publish a slot of office hours for `course_id` from block `start` to block `end`.
+		///
+		/// The dispatch origin for this call must be `HostOrigin`.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn publish_slot(
+			origin: OriginFor<T>,
+			course_id: T::Hash,
+			start: T::BlockNumber,
+			end: T::BlockNumber,
+		) -> DispatchResult {
+			let host = T::HostOrigin::ensure_origin(origin)?;
+			ensure!(T::Courses::exists(&course_id), <Error<T>>::CourseNotExist);
+			ensure!(
+				start > <frame_system::Pallet<T>>::block_number() && end > start,
+				<Error<T>>::InvalidSlot
+			);
+
+			let slot_id = Self::next_slot_id();
+			let next_id = slot_id.checked_add(1).ok_or(<Error<T>>::SlotIdOverflow)?;
+			<Slots<T>>::insert(
+				slot_id,
+				Slot { host: host.clone(), course_id, start, end, booking: None },
+			);
+			<NextSlotId<T>>::put(next_id);
+
+			Self::deposit_event(Event::SlotPublished(slot_id, host, course_id, start));
+			Ok(())
+		}
+
+		/// Cancel a slot of the sender, returning the deposit of its booking.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2))]
+		pub fn cancel_slot(origin: OriginFor<T>, slot_id: SlotId) -> DispatchResult {
+			let host = ensure_signed(origin)?;
+			let slot = Self::slots(slot_id).ok_or(<Error<T>>::SlotNotExist)?;
+			ensure!(slot.host == host, <Error<T>>::NotHost);
+
+			if let Some(booking) = &slot.booking {
+				T::Currency::unreserve(&booking.student, booking.deposit);
+				Self::remove_weekly_booking(&slot, &booking.student);
+			}
+			<Slots<T>>::remove(slot_id);
+
+			Self::deposit_event(Event::SlotCancelled(slot_id));
+			Ok(())
+		}
+
+		/// Book a slot of a course of the sender, reserving `BookingDeposit`.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2))]
+		pub fn book(origin: OriginFor<T>, slot_id: SlotId) -> DispatchResult {
+			let student = ensure_signed(origin)?;
+			let mut slot = Self::slots(slot_id).ok_or(<Error<T>>::SlotNotExist)?;
+			ensure!(slot.booking.is_none(), <Error<T>>::SlotTaken);
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() < slot.start,
+				<Error<T>>::SlotStarted
+			);
+			ensure!(
+				T::Courses::owner_of(&slot.course_id).as_ref() == Some(&student),
+				<Error<T>>::NotCourseOwner
+			);
+			let week = (slot.course_id, Self::week_of(slot.start));
+			ensure!(
+				!<WeeklyBookings<T>>::contains_key(&student, &week),
+				<Error<T>>::WeeklyBookingTaken
+			);
+
+			let deposit = T::BookingDeposit::get();
+			T::Currency::reserve(&student, deposit).map_err(|_| <Error<T>>::NotEnoughBalance)?;
+			<WeeklyBookings<T>>::insert(&student, &week, slot_id);
+			slot.booking = Some(Booking { student: student.clone(), deposit });
+			<Slots<T>>::insert(slot_id, slot);
+
+			Self::deposit_event(Event::SlotBooked(slot_id, student));
+			Ok(())
+		}
+
+		/// Cancel the sender's booking of a slot that has not started, returning the deposit.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2))]
+		pub fn cancel_booking(origin: OriginFor<T>, slot_id: SlotId) -> DispatchResult {
+			let student = ensure_signed(origin)?;
+			let mut slot = Self::slots(slot_id).ok_or(<Error<T>>::SlotNotExist)?;
+			let booking = Self::take_booking_of(&mut slot, &student)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() < slot.start,
+				<Error<T>>::SlotStarted
+			);
+
+			T::Currency::unreserve(&student, booking.deposit);
+			Self::remove_weekly_booking(&slot, &student);
+			<Slots<T>>::insert(slot_id, slot);
+
+			Self::deposit_event(Event::BookingCancelled(slot_id, student));
+			Ok(())
+		}
+
+		/// Record whether the student came to a booked slot of the sender, returning their
+		/// deposit if they did and slashing it otherwise.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 3))]
+		pub fn settle(origin: OriginFor<T>, slot_id: SlotId, attended: bool) -> DispatchResult {
+			let host = ensure_signed(origin)?;
+			let slot = Self::slots(slot_id).ok_or(<Error<T>>::SlotNotExist)?;
+			ensure!(slot.host == host, <Error<T>>::NotHost);
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= slot.start,
+				<Error<T>>::SlotNotStarted
+			);
+			let booking = slot.booking.clone().ok_or(<Error<T>>::NotBooked)?;
+
+			Self::remove_weekly_booking(&slot, &booking.student);
+			<Slots<T>>::remove(slot_id);
+
+			if attended {
+				T::Currency::unreserve(&booking.student, booking.deposit);
+				Self::deposit_event(Event::Attended(slot_id, booking.student));
+			} else {
+				let _ = T::Currency::slash_reserved(&booking.student, booking.deposit);
+				Self::deposit_event(Event::NoShow(slot_id, booking.student, booking.deposit));
+			}
+			Ok(())
+		}
+
+		/// Reclaim the deposit of a booked slot its host did not settle within `BlocksPerWeek`
+		/// blocks of its end.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 3))]
+		pub fn reclaim_deposit(origin: OriginFor<T>, slot_id: SlotId) -> DispatchResult {
+			let student = ensure_signed(origin)?;
+			let mut slot = Self::slots(slot_id).ok_or(<Error<T>>::SlotNotExist)?;
+			let booking = Self::take_booking_of(&mut slot, &student)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >
+					slot.end.saturating_add(T::BlocksPerWeek::get()),
+				<Error<T>>::SettlementPending
+			);
+
+			T::Currency::unreserve(&student, booking.deposit);
+			Self::remove_weekly_booking(&slot, &student);
+			<Slots<T>>::remove(slot_id);
+
+			Self::deposit_event(Event::DepositReclaimed(slot_id, student));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The week `block` is in, counted from genesis.
+		pub fn week_of(block: T::BlockNumber) -> u32 {
+			let blocks_per_week = T::BlocksPerWeek::get();
+			if blocks_per_week.is_zero() {
+				return 0
+			}
+			(block / blocks_per_week).unique_saturated_into()
+		}
+
+		fn take_booking_of(
+			slot: &mut Slot<T>,
+			student: &T::AccountId,
+		) -> Result<Booking<T>, Error<T>> {
+			let booking = slot.booking.take().ok_or(<Error<T>>::NotBooked)?;
+			ensure!(booking.student == *student, <Error<T>>::NotBooker);
+			Ok(booking)
+		}
+
+		fn remove_weekly_booking(slot: &Slot<T>, student: &T::AccountId) {
+			<WeeklyBookings<T>>::remove(student, (slot.course_id, Self::week_of(slot.start)));
+		}
+	}
+}
@@ -0,0 +1,119 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Runtime parameters tuned by governance instead of runtime upgrades.
+//!
+//! The runtime lists the parameters it makes adjustable as the variants of a `Key` type, each
+//! with the bounds governance can set it within and the value it has until then. Pallets read a
+//! parameter through [`ParameterOf`], which implements `Get` and can be used wherever a pallet
+//! takes a constant.
+//!
+//! Parameters bounding the length of stored collections must stay compile-time constants, as
+//! lowering them would make the stored collections undecodable.
+
+pub use pallet::*;
+
+use frame_support::{
+	sp_runtime::traits::UniqueSaturatedFrom, sp_std::marker::PhantomData, traits::Get,
+};
+
+/// The parameters a runtime makes adjustable, with the values they can take.
+pub trait ParameterSet {
+	/// The lowest and the highest value the parameter can be set to.
+	fn bounds(&self) -> (u128, u128);
+	/// The value of the parameter until governance sets one.
+	fn default_value(&self) -> u128;
+}
+
+/// The current value of the parameter `K::get()`, as a `V`.
+pub struct ParameterOf<T, K, V>(PhantomData<(T, K, V)>);
+
+impl<T: Config, K: Get<T::Key>, V: UniqueSaturatedFrom<u128>> Get<V> for ParameterOf<T, K, V> {
+	fn get() -> V {
+		V::unique_saturated_from(Pallet::<T>::value(&K::get()))
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::ParameterSet;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types it depends on.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The adjustable parameters.
+		type Key: ParameterSet + Parameter + MaxEncodedLen;
+
+		/// The origin allowed to set the parameters.
+		type SetOrigin: EnsureOrigin<Self::Origin>;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn values)]
+	/// The parameters set by governance. The others have their default value.
+	pub(super) type Values<T: Config> = StorageMap<_, Twox64Concat, T::Key, u128>;
+
+	// Errors.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The value is outside the bounds of the parameter.
+		OutOfBounds,
+		/// The parameter has its default value already.
+		NotSet,
+	}
+
+	// Events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A parameter was set. \[key, value\]
+		ParameterSet(T::Key, u128),
+		/// A parameter was reset to its default value. \[key, value\]
+		ParameterReset(T::Key, u128),
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the parameter `key` to `value`, within its bounds.
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn set_parameter(origin: OriginFor<T>, key: T::Key, value: u128) -> DispatchResult {
+			T::SetOrigin::ensure_origin(origin)?;
+			let (min, max) = key.bounds();
+			ensure!(min <= value && value <= max, <Error<T>>::OutOfBounds);
+
+			<Values<T>>::insert(&key, value);
+
+			Self::deposit_event(Event::ParameterSet(key, value));
+			Ok(())
+		}
+
+		/// Reset the parameter `key` to its default value.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn reset_parameter(origin: OriginFor<T>, key: T::Key) -> DispatchResult {
+			T::SetOrigin::ensure_origin(origin)?;
+			ensure!(<Values<T>>::contains_key(&key), <Error<T>>::NotSet);
+
+			<Values<T>>::remove(&key);
+
+			let value = key.default_value();
+			Self::deposit_event(Event::ParameterReset(key, value));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The current value of the parameter `key`. A value set before its bounds were narrowed
+		/// by a runtime upgrade is clamped to them.
+		pub fn value(key: &T::Key) -> u128 {
+			let (min, max) = key.bounds();
+			Self::values(key).map_or_else(|| key.default_value(), |value| value.clamp(min, max))
+		}
+	}
+}
@@ -0,0 +1,221 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Course market price oracle.
+//!
+//! At the start of every `UpdateInterval` blocks, the off-chain worker of every oracle node takes
+//! the recent sale prices of each course collection, computes their median and submits it
+//! on-chain as the collection's floor price with a signed transaction. The first submission of an
+//! interval is kept; later ones are rejected.
+//!
+//! Other pallets read the published prices through the [`FloorPrice`] trait.
+
+pub use pallet::*;
+
+use sp_core::crypto::KeyTypeId;
+use sp_std::vec::Vec;
+
+/// Key type of the oracle signing keys, inserted in the keystore of oracle nodes.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"orcl");
+
+/// The crypto of the oracle signing keys.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_core::sr25519::Signature as Sr25519Signature;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		traits::Verify,
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	pub struct OracleAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for OracleAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+
+	// Used for signing the oracle transactions in the runtime.
+	impl frame_system::offchain::AppCrypto<<Sr25519Signature as Verify>::Signer, Sr25519Signature>
+		for OracleAuthId
+	{
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// Source of the recent sale prices the floor prices are computed from.
+pub trait SaleHistory<Collection, Balance> {
+	/// The collections that have sales to report.
+	fn collections() -> Vec<Collection>;
+	/// The most recent sale prices of `collection`, in no particular order.
+	fn recent_sales(collection: &Collection) -> Vec<Balance>;
+}
+
+/// Read access to the published floor prices.
+pub trait FloorPrice<Collection, Balance> {
+	/// The last floor price published for `collection`, if any.
+	fn floor_price(collection: &Collection) -> Option<Balance>;
+}
+
+/// The median of `prices`, rounding down between the two middle prices.
+pub fn median<Balance>(mut prices: Vec<Balance>) -> Option<Balance>
+where
+	Balance: sp_runtime::traits::AtLeast32BitUnsigned + Copy,
+{
+	if prices.is_empty() {
+		return None
+	}
+	prices.sort();
+	let mid = prices.len() / 2;
+	if prices.len() % 2 == 0 {
+		let (low, high) = (prices[mid - 1], prices[mid]);
+		Some(low + (high - low) / 2u32.into())
+	} else {
+		Some(prices[mid])
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::{median, FloorPrice, SaleHistory};
+	use frame_support::{pallet_prelude::*, traits::Contains};
+	use frame_system::{
+		offchain::{AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer},
+		pallet_prelude::*,
+	};
+	use sp_runtime::traits::{AtLeast32BitUnsigned, Hash, Zero};
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types it depends on.
+	#[pallet::config]
+	pub trait Config: CreateSignedTransaction<Call<Self>> + frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The identifier type for the oracle signing keys.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// A group of courses priced together.
+		type Collection: Parameter + MaxEncodedLen;
+
+		/// The unit prices are expressed in.
+		type Balance: Parameter + MaxEncodedLen + AtLeast32BitUnsigned + Copy;
+
+		/// Where the recent sale prices are read from.
+		type Sales: SaleHistory<Self::Collection, Self::Balance>;
+
+		/// The accounts allowed to submit floor prices.
+		type Oracles: Contains<Self::AccountId>;
+
+		/// The number of blocks between two floor price updates of a collection.
+		#[pallet::constant]
+		type UpdateInterval: Get<Self::BlockNumber>;
+	}
+
+	// Errors.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The sender is not an oracle.
+		NotOracle,
+		/// The floor price of the collection was already updated in this interval.
+		TooEarly,
+	}
+
+	// Events.
+	//
+	// Events are indexed by the `collection` topic, derived as in `pallet_course_passing`.
+	#[pallet::event]
+	pub enum Event<T: Config> {
+		/// The floor price of a collection was updated. \[oracle, collection, price\]
+		FloorPriceUpdated(T::AccountId, T::Collection, T::Balance),
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn floor_prices)]
+	/// The last floor price of each collection and the block it was published at.
+	pub(super) type FloorPrices<T: Config> =
+		StorageMap<_, Twox64Concat, T::Collection, (T::Balance, T::BlockNumber)>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn offchain_worker(now: T::BlockNumber) {
+			if !(now % T::UpdateInterval::get()).is_zero() {
+				return
+			}
+
+			let signer = Signer::<T, T::AuthorityId>::any_account();
+			if !signer.can_sign() {
+				return
+			}
+
+			for collection in T::Sales::collections() {
+				if !Self::is_due(&collection, now) {
+					continue
+				}
+				let price = match median(T::Sales::recent_sales(&collection)) {
+					Some(price) => price,
+					None => continue,
+				};
+
+				let result = signer.send_signed_transaction(|_| Call::submit_floor_price {
+					collection: collection.clone(),
+					price,
+				});
+				if let Some((_, Err(()))) = result {
+					log::warn!("Failed to submit the floor price of {:?}.", collection);
+				}
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Publish the floor price of a collection.
+		///
+		/// Only the first submission of each update interval is accepted, and it is free for the
+		/// oracle.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn submit_floor_price(
+			origin: OriginFor<T>,
+			collection: T::Collection,
+			price: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			let oracle = ensure_signed(origin)?;
+			ensure!(T::Oracles::contains(&oracle), <Error<T>>::NotOracle);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(Self::is_due(&collection, now), <Error<T>>::TooEarly);
+
+			<FloorPrices<T>>::insert(&collection, (price, now));
+
+			let topic = T::Hashing::hash_of(&(b"collection", &collection));
+			let event = <<T as Config>::Event as From<Event<T>>>::from(Event::FloorPriceUpdated(
+				oracle, collection, price,
+			));
+			<frame_system::Pallet<T>>::deposit_event_indexed(&[topic], event.into());
+			Ok(Pays::No.into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether the floor price of `collection` was last published in an earlier interval.
+		fn is_due(collection: &T::Collection, now: T::BlockNumber) -> bool {
+			let interval = T::UpdateInterval::get();
+			match Self::floor_prices(collection) {
+				Some((_, updated)) => updated / interval < now / interval,
+				None => true,
+			}
+		}
+	}
+
+	impl<T: Config> FloorPrice<T::Collection, T::Balance> for Pallet<T> {
+		fn floor_price(collection: &T::Collection) -> Option<T::Balance> {
+			Self::floor_prices(collection).map(|(price, _)| price)
+		}
+	}
+}
@@ -9,7 +9,6 @@ pub mod pallet {
 
 	// The struct on which we build all of our Pallet logic.
 	#[pallet::pallet]
-	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(_);
 
 	/* Placeholder for defining custom types. */
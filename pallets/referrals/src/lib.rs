@@ -0,0 +1,157 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Referral rewards for onboarding new students.
+//!
+//! A new student names the account that referred them with `set_referrer`, before their first
+//! paid action. The runtime reports paid actions with `note_paid_action`. The first one of a
+//! referred student pays `ReferralReward` to the referrer from the pot of the pallet, an account
+//! funded by plain transfers. If the pot cannot afford the reward, the referral is settled without
+//! one.
+//!
+//! Nobody can refer themselves, and a student cannot name a referrer they referred, directly or
+//! through other referrals, so the referrals never form a cycle.
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		sp_runtime::traits::AccountIdConversion,
+		traits::{Currency, ExistenceRequirement},
+		PalletId,
+	};
+	use frame_system::pallet_prelude::*;
+
+	type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types it depends on.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency the rewards are paid in.
+		type Currency: Currency<Self::AccountId>;
+
+		/// The identifier the account of the reward pot is derived from.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The reward paid to the referrer on the first paid action of a student.
+		#[pallet::constant]
+		type ReferralReward: Get<BalanceOf<Self>>;
+
+		/// The maximum number of referrals above a new referrer. Longer chains cannot be
+		/// extended, which bounds the cycle check.
+		#[pallet::constant]
+		type MaxChainDepth: Get<u32>;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn referrers)]
+	/// The account that referred each student.
+	pub(super) type Referrers<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn is_active)]
+	/// The accounts that made a paid action already, and can no longer name a referrer.
+	pub(super) type Active<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	// Errors.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// An account cannot refer itself.
+		SelfReferral,
+		/// The account named its referrer already.
+		ReferrerSet,
+		/// The account made a paid action already.
+		AlreadyActive,
+		/// The referrer was referred by the account, directly or through other referrals.
+		ReferralCycle,
+		/// The referrer is more than `MaxChainDepth` referrals down a chain.
+		ChainTooLong,
+	}
+
+	// Events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A student named its referrer. \[student, referrer\]
+		ReferrerSet(T::AccountId, T::AccountId),
+		/// A referrer was rewarded for the first paid action of a student.
+		/// \[student, referrer, reward\]
+		ReferralRewarded(T::AccountId, T::AccountId, BalanceOf<T>),
+		/// The pot could not afford the reward of a referral, which was settled without it.
+		/// \[student, referrer\]
+		RewardUnpaid(T::AccountId, T::AccountId),
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Name the account that referred the sender, before the sender's first paid action.
+		#[pallet::weight(
+			10_000 +
+				T::DbWeight::get().reads_writes(T::MaxChainDepth::get() as Weight + 3, 1)
+		)]
+		pub fn set_referrer(origin: OriginFor<T>, referrer: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(who != referrer, <Error<T>>::SelfReferral);
+			ensure!(!<Referrers<T>>::contains_key(&who), <Error<T>>::ReferrerSet);
+			ensure!(!<Active<T>>::contains_key(&who), <Error<T>>::AlreadyActive);
+
+			// The sender has no referrer yet, so the only possible cycle goes through the
+			// referrers above `referrer`.
+			let mut above = Self::referrers(&referrer);
+			for _ in 0..T::MaxChainDepth::get() {
+				match above {
+					Some(account) => {
+						ensure!(account != who, <Error<T>>::ReferralCycle);
+						above = Self::referrers(&account);
+					},
+					None => break,
+				}
+			}
+			ensure!(above.is_none(), <Error<T>>::ChainTooLong);
+
+			<Referrers<T>>::insert(&who, &referrer);
+
+			Self::deposit_event(Event::ReferrerSet(who, referrer));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account of the reward pot.
+		pub fn pot() -> T::AccountId {
+			T::PalletId::get().into_account()
+		}
+
+		/// `who` paid for something, rewarding its referrer if it is its first paid action.
+		pub fn note_paid_action(who: &T::AccountId) {
+			if <Active<T>>::contains_key(who) {
+				return
+			}
+			<Active<T>>::insert(who, ());
+
+			if let Some(referrer) = Self::referrers(who) {
+				let reward = T::ReferralReward::get();
+				let paid = T::Currency::transfer(
+					&Self::pot(),
+					&referrer,
+					reward,
+					ExistenceRequirement::KeepAlive,
+				);
+				Self::deposit_event(match paid {
+					Ok(()) => Event::ReferralRewarded(who.clone(), referrer, reward),
+					Err(_) => Event::RewardUnpaid(who.clone(), referrer),
+				});
+			}
+		}
+	}
+}
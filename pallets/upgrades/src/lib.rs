@@ -0,0 +1,156 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Runtime upgrade governance.
+//!
+//! No single key can replace the runtime. The Senate proposes the new code, which the scheduler
+//! enacts with the root origin `EnactmentDelay` blocks later, leaving members and validators time
+//! to review it and prepare their nodes. The Senate can cancel the upgrade until then. For
+//! emergency fixes, the technical committee can bring the enactment forward to `FastTrackDelay`
+//! blocks. Only one upgrade is scheduled at a time.
+
+pub use pallet::*;
+
+/// The name the pending upgrade is scheduled under.
+pub const UPGRADE_ID: &[u8] = b"upgrades::pending";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::UPGRADE_ID;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::schedule::{DispatchTime, Named as ScheduleNamed, HARD_DEADLINE},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Hash, Saturating};
+	use sp_std::vec::Vec;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types it depends on.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The runtime call the `set_code` call is scheduled as.
+		type Call: From<frame_system::Call<Self>>;
+
+		/// The origin the scheduler dispatches the upgrade with.
+		type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+
+		/// The scheduler enacting the upgrades.
+		type Scheduler: ScheduleNamed<
+			Self::BlockNumber,
+			<Self as Config>::Call,
+			Self::PalletsOrigin,
+		>;
+
+		/// Origin allowed to schedule and cancel an upgrade.
+		type ProposeOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin allowed to fast-track a scheduled upgrade.
+		type FastTrackOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The number of blocks between scheduling an upgrade and its enactment.
+		#[pallet::constant]
+		type EnactmentDelay: Get<Self::BlockNumber>;
+
+		/// The number of blocks a fast-tracked upgrade is enacted after.
+		#[pallet::constant]
+		type FastTrackDelay: Get<Self::BlockNumber>;
+	}
+
+	// Errors.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// An upgrade is already scheduled.
+		UpgradePending,
+		/// No upgrade is scheduled.
+		NoUpgradePending,
+		/// The upgrade is already enacted sooner than a fast-track would.
+		AlreadySooner,
+		/// The scheduler refused the upgrade.
+		ScheduleFailed,
+	}
+
+	// Events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A runtime upgrade was scheduled. \[code_hash, enactment_block\]
+		UpgradeScheduled(T::Hash, T::BlockNumber),
+		/// The pending upgrade was fast-tracked. \[enactment_block\]
+		UpgradeFastTracked(T::BlockNumber),
+		/// The pending upgrade was cancelled.
+		UpgradeCancelled,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Schedule the runtime to be replaced by `code` in `EnactmentDelay` blocks.
+		#[pallet::weight((
+			10_000 + T::DbWeight::get().reads_writes(1, 1),
+			DispatchClass::Operational,
+		))]
+		pub fn propose_upgrade(origin: OriginFor<T>, code: Vec<u8>) -> DispatchResultWithPostInfo {
+			T::ProposeOrigin::ensure_origin(origin)?;
+			ensure!(Self::pending_upgrade().is_none(), <Error<T>>::UpgradePending);
+
+			let code_hash = T::Hashing::hash(&code);
+			let when =
+				<frame_system::Pallet<T>>::block_number().saturating_add(T::EnactmentDelay::get());
+			T::Scheduler::schedule_named(
+				UPGRADE_ID.to_vec(),
+				DispatchTime::At(when),
+				None,
+				HARD_DEADLINE,
+				frame_system::RawOrigin::Root.into(),
+				frame_system::Call::set_code { code }.into(),
+			)
+			.map_err(|_| <Error<T>>::ScheduleFailed)?;
+
+			Self::deposit_event(Event::UpgradeScheduled(code_hash, when));
+			Ok(Pays::No.into())
+		}
+
+		/// Enact the pending upgrade `FastTrackDelay` blocks from now.
+		#[pallet::weight((
+			10_000 + T::DbWeight::get().reads_writes(1, 1),
+			DispatchClass::Operational,
+		))]
+		pub fn fast_track_upgrade(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			T::FastTrackOrigin::ensure_origin(origin)?;
+			let scheduled = Self::pending_upgrade().ok_or(<Error<T>>::NoUpgradePending)?;
+
+			let when =
+				<frame_system::Pallet<T>>::block_number().saturating_add(T::FastTrackDelay::get());
+			ensure!(when < scheduled, <Error<T>>::AlreadySooner);
+			T::Scheduler::reschedule_named(UPGRADE_ID.to_vec(), DispatchTime::At(when))?;
+
+			Self::deposit_event(Event::UpgradeFastTracked(when));
+			Ok(Pays::No.into())
+		}
+
+		/// Cancel the pending upgrade.
+		#[pallet::weight((
+			10_000 + T::DbWeight::get().reads_writes(1, 1),
+			DispatchClass::Operational,
+		))]
+		pub fn cancel_upgrade(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			T::ProposeOrigin::ensure_origin(origin)?;
+			T::Scheduler::cancel_named(UPGRADE_ID.to_vec())
+				.map_err(|_| <Error<T>>::NoUpgradePending)?;
+
+			Self::deposit_event(Event::UpgradeCancelled);
+			Ok(Pays::No.into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The block the pending upgrade is enacted at, if one is scheduled.
+		pub fn pending_upgrade() -> Option<T::BlockNumber> {
+			T::Scheduler::next_dispatch_time(UPGRADE_ID.to_vec()).ok()
+		}
+	}
+}
@@ -0,0 +1,201 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Validator set management for the university consortium.
+//!
+//! The pallet keeps the list of consortium validators and hands it to `pallet_session` through
+//! the `SessionManager` trait. Additions and removals are queued and only take effect on the next
+//! session boundary, so validators can join, leave or rotate their session keys (with
+//! `session.set_keys`) without halting block production.
+//!
+//! The whole set is handed over on every session, as the session pallet leaves out the validators
+//! that have no keys yet and would otherwise never queue them once they set their keys.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{pallet_prelude::*, traits::Contains};
+	use frame_system::pallet_prelude::*;
+	use sp_staking::SessionIndex;
+	use sp_std::vec::Vec;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types it depends on.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Origin allowed to add or remove validators.
+		type AddRemoveOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The minimum number of validators that must remain in the set.
+		#[pallet::constant]
+		type MinValidators: Get<u32>;
+
+		/// The maximum number of validators in the set.
+		#[pallet::constant]
+		type MaxValidators: Get<u32>;
+	}
+
+	// Errors.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is already a validator.
+		AlreadyValidator,
+		/// The account is not a validator.
+		NotValidator,
+		/// Removing the validator would bring the set below `MinValidators`.
+		TooFewValidators,
+		/// Adding the validator would bring the set above `MaxValidators`.
+		TooManyValidators,
+	}
+
+	// Events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A validator was queued for addition. \[validator\]
+		ValidatorAdded(T::AccountId),
+		/// A validator was queued for removal. \[validator\]
+		ValidatorRemoved(T::AccountId),
+		/// A changed validator set was handed to the session pallet. \[session_index\]
+		ValidatorSetRotated(SessionIndex),
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn validators)]
+	/// The current (possibly not yet enacted) validator set.
+	pub(super) type Validators<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxValidators>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn validators_changed)]
+	/// Whether `Validators` changed since the last session boundary.
+	pub(super) type ValidatorsChanged<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	// Our pallet's genesis configuration.
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub validators: Vec<T::AccountId>,
+	}
+
+	// Required to implement default for GenesisConfig.
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> GenesisConfig<T> {
+			GenesisConfig { validators: vec![] }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let validators: BoundedVec<T::AccountId, T::MaxValidators> =
+				self.validators.clone().try_into().expect("Too many genesis validators");
+			<Validators<T>>::put(validators);
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			ensure!(
+				Self::validators().len() as u32 >= T::MinValidators::get(),
+				"The validator set is smaller than MinValidators"
+			);
+			Ok(())
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Add a validator to the set.
+		///
+		/// The validator starts authoring two sessions after it is added or, if it has no session
+		/// keys yet, after it sets them.
+		#[pallet::weight((10_000, DispatchClass::Operational))]
+		pub fn add_validator(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			<Validators<T>>::try_mutate(|validators| -> DispatchResult {
+				ensure!(!validators.contains(&validator), <Error<T>>::AlreadyValidator);
+				validators
+					.try_push(validator.clone())
+					.map_err(|_| <Error<T>>::TooManyValidators)?;
+				Ok(())
+			})?;
+			<ValidatorsChanged<T>>::put(true);
+
+			Self::deposit_event(Event::ValidatorAdded(validator));
+			Ok(Pays::No.into())
+		}
+
+		/// Remove a validator from the set.
+		///
+		/// The validator keeps authoring until the removal is enacted on a session boundary.
+		#[pallet::weight((10_000, DispatchClass::Operational))]
+		pub fn remove_validator(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			<Validators<T>>::try_mutate(|validators| -> DispatchResult {
+				let ind = validators
+					.iter()
+					.position(|v| *v == validator)
+					.ok_or(<Error<T>>::NotValidator)?;
+				ensure!(
+					validators.len() as u32 > T::MinValidators::get(),
+					<Error<T>>::TooFewValidators
+				);
+				validators.remove(ind);
+				Ok(())
+			})?;
+			<ValidatorsChanged<T>>::put(true);
+
+			Self::deposit_event(Event::ValidatorRemoved(validator));
+			Ok(Pays::No.into())
+		}
+	}
+
+	impl<T: Config> Contains<T::AccountId> for Pallet<T> {
+		fn contains(who: &T::AccountId) -> bool {
+			Self::validators().contains(who)
+		}
+	}
+
+	impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
+		fn new_session(new_index: SessionIndex) -> Option<Vec<T::AccountId>> {
+			// Hand over the set even if it did not change, so validators that were added before
+			// setting their keys are queued as soon as they have them.
+			if <ValidatorsChanged<T>>::take() {
+				log::info!("Rotating the validator set for session {}.", new_index);
+				Self::deposit_event(Event::ValidatorSetRotated(new_index));
+			}
+
+			Some(Self::validators().into_inner())
+		}
+
+		fn new_session_genesis(_new_index: SessionIndex) -> Option<Vec<T::AccountId>> {
+			Some(Self::validators().into_inner())
+		}
+
+		fn end_session(_end_index: SessionIndex) {}
+
+		fn start_session(_start_index: SessionIndex) {}
+	}
+}
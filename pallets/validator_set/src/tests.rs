@@ -0,0 +1,83 @@
+use crate::{mock::*, Error};
+use frame_support::{assert_noop, assert_ok};
+use pallet_session::SessionManager;
+
+fn rotated(session: u32) -> bool {
+	System::events().iter().any(|record| {
+		record.event == Event::ValidatorSet(crate::Event::ValidatorSetRotated(session))
+	})
+}
+
+#[test]
+fn genesis_validators_start_the_chain() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ValidatorSet::new_session_genesis(0), Some(vec![1, 2]));
+	});
+}
+
+#[test]
+fn validator_set_is_handed_over_every_session() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ValidatorSet::new_session(1), Some(vec![1, 2]));
+		assert_eq!(ValidatorSet::new_session(2), Some(vec![1, 2]));
+		assert!(!rotated(1) && !rotated(2));
+	});
+}
+
+#[test]
+fn added_validator_is_enacted_at_the_next_session() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 3));
+		assert!(ValidatorSet::validators_changed());
+
+		assert_eq!(ValidatorSet::new_session(1), Some(vec![1, 2, 3]));
+		assert!(rotated(1));
+		assert!(!ValidatorSet::validators_changed());
+
+		assert_eq!(ValidatorSet::new_session(2), Some(vec![1, 2, 3]));
+		assert!(!rotated(2));
+	});
+}
+
+#[test]
+fn removed_validator_is_enacted_at_the_next_session() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 3));
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 1));
+
+		assert_eq!(ValidatorSet::new_session(1), Some(vec![2, 3]));
+		assert!(rotated(1));
+	});
+}
+
+#[test]
+fn validator_set_stays_within_its_bounds() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 1),
+			Error::<Test>::TooFewValidators
+		);
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 3),
+			Error::<Test>::NotValidator
+		);
+		assert_noop!(
+			ValidatorSet::add_validator(Origin::root(), 1),
+			Error::<Test>::AlreadyValidator
+		);
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 3));
+		assert_noop!(
+			ValidatorSet::add_validator(Origin::root(), 4),
+			Error::<Test>::TooManyValidators
+		);
+	});
+}
+
+#[test]
+fn only_the_origin_changes_validators() {
+	new_test_ext().execute_with(|| {
+		assert!(ValidatorSet::add_validator(Origin::signed(1), 3).is_err());
+		assert!(ValidatorSet::remove_validator(Origin::signed(1), 2).is_err());
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2]);
+	});
+}
@@ -0,0 +1,297 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Webhook notifications for runtime events.
+//!
+//! Accounts register an HTTP endpoint on-chain, reserving a deposit, along with the kinds of events
+//! they want to be notified of. After each imported block, the off-chain worker of nodes that
+//! enabled delivery turns the block's events into JSON notifications and posts them to the
+//! endpoints of the accounts involved that subscribed to their kind. Every body is signed with the
+//! node's `hook` key, so back-office systems can check where a notification comes from.
+
+pub use pallet::*;
+
+use sp_core::crypto::KeyTypeId;
+use sp_std::vec::Vec;
+
+/// Key type of the keys signing the webhook bodies.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"hook");
+
+/// Persistent off-chain storage key enabling delivery on a node when set to any value.
+pub const DELIVERY_ENABLED_KEY: &[u8] = b"webhooks::enabled";
+
+/// How long the off-chain worker waits for an endpoint to accept a notification.
+const DELIVERY_TIMEOUT_MS: u64 = 5_000;
+
+/// The crypto of the webhook signing keys.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_core::sr25519::Signature as Sr25519Signature;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		traits::Verify,
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	pub struct WebhookAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for WebhookAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+
+	impl frame_system::offchain::AppCrypto<<Sr25519Signature as Verify>::Signer, Sr25519Signature>
+		for WebhookAuthId
+	{
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// Turns runtime events into webhook notifications.
+pub trait EventNotification<Event, AccountId, Kind> {
+	/// The kind of `event`, the accounts to notify of it and the JSON body, or `None` if nobody is
+	/// notified.
+	fn notification(event: &Event) -> Option<(Kind, Vec<AccountId>, Vec<u8>)>;
+}
+
+/// Lowercase hex encoding with a `0x` prefix.
+pub fn to_hex(bytes: &[u8]) -> Vec<u8> {
+	const DIGITS: &[u8] = b"0123456789abcdef";
+	let mut hex = Vec::with_capacity(2 + bytes.len() * 2);
+	hex.extend_from_slice(b"0x");
+	for byte in bytes {
+		hex.push(DIGITS[(byte >> 4) as usize]);
+		hex.push(DIGITS[(byte & 0xf) as usize]);
+	}
+	hex
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::{to_hex, EventNotification, DELIVERY_ENABLED_KEY, DELIVERY_TIMEOUT_MS};
+	use codec::Encode;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ReservableCurrency},
+	};
+	use frame_system::{
+		offchain::{AppCrypto, SignMessage, Signer, SigningTypes},
+		pallet_prelude::*,
+	};
+	use sp_runtime::{
+		offchain::{http, Duration, StorageKind},
+		traits::Zero,
+	};
+
+	type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	// Struct for holding the endpoint of an account.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Webhook<T: Config> {
+		pub url: BoundedVec<u8, T::MaxUrlLength>,
+		/// The kinds of events notified, every kind if empty.
+		pub events: BoundedVec<T::EventKind, T::MaxEventKinds>,
+		/// The deposit reserved from the account.
+		pub deposit: BalanceOf<T>,
+	}
+
+	/// The storage version of the pallet.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types it depends on.
+	#[pallet::config]
+	pub trait Config: SigningTypes + frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The identifier type for the webhook signing keys.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// The currency the endpoint deposits are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The kinds of events accounts can subscribe to.
+		type EventKind: Parameter + MaxEncodedLen;
+
+		/// Which events are delivered, to whom, and in which form.
+		type Notifications: EventNotification<
+			<Self as frame_system::Config>::Event,
+			Self::AccountId,
+			Self::EventKind,
+		>;
+
+		/// The deposit reserved for an endpoint.
+		#[pallet::constant]
+		type EndpointDeposit: Get<BalanceOf<Self>>;
+
+		/// The maximum length of an endpoint URL.
+		#[pallet::constant]
+		type MaxUrlLength: Get<u32>;
+
+		/// The maximum number of event kinds an endpoint subscribes to.
+		#[pallet::constant]
+		type MaxEventKinds: Get<u32>;
+	}
+
+	// Errors.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The endpoint is not an `http://` or `https://` URL.
+		InvalidUrl,
+		/// The account has no endpoint registered.
+		NoEndpoint,
+		/// The account cannot reserve the deposit.
+		NotEnoughBalance,
+	}
+
+	// Events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An account registered or replaced its endpoint. \[who\]
+		EndpointSet(T::AccountId),
+		/// An account removed its endpoint. \[who\]
+		EndpointCleared(T::AccountId),
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn endpoints)]
+	/// The endpoint notifications for an account are posted to.
+	pub(super) type Endpoints<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Webhook<T>>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Self::on_chain_storage_version() >= STORAGE_VERSION {
+				return 0
+			}
+
+			// Endpoints registered before deposits keep every kind of event and reserve nothing.
+			let mut count = 0;
+			<Endpoints<T>>::translate::<BoundedVec<u8, T::MaxUrlLength>, _>(|_, url| {
+				count += 1;
+				Some(Webhook { url, events: Default::default(), deposit: Zero::zero() })
+			});
+			STORAGE_VERSION.put::<Self>();
+
+			T::DbWeight::get().reads_writes(count + 1, count + 1)
+		}
+
+		fn offchain_worker(_n: T::BlockNumber) {
+			if sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, DELIVERY_ENABLED_KEY)
+				.is_none()
+			{
+				return
+			}
+
+			let signer = Signer::<T, T::AuthorityId>::any_account();
+			for record in <frame_system::Pallet<T>>::read_events_no_consensus() {
+				let (kind, recipients, body) = match T::Notifications::notification(&record.event) {
+					Some(notification) => notification,
+					None => continue,
+				};
+				let signature = match signer.sign_message(&body) {
+					Some((_, signature)) => signature,
+					None => {
+						log::warn!("No `hook` key in the keystore, cannot sign webhooks.");
+						return
+					},
+				};
+
+				for who in recipients {
+					let webhook = match Self::endpoints(&who) {
+						Some(webhook) => webhook,
+						None => continue,
+					};
+					if webhook.events.is_empty() || webhook.events.contains(&kind) {
+						if let Err(e) = Self::deliver(&webhook.url, &body, &signature.encode()) {
+							log::warn!("Failed to deliver a webhook to {:?}: {:?}", who, e);
+						}
+					}
+				}
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register the endpoint notifications for the sender are posted to, and the kinds of
+		/// events to notify, every kind if `events` is empty. Replaces the previous endpoint.
+		///
+		/// `EndpointDeposit` is reserved from the sender.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn set_endpoint(
+			origin: OriginFor<T>,
+			url: BoundedVec<u8, T::MaxUrlLength>,
+			events: BoundedVec<T::EventKind, T::MaxEventKinds>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				url.starts_with(b"https://") || url.starts_with(b"http://"),
+				<Error<T>>::InvalidUrl
+			);
+			ensure!(sp_std::str::from_utf8(&url).is_ok(), <Error<T>>::InvalidUrl);
+
+			let deposit = T::EndpointDeposit::get();
+			let reserved = Self::endpoints(&who).map_or_else(Zero::zero, |webhook| webhook.deposit);
+			if deposit > reserved {
+				T::Currency::reserve(&who, deposit - reserved)
+					.map_err(|_| <Error<T>>::NotEnoughBalance)?;
+			} else {
+				T::Currency::unreserve(&who, reserved - deposit);
+			}
+			<Endpoints<T>>::insert(&who, Webhook { url, events, deposit });
+
+			Self::deposit_event(Event::EndpointSet(who));
+			Ok(())
+		}
+
+		/// Stop receiving notifications, returning the deposit.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn clear_endpoint(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let webhook = <Endpoints<T>>::take(&who).ok_or(<Error<T>>::NoEndpoint)?;
+
+			T::Currency::unreserve(&who, webhook.deposit);
+
+			Self::deposit_event(Event::EndpointCleared(who));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn deliver(url: &[u8], body: &[u8], signature: &[u8]) -> Result<(), http::Error> {
+			let url = sp_std::str::from_utf8(url).map_err(|_| http::Error::Unknown)?;
+			let signature = to_hex(signature);
+			let signature = sp_std::str::from_utf8(&signature).map_err(|_| http::Error::Unknown)?;
+
+			let deadline =
+				sp_io::offchain::timestamp().add(Duration::from_millis(DELIVERY_TIMEOUT_MS));
+			let pending = http::Request::post(url, sp_std::vec![body])
+				.add_header("Content-Type", "application/json")
+				.add_header("X-Uni-Signature", signature)
+				.deadline(deadline)
+				.send()
+				.map_err(|_| http::Error::IoError)?;
+			let response =
+				pending.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
+
+			if !(200..300).contains(&response.code) {
+				log::warn!("Webhook endpoint answered with status {}", response.code);
+				return Err(http::Error::Unknown)
+			}
+			Ok(())
+		}
+	}
+}
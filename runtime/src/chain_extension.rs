@@ -0,0 +1,88 @@
+//! Chain extension exposing the course registry to ink! contracts.
+//!
+//! | `func_id` | Input                              | Output                         |
+//! |-----------|------------------------------------|--------------------------------|
+//! | 1         | `course_id: Hash`                  | `Option<AccountId>`, the owner |
+//! | 2         | `(to: AccountId, course_id: Hash)` | status code, see below         |
+//! | 3         | `course_id: Hash`                  | `Option<CourseYear>`           |
+//!
+//! Function 2 transfers a course on behalf of its owner. The calling contract must own the course
+//! or have been approved for it with `courseGrading.approve`. It returns `0` on success and one of
+//! the following codes when the transfer is refused:
+//!
+//! * `1`: the course does not exist,
+//! * `2`: the contract is neither the owner nor approved,
+//! * `3`: the recipient already owns the course,
+//! * `4`: the recipient cannot own more courses.
+//!
+//! The registry does not record individual grades, so function 3 reports the study year a course
+//! belongs to, which is the academic standing the chain tracks today.
+
+use crate::{AccountId, CourseGrading, Hash, MaxCoursesOwned, Runtime};
+use codec::Encode;
+use frame_support::{traits::Get, weights::Weight};
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use pallet_course_passing::{CourseInspector, Error as CourseError, WeightInfo};
+use sp_runtime::DispatchError;
+
+/// The chain extension registered with `pallet_contracts`.
+pub struct CoursesExtension;
+
+impl ChainExtension<Runtime> for CoursesExtension {
+	fn call<E>(func_id: u32, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = Runtime>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		let mut env = env.buf_in_buf_out();
+		let read_weight: Weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+
+		match func_id {
+			1 => {
+				let course_id: Hash = env.read_as()?;
+				env.charge_weight(read_weight)?;
+
+				let owner = CourseGrading::owner_of(&course_id);
+				env.write(&owner.encode(), false, None)
+					.map_err(|_| DispatchError::Other("Failed to write the course owner"))?;
+			},
+			2 => {
+				let (to, course_id): (AccountId, Hash) = env.read_as()?;
+				// Charged as if both accounts owned as many courses as they can.
+				env.charge_weight(
+					<Runtime as pallet_course_passing::Config>::WeightInfo::transfer(
+						MaxCoursesOwned::get(),
+						MaxCoursesOwned::get(),
+					),
+				)?;
+
+				let operator = env.ext().address().clone();
+				let status = match CourseGrading::transfer_approved(&operator, &to, &course_id) {
+					Ok(()) => 0,
+					Err(CourseError::<Runtime>::CourseNotExist) => 1,
+					Err(CourseError::<Runtime>::NotApproved) => 2,
+					Err(CourseError::<Runtime>::TransferToSelf) => 3,
+					Err(CourseError::<Runtime>::ExceedMaxCourseOwned) => 4,
+					Err(e) => return Err(e.into()),
+				};
+				return Ok(RetVal::Converging(status))
+			},
+			3 => {
+				let course_id: Hash = env.read_as()?;
+				env.charge_weight(read_weight)?;
+
+				let year = CourseGrading::year_of(&course_id);
+				env.write(&year.encode(), false, None)
+					.map_err(|_| DispatchError::Other("Failed to write the course year"))?;
+			},
+			_ => {
+				log::error!("Called an unregistered chain extension `func_id`: {:}", func_id);
+				return Err(DispatchError::Other("Unimplemented func_id"))
+			},
+		}
+
+		Ok(RetVal::Converging(0))
+	}
+}
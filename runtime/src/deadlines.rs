@@ -0,0 +1,90 @@
+//! Raises the priority of academic calls as their deadline approaches, so they are not crowded
+//! out of the last blocks before it by marketplace activity.
+//!
+//! The boost grows linearly over the `BOOST_WINDOW` blocks before the deadline, up to `MAX_BOOST`
+//! in the deadline block itself, and is added to the priority from the fee.
+
+use crate::{AccountId, Assignments, BlockNumber, Call, Misconduct, System, HOURS};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, SignedExtension},
+	transaction_validity::{
+		TransactionPriority, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+	RuntimeDebug,
+};
+
+/// The number of blocks before a deadline calls are boosted in.
+pub const BOOST_WINDOW: BlockNumber = 6 * HOURS;
+
+/// The boost of calls in the deadline block, above any priority a tip can buy.
+pub const MAX_BOOST: TransactionPriority = TransactionPriority::max_value() / 2;
+
+/// Boosts the priority of assignment submissions and misconduct responses near their deadline.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, RuntimeDebug, TypeInfo)]
+pub struct PrioritizeDeadlines;
+
+impl PrioritizeDeadlines {
+	/// The deadline of `call`, if it has one.
+	fn deadline(call: &Call) -> Option<BlockNumber> {
+		match call {
+			Call::Assignments(pallet_assignments::Call::submit { assignment_id, .. }) =>
+				Assignments::assignments(assignment_id).map(|assignment| assignment.deadline),
+			Call::Assignments(pallet_assignments::Call::submit_for_group { group_id, .. }) |
+			Call::Assignments(pallet_assignments::Call::attest { group_id }) =>
+				Assignments::groups(group_id)
+					.and_then(|group| Assignments::assignments(group.assignment_id))
+					.map(|assignment| assignment.deadline),
+			Call::Misconduct(pallet_misconduct::Call::respond { case_id, .. }) =>
+				Misconduct::cases(case_id).map(|case| case.response_deadline),
+			_ => None,
+		}
+	}
+
+	/// The boost of a call due at `deadline`, nothing once it passed or outside the window.
+	fn boost(deadline: BlockNumber, now: BlockNumber) -> TransactionPriority {
+		match deadline.checked_sub(now) {
+			Some(remaining) if remaining < BOOST_WINDOW => {
+				let elapsed = (BOOST_WINDOW - remaining) as TransactionPriority;
+				MAX_BOOST / BOOST_WINDOW as TransactionPriority * elapsed
+			},
+			_ => 0,
+		}
+	}
+}
+
+impl SignedExtension for PrioritizeDeadlines {
+	const IDENTIFIER: &'static str = "PrioritizeDeadlines";
+	type AccountId = AccountId;
+	type Call = Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		_who: &AccountId,
+		call: &Call,
+		_info: &DispatchInfoOf<Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		let priority = Self::deadline(call)
+			.map_or(0, |deadline| Self::boost(deadline, System::block_number()));
+		Ok(ValidTransaction { priority, ..Default::default() })
+	}
+
+	// The priority only matters in the pool.
+	fn pre_dispatch(
+		self,
+		_who: &AccountId,
+		_call: &Call,
+		_info: &DispatchInfoOf<Call>,
+		_len: usize,
+	) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+}
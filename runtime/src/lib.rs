@@ -6,17 +6,31 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+mod chain_extension;
+mod deadlines;
+mod mints;
+mod notifications;
+mod registration;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::{fungible::ItemOf, EnsureOrigin};
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
 };
 use sp_api::impl_runtime_apis;
-use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+use sp_core::{
+	crypto::KeyTypeId,
+	u32_trait::{_1, _2, _3},
+	OpaqueMetadata,
+};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
+	traits::{
+		AccountIdLookup, BlakeTwo256, Block as BlockT, ConvertInto, Hash as HashT, IdentifyAccount,
+		NumberFor, OpaqueKeys, SaturatedConversion, StaticLookup, Verify,
+	},
 	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, MultiSignature,
+	ApplyExtrinsicResult, DispatchError, MultiSignature, RuntimeDebug,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -26,22 +40,43 @@ use sp_version::RuntimeVersion;
 // A few exports that help ease life for downstream crates.
 pub use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{ConstU128, ConstU32, ConstU8, KeyOwnerProofSystem, Randomness, StorageInfo},
+	traits::{
+		ConstBool, ConstU128, ConstU32, ConstU8, Contains, EqualPrivilegeOnly, Get,
+		KeyOwnerProofSystem, Nothing, Randomness, StorageInfo,
+	},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use pallet_balances::Call as BalancesCall;
+use pallet_course_passing::CourseYear;
 pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::CurrencyAdapter;
+use scale_info::TypeInfo;
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
 pub use sp_runtime::{Perbill, Permill};
 
+pub use pallet_airdrop;
+pub use pallet_assignments;
+pub use pallet_budgets;
+pub use pallet_catalog_referenda;
+pub use pallet_commit_reveal;
 /// Import the template pallet.
 pub use pallet_course_passing;
+pub use pallet_did;
+pub use pallet_enactment;
+pub use pallet_maintenance;
+pub use pallet_misconduct;
+pub use pallet_office_hours;
+pub use pallet_parameters;
+pub use pallet_price_oracle;
+pub use pallet_referrals;
+pub use pallet_upgrades;
+pub use pallet_validator_set;
+pub use pallet_webhooks;
 pub use proof_of_existence;
 
 /// An index to a block.
@@ -81,7 +116,7 @@ pub mod opaque {
 
 	impl_opaque_keys! {
 		pub struct SessionKeys {
-			pub aura: Aura,
+			pub babe: Babe,
 			pub grandpa: Grandpa,
 		}
 	}
@@ -99,17 +134,17 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 3,
 	state_version: 1,
 };
 
 /// This determines the average expected block time that we are targeting.
 /// Blocks will be produced at a minimum duration defined by `SLOT_DURATION`.
 /// `SLOT_DURATION` is picked up by `pallet_timestamp` which is in turn picked
-/// up by `pallet_aura` to implement `fn slot_duration()`.
+/// up by `pallet_babe` to implement `fn slot_duration()`.
 ///
 /// Change this to adjust the block time.
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
@@ -123,6 +158,35 @@ pub const MINUTES: BlockNumber = 60_000 / (MILLISECS_PER_BLOCK as BlockNumber);
 pub const HOURS: BlockNumber = MINUTES * 60;
 pub const DAYS: BlockNumber = HOURS * 24;
 
+// Currency units, for pricing storage used by contracts.
+pub const MILLICENTS: Balance = 1_000_000_000;
+pub const CENTS: Balance = 1_000 * MILLICENTS;
+pub const DOLLARS: Balance = 100 * CENTS;
+
+/// The deposit held for `items` storage entries taking `bytes` bytes in total.
+pub const fn deposit(items: u32, bytes: u32) -> Balance {
+	items as Balance * 15 * CENTS + (bytes as Balance) * 6 * CENTS
+}
+
+// NOTE: Currently it is not possible to change the epoch duration after the chain has started.
+//       Attempting to do so will brick block production.
+pub const EPOCH_DURATION_IN_BLOCKS: BlockNumber = 10 * MINUTES;
+pub const EPOCH_DURATION_IN_SLOTS: u64 = {
+	const SLOT_FILL_RATE: f64 = MILLISECS_PER_BLOCK as f64 / SLOT_DURATION as f64;
+
+	(EPOCH_DURATION_IN_BLOCKS as f64 * SLOT_FILL_RATE) as u64
+};
+
+/// The BABE epoch configuration at genesis.
+///
+/// With `c = (1, 4)` a validator has a 1 in 4 chance of winning a primary VRF slot; the
+/// remaining slots are filled by secondary VRF slots so that block production stays regular.
+pub const BABE_GENESIS_EPOCH_CONFIG: sp_consensus_babe::BabeEpochConfiguration =
+	sp_consensus_babe::BabeEpochConfiguration {
+		c: (1, 4),
+		allowed_slots: sp_consensus_babe::AllowedSlots::PrimaryAndSecondaryVRFSlots,
+	};
+
 /// The version information used to identify this runtime when compiled natively.
 #[cfg(feature = "std")]
 pub fn native_version() -> NativeVersion {
@@ -142,11 +206,32 @@ parameter_types! {
 	pub const SS58Prefix: u8 = 42;
 }
 
+/// Rejects the calls disabled by `Maintenance`. The calls of the core and governance pallets can't
+/// be disabled, so blocks keep being produced and maintenance can always be lifted.
+pub struct BaseFilter;
+
+impl Contains<Call> for BaseFilter {
+	fn contains(call: &Call) -> bool {
+		match call {
+			Call::System(_) |
+			Call::Timestamp(_) |
+			Call::Senate(_) |
+			Call::TechnicalCommittee(_) |
+			Call::Maintenance(_) => true,
+			// Department spends are proposed through `Budgets`, bounties through `Bounties`.
+			Call::Treasury(pallet_treasury::Call::propose_spend { .. }) => false,
+			// Only the Senate creates assets, with `force_create`.
+			Call::Assets(pallet_assets::Call::create { .. }) => false,
+			_ => <Maintenance as Contains<Call>>::contains(call),
+		}
+	}
+}
+
 // Configure FRAME pallets to include in runtime.
 
 impl frame_system::Config for Runtime {
 	/// The basic call filter to use in dispatchable.
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = BaseFilter;
 	/// Block & extrinsics weights: base values and limits.
 	type BlockWeights = BlockWeights;
 	/// The maximum length of a block (in bytes).
@@ -196,14 +281,58 @@ impl frame_system::Config for Runtime {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
-impl pallet_randomness_collective_flip::Config for Runtime {}
+parameter_types! {
+	pub const EpochDuration: u64 = EPOCH_DURATION_IN_SLOTS;
+	pub const ExpectedBlockTime: u64 = MILLISECS_PER_BLOCK;
+}
 
-impl pallet_aura::Config for Runtime {
-	type AuthorityId = AuraId;
-	type DisabledValidators = ();
+impl pallet_babe::Config for Runtime {
+	type EpochDuration = EpochDuration;
+	type ExpectedBlockTime = ExpectedBlockTime;
+	/// Epochs are changed by the session pallet on session boundaries.
+	type EpochChangeTrigger = pallet_babe::ExternalTrigger;
+	type DisabledValidators = Session;
+
+	type KeyOwnerProofSystem = ();
+
+	type KeyOwnerProof = <Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(
+		KeyTypeId,
+		pallet_babe::AuthorityId,
+	)>>::Proof;
+
+	type KeyOwnerIdentification = <Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(
+		KeyTypeId,
+		pallet_babe::AuthorityId,
+	)>>::IdentificationTuple;
+
+	type HandleEquivocation = ();
+
+	type WeightInfo = ();
 	type MaxAuthorities = ConstU32<32>;
 }
 
+impl pallet_validator_set::Config for Runtime {
+	type Event = Event;
+	/// Validators join and leave the consortium by a Senate majority.
+	type AddRemoveOrigin = EnsureSenateMajority;
+	type MinValidators = ConstU32<1>;
+	type MaxValidators = ConstU32<32>;
+}
+
+impl pallet_session::Config for Runtime {
+	type Event = Event;
+	type ValidatorId = <Self as frame_system::Config>::AccountId;
+	type ValidatorIdOf = ConvertInto;
+	/// A session lasts exactly one BABE epoch.
+	type ShouldEndSession = Babe;
+	type NextSessionRotation = Babe;
+	/// The consortium validator set, changes to which are enacted on session boundaries.
+	type SessionManager = ValidatorSet;
+	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = opaque::SessionKeys;
+	type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
+}
+
 impl pallet_grandpa::Config for Runtime {
 	type Event = Event;
 	type Call = Call;
@@ -231,7 +360,7 @@ parameter_types! {
 impl pallet_timestamp::Config for Runtime {
 	/// A timestamp: milliseconds since the unix epoch.
 	type Moment = u64;
-	type OnTimestampSet = Aura;
+	type OnTimestampSet = Babe;
 	type MinimumPeriod = MinimumPeriod;
 	type WeightInfo = ();
 }
@@ -262,22 +391,672 @@ impl pallet_transaction_payment::Config for Runtime {
 	type FeeMultiplierUpdate = ();
 }
 
-impl pallet_sudo::Config for Runtime {
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * BlockWeights::get().max_block;
+	/// Calls whose preimage is missing at enactment are retried every minute.
+	pub const NoPreimagePostponement: Option<BlockNumber> = Some(10);
+}
+
+impl pallet_scheduler::Config for Runtime {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	/// Only root calls, such as enacted upgrades, can schedule further calls.
+	type ScheduleOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxScheduledPerBlock = ConstU32<50>;
+	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type PreimageProvider = Preimage;
+	type NoPreimagePostponement = NoPreimagePostponement;
+}
+
+impl pallet_preimage::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	/// Preimages requested by the scheduler are kept until enacted, others are freed by root.
+	type ManagerOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxSize = ConstU32<{ 4 * 1024 * 1024 }>;
+	type BaseDeposit = ConstU128<{ deposit(2, 64) }>;
+	type ByteDeposit = ConstU128<{ deposit(0, 1) }>;
+	type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_enactment::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	/// Root calls are as powerful as upgrades, and take the same two thirds of the Senate.
+	type ScheduleOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, SenateCollective>;
+	type CancelOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, TechnicalCollective>;
+	type EnactmentDelay = ConstU32<{ 7 * DAYS }>;
+}
+
+impl pallet_vesting::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = ConstU128<{ DOLLARS }>;
+	type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
+	const MAX_VESTING_SCHEDULES: u32 = 28;
+}
+
+/// The Senate, one seat per member university.
+type SenateCollective = pallet_collective::Instance1;
+
+/// A strict majority of the Senate.
+pub type EnsureSenateMajority =
+	pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, SenateCollective>;
+
+impl pallet_collective::Config<SenateCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = ConstU32<{ 3 * DAYS }>;
+	type MaxProposals = ConstU32<100>;
+	type MaxMembers = ConstU32<100>;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+/// The technical committee, maintainers able to fast-track emergency fixes.
+type TechnicalCollective = pallet_collective::Instance2;
+
+impl pallet_collective::Config<TechnicalCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = ConstU32<DAYS>;
+	type MaxProposals = ConstU32<100>;
+	type MaxMembers = ConstU32<20>;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+/// The appeals committee, resolving grade appeals and misconduct cases.
+type AppealsCollective = pallet_collective::Instance3;
+
+/// A strict majority of the appeals committee.
+pub type EnsureAppealsMajority =
+	pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, AppealsCollective>;
+
+impl pallet_collective::Config<AppealsCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = ConstU32<{ 7 * DAYS }>;
+	type MaxProposals = ConstU32<100>;
+	type MaxMembers = ConstU32<9>;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+/// The members of the appeals committee, elected by the Senate each academic year.
+type AppealsMembership = pallet_membership::Instance1;
+
+impl pallet_membership::Config<AppealsMembership> for Runtime {
+	type Event = Event;
+	type AddOrigin = EnsureSenateMajority;
+	type RemoveOrigin = EnsureSenateMajority;
+	type SwapOrigin = EnsureSenateMajority;
+	/// The yearly election replaces the whole committee.
+	type ResetOrigin = EnsureSenateMajority;
+	type PrimeOrigin = EnsureSenateMajority;
+	type MembershipInitialized = AppealsCommittee;
+	type MembershipChanged = AppealsCommittee;
+	type MaxMembers = ConstU32<9>;
+	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_maintenance::Config for Runtime {
+	type Event = Event;
+	/// Calls are disabled and enabled by two thirds of the technical committee.
+	type ControlOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, TechnicalCollective>;
+}
+
+impl pallet_upgrades::Config for Runtime {
 	type Event = Event;
 	type Call = Call;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	/// Upgrades are scheduled and cancelled by two thirds of the Senate.
+	type ProposeOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, SenateCollective>;
+	/// Fast-tracking takes two thirds of the technical committee.
+	type FastTrackOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, TechnicalCollective>;
+	type EnactmentDelay = ConstU32<{ 7 * DAYS }>;
+	type FastTrackDelay = ConstU32<HOURS>;
+}
+
+/// Randomness backed by BABE's VRF output of the epoch before last.
+///
+/// During the first epochs after genesis it is the randomness BABE was started with, which is
+/// known in advance, so courses minted then mix it with the commit-reveal seed below.
+pub type BabeRandomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
+
+/// Course DNA randomness mixing BABE's with the latest commit-reveal seed, so neither the block
+/// producers nor the participants of the commit-reveal rounds choose it alone.
+pub struct CourseDnaRandomness;
+
+impl Randomness<Hash, BlockNumber> for CourseDnaRandomness {
+	fn random(subject: &[u8]) -> (Hash, BlockNumber) {
+		let (babe, babe_block) = BabeRandomness::random(subject);
+		let (seed, seed_block) = CommitReveal::random(subject);
+		(BlakeTwo256::hash_of(&(babe, seed)), babe_block.max(seed_block))
+	}
+}
+
+/// The index of the current session, for the course mint quotas.
+pub struct CurrentSession;
+
+impl Get<u32> for CurrentSession {
+	fn get() -> u32 {
+		Session::current_index()
+	}
+}
+
+/// The parameters of the course economy the Senate can tune without a runtime upgrade.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum RuntimeParameter {
+	/// The number of courses an account can create, breed or clone per session.
+	MaxMintsPerSession,
+	/// The number of courses an account can own, at most `MaxCoursesOwned`.
+	CoursesOwnedLimit,
+	/// The blocks a course must be owned for before it can be bred.
+	MinBreedingAge,
+	/// The blocks a course cannot be transferred for after changing hands.
+	TransferCooldown,
+	/// The sale price from which the proceeds of a sale vest to the seller.
+	VestingThreshold,
+}
+
+impl pallet_parameters::ParameterSet for RuntimeParameter {
+	fn bounds(&self) -> (u128, u128) {
+		match self {
+			Self::MaxMintsPerSession => (1, 100),
+			Self::CoursesOwnedLimit => (1, MaxCoursesOwned::get().into()),
+			Self::MinBreedingAge => (0, (30 * DAYS).into()),
+			Self::TransferCooldown => (0, (7 * DAYS).into()),
+			Self::VestingThreshold => (DOLLARS, 10_000 * DOLLARS),
+		}
+	}
+
+	fn default_value(&self) -> u128 {
+		match self {
+			Self::MaxMintsPerSession => 10,
+			Self::CoursesOwnedLimit => MaxCoursesOwned::get().into(),
+			Self::MinBreedingAge => (7 * DAYS).into(),
+			Self::TransferCooldown => DAYS.into(),
+			Self::VestingThreshold => 100 * DOLLARS,
+		}
+	}
 }
 
+impl pallet_parameters::Config for Runtime {
+	type Event = Event;
+	type Key = RuntimeParameter;
+	type SetOrigin = EnsureSenateMajority;
+}
+
+parameter_types! {
+	pub const MaxMintsPerSessionKey: RuntimeParameter = RuntimeParameter::MaxMintsPerSession;
+	pub const CoursesOwnedLimitKey: RuntimeParameter = RuntimeParameter::CoursesOwnedLimit;
+	pub const MinBreedingAgeKey: RuntimeParameter = RuntimeParameter::MinBreedingAge;
+	pub const TransferCooldownKey: RuntimeParameter = RuntimeParameter::TransferCooldown;
+	pub const VestingThresholdKey: RuntimeParameter = RuntimeParameter::VestingThreshold;
+}
+
+/// The value of a `RuntimeParameter` set through `pallet_parameters`.
+type Param<K, V> = pallet_parameters::ParameterOf<Runtime, K, V>;
+
 /// Configure the pallet-course_passing in pallets/course_passing.
 
 parameter_types! {
 	pub const MaxCoursesOwned: u32 = 80;
+	/// Covers the course and its entry in the owner's list.
+	pub const CourseDeposit: Balance = deposit(2, 100);
+	/// The whole deposit is returned for courses burned within a term, half of it afterwards.
+	pub CourseRefunds: pallet_course_passing::RefundSchedule<BlockNumber> =
+		pallet_course_passing::RefundSchedule {
+			full_refund_period: 16 * 7 * DAYS,
+			late_refund: Perbill::from_percent(50),
+		};
+	/// Bids on second-price listings that are not revealed forfeit a tenth of their reserve.
+	pub const UnrevealedForfeit: Perbill = Perbill::from_percent(10);
 }
 
 impl pallet_course_passing::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type MaxCoursesOwned = MaxCoursesOwned;
-	type CourseRandomness = RandomnessCollectiveFlip;
+	type CoursesOwnedLimit = Param<CoursesOwnedLimitKey, u32>;
+	type CourseDeposit = CourseDeposit;
+	type DepositRefundSchedule = CourseRefunds;
+	type DepositPerByte = ConstU128<{ deposit(0, 1) }>;
+	type MaxCoursesPerYear = ConstU32<20>;
+	type MaxMintsPerSession = Param<MaxMintsPerSessionKey, u32>;
+	type CurrentSession = CurrentSession;
+	type CourseRandomness = CourseDnaRandomness;
+	type BreedSameYearOnly = ConstBool<true>;
+	type MinBreedingAge = Param<MinBreedingAgeKey, BlockNumber>;
+	type MaxCidLength = ConstU32<64>;
+	type MaxMetadataHistory = ConstU32<16>;
+	type MaxPendingPins = ConstU32<32>;
+	type MaxRecentSales = ConstU32<32>;
+	type MaxRecentTrades = ConstU32<100>;
+	/// A day of 10 minute sessions.
+	type MaxStatsSessions = ConstU32<144>;
+	type MaxBundleSize = ConstU32<16>;
+	type TransferCooldown = Param<TransferCooldownKey, BlockNumber>;
+	/// There is no student registry yet, anyone can buy courses.
+	type BuyerFilter = frame_support::traits::Everything;
+	type ForceOrigin = EnsureSenateMajority;
+	/// Trading is halted by two thirds of the technical committee.
+	type PauseOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, TechnicalCollective>;
+	type OfferExpiry = ConstU32<{ 7 * DAYS }>;
+	/// Every migration of the course storage, so that a chain at any older storage version is
+	/// brought to the current one.
+	type Migration = (
+		pallet_course_passing::migrations::CountOwnedCourses<Runtime>,
+		pallet_course_passing::migrations::CountMarketStats<Runtime>,
+		pallet_course_passing::migrations::ExtendDna<Runtime>,
+		pallet_course_passing::migrations::CountYearStats<Runtime>,
+		pallet_course_passing::migrations::AddBlobsRoot<Runtime>,
+	);
+	type OnTransfer = ();
+	/// A purchase is the paid action settling a referral.
+	type OnPurchase = ReferralPurchases;
+	type Vesting = Vesting;
+	/// Sales of a hundred dollars or more by default vest to the seller over a month.
+	type VestingThreshold = Param<VestingThresholdKey, Balance>;
+	type ProceedsVestingPeriod = ConstU32<{ 30 * DAYS }>;
+	type StableCurrency = ItemOf<Assets, ConstU32<UNI_EUR>, AccountId>;
+	/// Sealed bids are taken for an hour and revealed in the next one.
+	type SealedCommitPeriod = ConstU32<HOURS>;
+	type SealedRevealPeriod = ConstU32<HOURS>;
+	type UnrevealedForfeit = UnrevealedForfeit;
+	/// Covers the watcher's entry in the watchlist of the course.
+	type WatchDeposit = ConstU128<{ deposit(0, 64) }>;
+	type MaxWatchers = ConstU32<16>;
+	type MaxArchiveBatch = ConstU32<64>;
+	type MaxBlobLen = ConstU32<{ 16 * 1024 }>;
+	type WeightInfo = pallet_course_passing::weights::SubstrateWeight<Runtime>;
+}
+
+/// Reports course purchases to `pallet_referrals` as paid actions of the buyer.
+pub struct ReferralPurchases;
+
+impl pallet_course_passing::PurchaseHandler<AccountId, Balance> for ReferralPurchases {
+	fn on_purchase(buyer: &AccountId, _seller: &AccountId, _price: Balance) {
+		Referrals::note_paid_action(buyer);
+	}
+}
+
+/// Mints the courses claimed from `pallet_airdrop` drops, one collection per `CourseYear`.
+pub struct AirdropCourses;
+
+impl pallet_airdrop::CourseMinter<AccountId, CourseYear, Hash> for AirdropCourses {
+	fn mint(to: &AccountId, funder: &AccountId, year: &CourseYear) -> Result<Hash, DispatchError> {
+		CourseGrading::mint_funded(to, funder, year.clone()).map_err(Into::into)
+	}
+}
+
+impl pallet_airdrop::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type Collection = CourseYear;
+	type Courses = AirdropCourses;
+	/// The issuer funds the deposit every claimed course holds.
+	type DepositPerCourse = CourseDeposit;
+	type MaxDropSize = ConstU32<10_000>;
+	type MaxDropDuration = ConstU32<{ 16 * 7 * DAYS }>;
+}
+
+impl pallet_identity::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BasicDeposit = ConstU128<{ deposit(1, 258) }>;
+	type FieldDeposit = ConstU128<{ deposit(0, 66) }>;
+	type SubAccountDeposit = ConstU128<{ deposit(1, 53) }>;
+	type MaxSubAccounts = ConstU32<100>;
+	type MaxAdditionalFields = ConstU32<100>;
+	type MaxRegistrars = ConstU32<20>;
+	type Slashed = Treasury;
+	type ForceOrigin = EnsureSenateMajority;
+	type RegistrarOrigin = EnsureSenateMajority;
+	type WeightInfo = pallet_identity::weights::SubstrateWeight<Runtime>;
+}
+
+/// The asset ID of UNI-EUR, the stablecoin of the university, created at genesis.
+pub const UNI_EUR: u32 = 1;
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	/// Assets are issued by the university, not by its students.
+	type ForceOrigin = EnsureSenateMajority;
+	type AssetDeposit = ConstU128<{ 100 * DOLLARS }>;
+	type MetadataDepositBase = ConstU128<{ deposit(1, 68) }>;
+	type MetadataDepositPerByte = ConstU128<{ deposit(0, 1) }>;
+	type ApprovalDeposit = ConstU128<{ 10 * CENTS }>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_recovery::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	/// Deposits are kept low, students set recovery up with little balance.
+	type ConfigDepositBase = ConstU128<{ DOLLARS }>;
+	type FriendDepositFactor = ConstU128<{ 10 * CENTS }>;
+	/// Enough for a handful of friends and the registrar.
+	type MaxFriends = frame_support::traits::ConstU16<9>;
+	/// Slashed from a rescuer whose recovery the lost account closes, deterring false claims.
+	type RecoveryDeposit = ConstU128<{ DOLLARS }>;
+}
+
+/// The registrar index of the university, the first registrar the Senate adds.
+pub const UNIVERSITY_REGISTRAR: pallet_identity::RegistrarIndex = 0;
+
+/// Whether the university judged the identity of `who` to be `Reasonable` or `KnownGood`.
+pub fn is_verified(who: &AccountId) -> bool {
+	Identity::identity(who).map_or(false, |registration| {
+		registration.judgements.iter().any(|(registrar, judgement)| {
+			*registrar == UNIVERSITY_REGISTRAR &&
+				matches!(
+					judgement,
+					pallet_identity::Judgement::Reasonable | pallet_identity::Judgement::KnownGood
+				)
+		})
+	})
+}
+
+/// Members of the Senate whose identity the university verified, resolving to their account.
+pub struct EnsureVerifiedFaculty;
+
+impl EnsureOrigin<Origin> for EnsureVerifiedFaculty {
+	type Success = AccountId;
+
+	fn try_origin(origin: Origin) -> Result<AccountId, Origin> {
+		let who =
+			pallet_collective::EnsureMember::<AccountId, SenateCollective>::try_origin(origin)?;
+		if is_verified(&who) {
+			Ok(who)
+		} else {
+			Err(pallet_collective::RawOrigin::<AccountId, SenateCollective>::Member(who).into())
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		pallet_collective::EnsureMember::<AccountId, SenateCollective>::successful_origin()
+	}
+}
+
+/// The roles of the voters of catalog referenda.
+pub struct VotingRoles;
+
+impl pallet_catalog_referenda::Roles<AccountId> for VotingRoles {
+	/// Verified faculty vote as professors and registered students as students. Graduation is not
+	/// recorded on-chain yet, so nobody votes as an alumnus.
+	fn role(who: &AccountId) -> Option<pallet_catalog_referenda::Role> {
+		if Senate::is_member(who) && is_verified(who) {
+			Some(pallet_catalog_referenda::Role::Professor)
+		} else if is_verified(who) {
+			Some(pallet_catalog_referenda::Role::Student)
+		} else {
+			None
+		}
+	}
+}
+
+impl pallet_catalog_referenda::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type Roles = VotingRoles;
+	type ProposeOrigin = EnsureSenateMajority;
+	type WeightOrigin = EnsureSenateMajority;
+	type VotingPeriod = ConstU32<{ 14 * DAYS }>;
+	type LockPeriod = ConstU32<{ 7 * DAYS }>;
+	type MaxVoteStake = ConstU128<{ 100 * DOLLARS }>;
+}
+
+impl pallet_commit_reveal::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	/// Withholding a secret costs ten dollars, more than a seat in a lottery is worth.
+	type CommitDeposit = ConstU128<{ 10 * DOLLARS }>;
+	type Slashed = Treasury;
+	/// A seed a day: half a day to commit, half a day to reveal.
+	type CommitPeriod = ConstU32<{ 12 * HOURS }>;
+	type RevealPeriod = ConstU32<{ 12 * HOURS }>;
+	type MaxParticipants = ConstU32<100>;
+	type MinReveals = ConstU32<3>;
+}
+
+impl pallet_assignments::Config for Runtime {
+	type Event = Event;
+	type Courses = CourseGrading;
+	/// There is no professor role yet, assignments are set by members of the Senate with an
+	/// identity verified by the university.
+	type InstructorOrigin = EnsureVerifiedFaculty;
+	type MaxGroupSize = ConstU32<8>;
+}
+
+impl pallet_office_hours::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type Courses = CourseGrading;
+	/// There is no professor role yet, office hours are held by members of the Senate.
+	type HostOrigin = pallet_collective::EnsureMember<AccountId, SenateCollective>;
+	type BookingDeposit = ConstU128<{ 10 * CENTS }>;
+	type BlocksPerWeek = ConstU32<{ 7 * DAYS }>;
+}
+
+impl pallet_misconduct::Config for Runtime {
+	type Event = Event;
+	type Courses = CourseGrading;
+	/// There is no professor or proctor role yet, cases are opened by members of the Senate.
+	type ReporterOrigin = pallet_collective::EnsureMember<AccountId, SenateCollective>;
+	type CommitteeOrigin = EnsureAppealsMajority;
+	/// There are no grades or credentials to void or block yet.
+	type OnSanction = ();
+	type ResponsePeriod = ConstU32<{ 14 * DAYS }>;
+	type MaxEvidence = ConstU32<32>;
+}
+
+parameter_types! {
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const TreasuryProposalBond: Permill = Permill::from_percent(5);
+}
+
+impl pallet_treasury::Config for Runtime {
+	type PalletId = TreasuryPalletId;
+	type Currency = Balances;
+	/// Approves bounties, the treasury's own spend proposals are filtered out.
+	type ApproveOrigin = EnsureSenateMajority;
+	type RejectOrigin = EnsureSenateMajority;
+	type Event = Event;
+	type OnSlash = Treasury;
+	type ProposalBond = TreasuryProposalBond;
+	type ProposalBondMinimum = ConstU128<{ DOLLARS }>;
+	type ProposalBondMaximum = ();
+	type SpendPeriod = ConstU32<{ 7 * DAYS }>;
+	/// Funds left over roll into the next period.
+	type Burn = ();
+	type BurnDestination = ();
+	/// Funds the approved bounties every spend period.
+	type SpendFunds = Bounties;
+	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+	type MaxApprovals = ConstU32<100>;
+}
+
+parameter_types! {
+	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
+}
+
+impl pallet_bounties::Config for Runtime {
+	type Event = Event;
+	type BountyDepositBase = ConstU128<{ DOLLARS }>;
+	/// The time the Senate has to unassign a curator that awarded a bounty in bad faith.
+	type BountyDepositPayoutDelay = ConstU32<{ DAYS }>;
+	type BountyUpdatePeriod = ConstU32<{ 30 * DAYS }>;
+	type BountyCuratorDeposit = BountyCuratorDeposit;
+	type BountyValueMinimum = ConstU128<{ 5 * DOLLARS }>;
+	type DataDepositPerByte = ConstU128<{ deposit(0, 1) }>;
+	type MaximumReasonLength = ConstU32<16384>;
+	type ChildBountyManager = ();
+	type WeightInfo = pallet_bounties::weights::SubstrateWeight<Runtime>;
+}
+
+/// The account of the treasury, paying the spends of `pallet_budgets`.
+pub struct TreasuryAccount;
+
+impl Get<AccountId> for TreasuryAccount {
+	fn get() -> AccountId {
+		Treasury::account_id()
+	}
+}
+
+impl pallet_budgets::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type Treasury = TreasuryAccount;
+	type BudgetOrigin = EnsureSenateMajority;
+	type ApproveOrigin = EnsureSenateMajority;
+	type ProposalBond = ConstU128<{ 5 * DOLLARS }>;
+	/// A semester.
+	type TermLength = ConstU32<{ 120 * DAYS }>;
+	type MaxDepartmentLength = ConstU32<64>;
+}
+
+parameter_types! {
+	pub const ReferralPotId: PalletId = PalletId(*b"py/refer");
+}
+
+impl pallet_referrals::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	/// The pot is funded by transfers to its account.
+	type PalletId = ReferralPotId;
+	type ReferralReward = ConstU128<{ DOLLARS }>;
+	type MaxChainDepth = ConstU32<16>;
+}
+
+impl pallet_did::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type DidDeposit = ConstU128<{ deposit(1, 1024) }>;
+	type MaxKeys = ConstU32<16>;
+	type MaxServices = ConstU32<8>;
+	type HandleDeposit = ConstU128<{ deposit(1, 64) }>;
+}
+
+/// Feeds the sales recorded by `pallet_course_passing` to the price oracle, one collection per
+/// `CourseYear`.
+pub struct CourseSales;
+
+impl pallet_price_oracle::SaleHistory<CourseYear, Balance> for CourseSales {
+	fn collections() -> Vec<CourseYear> {
+		vec![CourseYear::First, CourseYear::Second, CourseYear::Third, CourseYear::Fourth]
+	}
+
+	fn recent_sales(year: &CourseYear) -> Vec<Balance> {
+		CourseGrading::recent_sales(year).into_inner()
+	}
+}
+
+impl pallet_price_oracle::Config for Runtime {
+	type Event = Event;
+	type AuthorityId = pallet_price_oracle::crypto::OracleAuthId;
+	type Collection = CourseYear;
+	type Balance = Balance;
+	type Sales = CourseSales;
+	/// Validators run the oracle off-chain workers.
+	type Oracles = ValidatorSet;
+	/// Floor prices are refreshed once per epoch.
+	type UpdateInterval = ConstU32<EPOCH_DURATION_IN_BLOCKS>;
+}
+
+impl pallet_webhooks::Config for Runtime {
+	type Event = Event;
+	type AuthorityId = pallet_webhooks::crypto::WebhookAuthId;
+	type Currency = Balances;
+	type EventKind = notifications::NotificationKind;
+	type Notifications = notifications::MarketplaceNotifications;
+	type EndpointDeposit = ConstU128<{ deposit(1, 300) }>;
+	type MaxUrlLength = ConstU32<256>;
+	type MaxEventKinds = ConstU32<8>;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Index,
+	) -> Option<(Call, <UncheckedExtrinsic as sp_runtime::traits::Extrinsic>::SignaturePayload)> {
+		let tip = 0;
+		// Take the biggest period possible.
+		let period =
+			BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2) as u64;
+		let current_block = System::block_number()
+			.saturated_into::<u64>()
+			// The `System::block_number` is initialized with `n+1`,
+			// so the actual block number is `n`.
+			.saturating_sub(1);
+		let era = generic::Era::mortal(period, current_block);
+		let extra = (
+			frame_system::CheckNonZeroSender::<Runtime>::new(),
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(era),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+			registration::CheckStudentRegistration,
+			deadlines::PrioritizeDeadlines,
+			mints::DeduplicateMints,
+		);
+		let raw_payload = SignedPayload::new(call, extra)
+			.map_err(|e| {
+				log::warn!("Unable to create signed payload: {:?}", e);
+			})
+			.ok()?;
+		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+		let address = <Runtime as frame_system::Config>::Lookup::unlookup(account);
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (address, signature.into(), extra)))
+	}
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	Call: From<C>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = UncheckedExtrinsic;
 }
 
 impl proof_of_existence::Config for Runtime {
@@ -285,6 +1064,35 @@ impl proof_of_existence::Config for Runtime {
 	type MaxBytesInHash = frame_support::traits::ConstU32<64>;
 }
 
+parameter_types! {
+	pub const DepositPerItem: Balance = deposit(1, 0);
+	pub const DepositPerByte: Balance = deposit(0, 1);
+	/// Lazily remove the storage of terminated contracts with at most 10% of the block weight.
+	pub DeletionWeightLimit: Weight = Perbill::from_percent(10) * BlockWeights::get().max_block;
+	pub const DeletionQueueDepth: u32 = 128;
+	pub Schedule: pallet_contracts::Schedule<Runtime> = Default::default();
+}
+
+impl pallet_contracts::Config for Runtime {
+	type Time = Timestamp;
+	type Randomness = BabeRandomness;
+	type Currency = Balances;
+	type Event = Event;
+	type Call = Call;
+	/// Contracts cannot dispatch runtime calls, they reach the course registry through the chain
+	/// extension instead.
+	type CallFilter = Nothing;
+	type DepositPerItem = DepositPerItem;
+	type DepositPerByte = DepositPerByte;
+	type CallStack = [pallet_contracts::Frame<Self>; 31];
+	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
+	type WeightInfo = pallet_contracts::weights::SubstrateWeight<Self>;
+	type ChainExtension = chain_extension::CoursesExtension;
+	type DeletionQueueDepth = DeletionQueueDepth;
+	type DeletionWeightLimit = DeletionWeightLimit;
+	type Schedule = Schedule;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime where
@@ -293,16 +1101,45 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic
 	{
 		System: frame_system,
-		RandomnessCollectiveFlip: pallet_randomness_collective_flip,
 		Timestamp: pallet_timestamp,
-		Aura: pallet_aura,
+		Babe: pallet_babe,
+		// Must be placed before `Session` so the genesis validator set is available to it.
+		ValidatorSet: pallet_validator_set,
+		Session: pallet_session,
 		Grandpa: pallet_grandpa,
 		Balances: pallet_balances,
 		TransactionPayment: pallet_transaction_payment,
-		Sudo: pallet_sudo,
 		// Include the custom logic from the pallet-template in the runtime.
 		CourseGrading: pallet_course_passing,
-		ProofOfExistence: proof_of_existence
+		ProofOfExistence: proof_of_existence,
+		Contracts: pallet_contracts,
+		PriceOracle: pallet_price_oracle,
+		Webhooks: pallet_webhooks,
+		Scheduler: pallet_scheduler,
+		Senate: pallet_collective::<Instance1>,
+		TechnicalCommittee: pallet_collective::<Instance2>,
+		AppealsCommittee: pallet_collective::<Instance3>,
+		AppealsCommitteeMembership: pallet_membership::<Instance1>,
+		Upgrades: pallet_upgrades,
+		Maintenance: pallet_maintenance,
+		Airdrop: pallet_airdrop,
+		Did: pallet_did,
+		Assignments: pallet_assignments,
+		OfficeHours: pallet_office_hours,
+		Misconduct: pallet_misconduct,
+		Referrals: pallet_referrals,
+		Treasury: pallet_treasury,
+		Bounties: pallet_bounties,
+		Budgets: pallet_budgets,
+		Identity: pallet_identity,
+		Recovery: pallet_recovery,
+		CatalogReferenda: pallet_catalog_referenda,
+		Vesting: pallet_vesting,
+		Preimage: pallet_preimage,
+		Enactment: pallet_enactment,
+		Assets: pallet_assets,
+		CommitReveal: pallet_commit_reveal,
+		Parameters: pallet_parameters,
 	}
 );
 
@@ -322,9 +1159,14 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	registration::CheckStudentRegistration,
+	deadlines::PrioritizeDeadlines,
+	mints::DeduplicateMints,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
+/// The payload being signed in transactions.
+pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -334,6 +1176,9 @@ pub type Executive = frame_executive::Executive<
 	AllPalletsWithSystem,
 >;
 
+/// Return the debug buffer of contract calls dry-run over RPC, to ease developing contracts.
+const CONTRACTS_DEBUG_OUTPUT: bool = true;
+
 #[cfg(feature = "runtime-benchmarks")]
 #[macro_use]
 extern crate frame_benchmarking;
@@ -344,11 +1189,33 @@ mod benches {
 		[frame_benchmarking, BaselineBench::<Runtime>]
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
+		[pallet_collective, Senate]
+		[pallet_scheduler, Scheduler]
 		[pallet_timestamp, Timestamp]
-		[pallet_template, TemplateModule]
+		[pallet_course_passing, CourseGrading]
+		[pallet_contracts, Contracts]
 	);
 }
 
+/// The metadata of `course_id` served by the runtime APIs, if the course exists.
+fn course_metadata(
+	course_id: &Hash,
+) -> Option<pallet_course_passing_runtime_api::CourseMetadata<AccountId, Balance>> {
+	let course = CourseGrading::courses(course_id)?;
+	let (syllabus, materials) = match CourseGrading::course_content(course_id) {
+		Some(content) => (content.syllabus, content.materials),
+		None => (None, None),
+	};
+	Some(pallet_course_passing_runtime_api::CourseMetadata {
+		owner: course.owner,
+		year: course.course_year,
+		dna: course.dna,
+		price: CourseGrading::ask_price(course_id),
+		syllabus: syllabus.map(|cid| cid.into_inner()),
+		materials: materials.map(|cid| cid.into_inner()),
+	})
+}
+
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
@@ -407,13 +1274,49 @@ impl_runtime_apis! {
 		}
 	}
 
-	impl sp_consensus_aura::AuraApi<Block, AuraId> for Runtime {
-		fn slot_duration() -> sp_consensus_aura::SlotDuration {
-			sp_consensus_aura::SlotDuration::from_millis(Aura::slot_duration())
+	impl sp_consensus_babe::BabeApi<Block> for Runtime {
+		fn configuration() -> sp_consensus_babe::BabeGenesisConfiguration {
+			// The choice of `c` parameter (where `1 - c` represents the
+			// probability of a slot being empty), is done in accordance to the
+			// slot duration and expected target block time, for safely
+			// resisting network delays of maximum two seconds.
+			// <https://research.web3.foundation/en/latest/polkadot/BABE/Babe/#6-practical-results>
+			sp_consensus_babe::BabeGenesisConfiguration {
+				slot_duration: Babe::slot_duration(),
+				epoch_length: EpochDuration::get(),
+				c: BABE_GENESIS_EPOCH_CONFIG.c,
+				genesis_authorities: Babe::authorities().to_vec(),
+				randomness: Babe::randomness(),
+				allowed_slots: BABE_GENESIS_EPOCH_CONFIG.allowed_slots,
+			}
+		}
+
+		fn current_epoch_start() -> sp_consensus_babe::Slot {
+			Babe::current_epoch_start()
+		}
+
+		fn current_epoch() -> sp_consensus_babe::Epoch {
+			Babe::current_epoch()
+		}
+
+		fn next_epoch() -> sp_consensus_babe::Epoch {
+			Babe::next_epoch()
 		}
 
-		fn authorities() -> Vec<AuraId> {
-			Aura::authorities().into_inner()
+		fn generate_key_ownership_proof(
+			_slot: sp_consensus_babe::Slot,
+			_authority_id: sp_consensus_babe::AuthorityId,
+		) -> Option<sp_consensus_babe::OpaqueKeyOwnershipProof> {
+			// NOTE: the key owner proof system is a bottom type, see the grandpa
+			// implementation below.
+			None
+		}
+
+		fn submit_report_equivocation_unsigned_extrinsic(
+			_equivocation_proof: sp_consensus_babe::EquivocationProof<<Block as BlockT>::Header>,
+			_key_owner_proof: sp_consensus_babe::OpaqueKeyOwnershipProof,
+		) -> Option<()> {
+			None
 		}
 	}
 
@@ -480,6 +1383,220 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_contracts_rpc_runtime_api::ContractsApi<Block, AccountId, Balance, BlockNumber, Hash>
+		for Runtime
+	{
+		fn call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			gas_limit: u64,
+			storage_deposit_limit: Option<Balance>,
+			input_data: Vec<u8>,
+		) -> pallet_contracts_primitives::ContractExecResult<Balance> {
+			Contracts::bare_call(
+				origin,
+				dest,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				input_data,
+				CONTRACTS_DEBUG_OUTPUT,
+			)
+		}
+
+		fn instantiate(
+			origin: AccountId,
+			value: Balance,
+			gas_limit: u64,
+			storage_deposit_limit: Option<Balance>,
+			code: pallet_contracts_primitives::Code<Hash>,
+			data: Vec<u8>,
+			salt: Vec<u8>,
+		) -> pallet_contracts_primitives::ContractInstantiateResult<AccountId, Balance> {
+			Contracts::bare_instantiate(
+				origin,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				code,
+				data,
+				salt,
+				CONTRACTS_DEBUG_OUTPUT,
+			)
+		}
+
+		fn upload_code(
+			origin: AccountId,
+			code: Vec<u8>,
+			storage_deposit_limit: Option<Balance>,
+		) -> pallet_contracts_primitives::CodeUploadResult<Hash, Balance> {
+			Contracts::bare_upload_code(origin, code, storage_deposit_limit)
+		}
+
+		fn get_storage(
+			address: AccountId,
+			key: [u8; 32],
+		) -> pallet_contracts_primitives::GetStorageResult {
+			Contracts::get_storage(address, key)
+		}
+	}
+
+	impl pallet_course_passing_runtime_api::CourseEventsApi<Block, AccountId> for Runtime {
+		fn event_schema() -> Vec<pallet_course_passing_runtime_api::EventSchema> {
+			let topics: Vec<Vec<u8>> = pallet_course_passing::EVENT_TOPICS
+				.iter()
+				.map(|topic| topic.as_bytes().to_vec())
+				.collect();
+			pallet_course_passing::EVENT_SCHEMA
+				.iter()
+				.map(|event| pallet_course_passing_runtime_api::EventSchema {
+					name: event.name.as_bytes().to_vec(),
+					fields: event.fields.iter().map(|field| field.as_bytes().to_vec()).collect(),
+					topics: topics.clone(),
+				})
+				.collect()
+		}
+
+		fn owner_topic(who: AccountId) -> Hash {
+			CourseGrading::owner_topic(&who)
+		}
+
+		fn collection_topic(year: CourseYear) -> Hash {
+			CourseGrading::collection_topic(&year)
+		}
+
+		fn account_topic(who: AccountId) -> Hash {
+			CourseGrading::account_topic(&who)
+		}
+
+		fn course_topic(course_id: Hash) -> Hash {
+			CourseGrading::course_topic(&course_id)
+		}
+	}
+
+	impl pallet_course_passing_runtime_api::CourseMetadataApi<Block, AccountId, Balance> for Runtime {
+		fn course_metadata(
+			course_id: Hash,
+		) -> Option<pallet_course_passing_runtime_api::CourseMetadata<AccountId, Balance>> {
+			course_metadata(&course_id)
+		}
+	}
+
+	impl pallet_course_passing_runtime_api::CourseExportApi<Block, AccountId, Balance> for Runtime {
+		fn courses(
+			cursor: Option<Vec<u8>>,
+			limit: u32,
+		) -> (
+			Vec<(Hash, pallet_course_passing_runtime_api::CourseMetadata<AccountId, Balance>)>,
+			Option<Vec<u8>>,
+		) {
+			let (ids, next) = CourseGrading::course_ids(cursor, limit);
+			let courses = ids
+				.into_iter()
+				.filter_map(|course_id| Some((course_id, course_metadata(&course_id)?)))
+				.collect();
+			(courses, next)
+		}
+	}
+
+	impl pallet_course_passing_runtime_api::CourseQuotaApi<Block, AccountId> for Runtime {
+		fn remaining_mints(who: AccountId) -> u32 {
+			CourseGrading::remaining_mints(&who)
+		}
+	}
+
+	impl pallet_course_passing_runtime_api::CourseMarketApi<Block, Balance> for Runtime {
+		fn market_stats(year: CourseYear) -> pallet_course_passing::MarketSummary<Balance> {
+			CourseGrading::market_summary(&year)
+		}
+	}
+
+	impl pallet_course_passing_runtime_api::CourseTradesApi<Block, AccountId, Balance> for Runtime {
+		fn recent_trades(
+			limit: u32,
+		) -> Vec<pallet_course_passing::Trade<AccountId, Hash, Balance, BlockNumber>> {
+			CourseGrading::recent_trades(limit)
+		}
+	}
+
+	impl pallet_course_passing_runtime_api::CourseStatsApi<Block, Balance> for Runtime {
+		fn chain_stats() -> pallet_course_passing::ChainStats<Balance> {
+			CourseGrading::chain_stats()
+		}
+
+		fn year_stats(year: CourseYear) -> pallet_course_passing::YearStats<Balance> {
+			CourseGrading::year_stats(&year)
+		}
+	}
+
+	impl pallet_course_passing_runtime_api::CourseProofApi<Block> for Runtime {
+		fn course_storage(course_id: Hash) -> (Vec<u8>, Option<Vec<u8>>) {
+			CourseGrading::course_storage(&course_id)
+		}
+
+		fn course_blob(course_id: Hash, blob: pallet_course_passing::blobs::Blob) -> Option<Vec<u8>> {
+			CourseGrading::course_blob(&course_id, blob)
+		}
+	}
+
+	impl pallet_course_passing_runtime_api::CourseAuditApi<Block, AccountId> for Runtime {
+		fn audit_storage(
+			cursor: Option<Vec<u8>>,
+			limit: u32,
+		) -> pallet_course_passing::audit::AuditPage<AccountId, Hash> {
+			CourseGrading::audit_storage(cursor, limit)
+		}
+
+		fn report_storage(
+			course_year: CourseYear,
+			cursor: Option<Vec<u8>>,
+			limit: u32,
+		) -> pallet_course_passing::audit::ReportPage {
+			CourseGrading::report_storage(course_year, cursor, limit)
+		}
+	}
+
+	impl pallet_course_passing_runtime_api::CourseArchiveApi<Block, AccountId, Balance> for Runtime {
+		fn archived_course(
+			term: pallet_course_passing::Term,
+			course_id: Hash,
+		) -> Option<
+			pallet_course_passing::archive::ArchivedCourse<AccountId, Balance, BlockNumber, Hash>,
+		> {
+			CourseGrading::archived_course(term, &course_id)
+		}
+	}
+
+	impl pallet_did_runtime_api::DidHandlesApi<Block, AccountId> for Runtime {
+		fn handles(accounts: Vec<AccountId>) -> Vec<Option<Vec<u8>>> {
+			accounts
+				.iter()
+				.map(|who| Did::handles(who).map(|(handle, _)| handle.into_inner()))
+				.collect()
+		}
+
+		fn account_of(handle: Vec<u8>) -> Option<AccountId> {
+			let handle = pallet_did::Handle::try_from(handle).ok()?;
+			Did::handle_owners(handle)
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	impl frame_try_runtime::TryRuntime<Block> for Runtime {
+		fn on_runtime_upgrade() -> (Weight, Weight) {
+			// NOTE: intentional unwrap: we don't want to propagate the error backwards, and want to
+			// have a backtrace here. If any of the pre/post migration checks fail, we shall stop
+			// right here and right now.
+			let weight = Executive::try_runtime_upgrade().unwrap();
+			(weight, BlockWeights::get().max_block)
+		}
+
+		fn execute_block_no_check(block: Block) -> Weight {
+			Executive::execute_block_no_check(block)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -0,0 +1,59 @@
+//! Lets the pool hold a single `create_course` of each account per block.
+//!
+//! The mint provides a tag of its sender and the block it is validated for, so a second one for
+//! the same block is rejected by the pool, or replaces the first if it has a higher priority,
+//! instead of taking space in the block only to fail.
+
+use crate::{AccountId, Call, System};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, SignedExtension},
+	transaction_validity::{TransactionValidity, TransactionValidityError, ValidTransaction},
+	RuntimeDebug,
+};
+use sp_std::vec;
+
+/// Tags course mints with their sender and block.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, RuntimeDebug, TypeInfo)]
+pub struct DeduplicateMints;
+
+impl SignedExtension for DeduplicateMints {
+	const IDENTIFIER: &'static str = "DeduplicateMints";
+	type AccountId = AccountId;
+	type Call = Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &AccountId,
+		call: &Call,
+		_info: &DispatchInfoOf<Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		match call {
+			Call::CourseGrading(pallet_course_passing::Call::create_course { .. }) =>
+				Ok(ValidTransaction {
+					provides: vec![(b"create_course", who, System::block_number()).encode()],
+					..Default::default()
+				}),
+			_ => Ok(ValidTransaction::default()),
+		}
+	}
+
+	// The tag only matters in the pool.
+	fn pre_dispatch(
+		self,
+		_who: &AccountId,
+		_call: &Call,
+		_info: &DispatchInfoOf<Call>,
+		_len: usize,
+	) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+}
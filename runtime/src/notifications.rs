@@ -0,0 +1,102 @@
+//! Webhook notifications for marketplace events.
+//!
+//! Bodies are flat JSON objects. Accounts and course IDs are hex encoded, prices are decimal
+//! strings in the smallest balance unit, for instance:
+//!
+//! ```json
+//! {"event":"Bought","block":42,"course_id":"0x..","buyer":"0x..","seller":"0x..","price":"1000"}
+//! ```
+
+use crate::{AccountId, Event, System};
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::fmt::Write;
+use frame_support::RuntimeDebug;
+use pallet_course_passing::Event as CourseEvent;
+use pallet_webhooks::{to_hex, EventNotification};
+use scale_info::TypeInfo;
+use sp_std::{vec, vec::Vec};
+
+/// The kinds of notifications an endpoint can subscribe to.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum NotificationKind {
+	/// A course was bought.
+	Bought,
+	/// A course was transferred.
+	Transferred,
+}
+
+/// Delivers `Bought` and `Transferred` course events to both parties.
+pub struct MarketplaceNotifications;
+
+impl EventNotification<Event, AccountId, NotificationKind> for MarketplaceNotifications {
+	fn notification(event: &Event) -> Option<(NotificationKind, Vec<AccountId>, Vec<u8>)> {
+		let mut json = Json::new();
+		let (kind, recipients) = match event {
+			Event::CourseGrading(CourseEvent::Bought(buyer, seller, course_id, price)) => {
+				json.field("event", "Bought");
+				json.hex("course_id", course_id.as_ref());
+				json.hex("buyer", buyer.as_ref());
+				json.hex("seller", seller.as_ref());
+				json.field("price", price);
+				(NotificationKind::Bought, vec![buyer.clone(), seller.clone()])
+			},
+			Event::CourseGrading(CourseEvent::Transferred(from, to, course_id)) => {
+				json.field("event", "Transferred");
+				json.hex("course_id", course_id.as_ref());
+				json.hex("from", from.as_ref());
+				json.hex("to", to.as_ref());
+				(NotificationKind::Transferred, vec![from.clone(), to.clone()])
+			},
+			_ => return None,
+		};
+		json.number("block", System::block_number());
+
+		Some((kind, recipients, json.finish()))
+	}
+}
+
+/// A minimal writer for flat JSON objects with string and number values.
+struct Json(Vec<u8>);
+
+impl Json {
+	fn new() -> Self {
+		Json(b"{".to_vec())
+	}
+
+	fn key(&mut self, key: &str) {
+		if self.0.len() > 1 {
+			self.0.push(b',');
+		}
+		let _ = write!(self, "\"{}\":", key);
+	}
+
+	/// A string value. Only used for values without quotes or backslashes.
+	fn field(&mut self, key: &str, value: impl core::fmt::Display) {
+		self.key(key);
+		let _ = write!(self, "\"{}\"", value);
+	}
+
+	fn hex(&mut self, key: &str, bytes: &[u8]) {
+		self.key(key);
+		self.0.push(b'"');
+		self.0.extend_from_slice(&to_hex(bytes));
+		self.0.push(b'"');
+	}
+
+	fn number(&mut self, key: &str, value: impl core::fmt::Display) {
+		self.key(key);
+		let _ = write!(self, "{}", value);
+	}
+
+	fn finish(mut self) -> Vec<u8> {
+		self.0.push(b'}');
+		self.0
+	}
+}
+
+impl Write for Json {
+	fn write_str(&mut self, s: &str) -> core::fmt::Result {
+		self.0.extend_from_slice(s.as_bytes());
+		Ok(())
+	}
+}
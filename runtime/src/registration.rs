@@ -0,0 +1,62 @@
+//! Keeps academic calls of accounts that are not registered students out of the pool.
+//!
+//! A student is registered once the university, as registrar, judged their identity `Reasonable`
+//! or `KnownGood`, and stays registered until a later judgment takes it away. A DID alone does not
+//! register anyone, as any account can create one.
+
+use crate::{is_verified, AccountId, Call};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, SignedExtension},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+	RuntimeDebug,
+};
+
+/// The `InvalidTransaction::Custom` code of academic calls of unregistered accounts.
+pub const NOT_REGISTERED: u8 = 1;
+
+/// Rejects academic calls unless the university verified the identity of the signer.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, RuntimeDebug, TypeInfo)]
+pub struct CheckStudentRegistration;
+
+impl CheckStudentRegistration {
+	/// Whether only registered students can make `call`.
+	fn is_academic(call: &Call) -> bool {
+		matches!(
+			call,
+			Call::CourseGrading(pallet_course_passing::Call::claim_transfer { .. }) |
+				Call::Assignments(pallet_assignments::Call::submit { .. }) |
+				Call::Assignments(pallet_assignments::Call::submit_for_group { .. }) |
+				Call::Assignments(pallet_assignments::Call::attest { .. }) |
+				Call::OfficeHours(pallet_office_hours::Call::book { .. })
+		)
+	}
+}
+
+impl SignedExtension for CheckStudentRegistration {
+	const IDENTIFIER: &'static str = "CheckStudentRegistration";
+	type AccountId = AccountId;
+	type Call = Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &AccountId,
+		call: &Call,
+		_info: &DispatchInfoOf<Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		if Self::is_academic(call) && !is_verified(who) {
+			return Err(InvalidTransaction::Custom(NOT_REGISTERED).into())
+		}
+		Ok(ValidTransaction::default())
+	}
+}